@@ -31,11 +31,15 @@ bus_impl_message_all!(Deregister, Deregister, (), Registry);
 
 bus_impl_vector_request!(Nodes, Node);
 bus_impl_message_all!(GetNodes, GetNodes, Nodes, Node);
+bus_impl_message_all!(CordonNode, CordonNode, Node, Node);
+bus_impl_message_all!(UncordonNode, UncordonNode, Node, Node);
 
 bus_impl_message_all!(CreatePool, CreatePool, Pool, Pool);
 
 bus_impl_message_all!(DestroyPool, DestroyPool, (), Pool);
 
+bus_impl_message_all!(LabelPool, LabelPool, Pool, Pool);
+
 bus_impl_vector_request!(Pools, Pool);
 bus_impl_message_all!(GetPools, GetPools, Pools, Pool);
 
@@ -83,6 +87,9 @@ bus_impl_message_all!(AddVolumeNexus, AddVolumeNexus, Nexus, Volume);
 bus_impl_message_all!(RemoveVolumeNexus, RemoveVolumeNexus, (), Volume);
 
 bus_impl_message_all!(SetVolumeReplica, SetVolumeReplica, Volume, Volume);
+bus_impl_message_all!(CreateVolumeReplica, CreateVolumeReplica, Volume, Volume);
+bus_impl_message_all!(RemoveVolumeReplica, RemoveVolumeReplica, Volume, Volume);
+bus_impl_message_all!(GetVolumeCapacity, GetVolumeCapacity, VolumeCapacity, Volume);
 
 bus_impl_message_all!(JsonGrpcRequest, JsonGrpc, Value, JsonGrpc);
 
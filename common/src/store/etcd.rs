@@ -4,14 +4,15 @@ use crate::{
         definitions::{
             Connect, Delete, DeserialiseValue, Get, GetPrefix, KeyString, ObjectKey, Put,
             SerialiseValue, StorableObject, Store, StoreError, StoreError::MissingEntry, StoreKey,
-            StoreValue, ValueString, Watch, WatchEvent,
+            StoreValue, StoreWatchReceiver, ValueString, Watch, WatchEvent,
         },
-        registry::ControlPlaneService,
+        registry::{ControlPlaneService, StoreLeaseOwner},
     },
 };
 use async_trait::async_trait;
 use etcd_client::{
-    Client, Compare, CompareOp, EventType, GetOptions, KeyValue, Txn, TxnOp, WatchStream, Watcher,
+    Client, Compare, CompareOp, EventType, GetOptions, KeyValue, Txn, TxnOp, WatchOptions,
+    WatchStream, Watcher,
 };
 use serde_json::Value;
 use snafu::ResultExt;
@@ -71,6 +72,79 @@ impl Etcd {
         Ok(Self::from(&client, Some(lease_info)))
     }
 
+    /// Create a new instance of the etcd client with a lease associated with `service_name`.
+    /// Unlike `new_leased`, if another instance of `service_name` currently holds the lease lock
+    /// then we wait as a standby until its lease expires, at which point we take over.
+    /// See `EtcdLeaseLockKeeper` for more information.
+    pub async fn new_leased_standby<E: AsRef<str>, S: AsRef<[E]>>(
+        endpoints: S,
+        service_name: ControlPlaneService,
+        lease_time: std::time::Duration,
+    ) -> Result<Etcd, StoreError> {
+        let _ = crate::platform::init_cluster_info()
+            .await
+            .map_err(|error| StoreError::NotReady {
+                reason: format!("Platform not ready: {}", error),
+            })?;
+
+        let client = Client::connect(endpoints, None).await.context(Connect {})?;
+
+        loop {
+            match EtcdSingletonLock::start_standby(client.clone(), service_name.clone(), lease_time)
+                .await
+            {
+                Ok(lease_info) => return Ok(Self::from(&client, Some(lease_info))),
+                Err(error) => {
+                    // etcd might have gone down long enough for us to lose our lease, retry
+                    tracing::error!(%error, "Failed to wait for the lease lock, retrying...");
+                    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                }
+            }
+        }
+    }
+
+    /// Get the `StoreLeaseOwner` of `service_name` represented by this instance, if it currently
+    /// holds the lease lock.
+    pub fn lease_owner(&self, service_name: &ControlPlaneService) -> Option<StoreLeaseOwner> {
+        match self.lease_lock() {
+            Ok(Some((lease_id, _))) => Some(StoreLeaseOwner::new(service_name, lease_id)),
+            _ => None,
+        }
+    }
+
+    /// 'Watch' all etcd entries with the given key prefix.
+    /// A receiver channel is returned which is signalled when any of the entries is changed.
+    /// Unlike `watch_kv`, deleting an entry does not stop the watch.
+    pub async fn watch_prefix(
+        &mut self,
+        key_prefix: &str,
+    ) -> Result<StoreWatchReceiver, StoreError> {
+        let (sender, receiver) = channel(100);
+        let (watcher, stream) = self
+            .client
+            .watch(key_prefix, Some(WatchOptions::new().with_prefix()))
+            .await
+            .context(Watch { key: key_prefix })?;
+        watch(watcher, stream, sender, false);
+        Ok(receiver)
+    }
+
+    /// Get the revision of the entries with the given key prefix, ie: the number of entries and
+    /// the latest modification revision amongst them.
+    /// Any put or delete of an entry with the prefix yields a different revision.
+    pub async fn prefix_revision(&mut self, key_prefix: &str) -> Result<(usize, i64), StoreError> {
+        let resp = self
+            .client
+            .get(
+                key_prefix,
+                Some(GetOptions::new().with_prefix().with_keys_only()),
+            )
+            .await
+            .context(GetPrefix { prefix: key_prefix })?;
+        let revision = resp.kvs().iter().map(|kv| kv.mod_revision()).max();
+        Ok((resp.kvs().len(), revision.unwrap_or_default()))
+    }
+
     /// Get the lease lock pair, (lease_id, lock_key)
     /// Returns `StoreError::NotReady` if the lease is not active
     fn lease_lock(&self) -> Result<Option<(i64, String)>, StoreError> {
@@ -80,6 +154,15 @@ impl Etcd {
         }
     }
 
+    /// Check if the lease lock is active, meaning that store writes can be issued.
+    /// An instance without a lease is always considered active.
+    pub fn lease_active(&self) -> bool {
+        match &self.lease_lock_info {
+            None => true,
+            Some(lease_info) => lease_info.is_active(),
+        }
+    }
+
     /// Revokes the lease and releases the associated lock
     pub async fn revoke(&self) {
         if let Some(info) = &self.lease_lock_info {
@@ -193,7 +276,7 @@ impl Store for Etcd {
             .context(Watch {
                 key: key.to_string(),
             })?;
-        watch(watcher, stream, sender);
+        watch(watcher, stream, sender, true);
         Ok(receiver)
     }
 
@@ -281,7 +364,7 @@ impl Store for Etcd {
             .watch(key.key(), None)
             .await
             .context(Watch { key: key.key() })?;
-        watch(watcher, stream, sender);
+        watch(watcher, stream, sender, true);
         Ok(receiver)
     }
 
@@ -292,11 +375,12 @@ impl Store for Etcd {
 
 /// Watch for events in the key-value store.
 /// When an event occurs, a WatchEvent is sent over the channel.
-/// When a 'delete' event is received, the watcher stops watching.
+/// When a 'delete' event is received, the watcher stops watching if `stop_on_delete` is set.
 fn watch(
     _watcher: Watcher,
     mut stream: WatchStream,
     sender: Sender<Result<WatchEvent, StoreError>>,
+    stop_on_delete: bool,
 ) {
     // For now we spawn a thread for each value that is watched.
     // If we find that we are watching lots of events, this can be optimised.
@@ -335,11 +419,10 @@ fn watch(
                         }
                     }
                     EventType::Delete => {
-                        // Send only fails if the receiver is closed. We are
-                        // returning here anyway, so the error doesn't need to
-                        // be handled.
-                        let _ = sender.send(Ok(WatchEvent::Delete)).await;
-                        return;
+                        // Send only fails if the receiver is closed, so just stop watching.
+                        if sender.send(Ok(WatchEvent::Delete)).await.is_err() || stop_on_delete {
+                            return;
+                        }
                     }
                 }
             }
@@ -3,7 +3,9 @@ use crate::types::v0::store::{
     definitions::{ObjectKey, Store, StoreError},
     registry::{ControlPlaneService, StoreLeaseLockKey, StoreLeaseOwner, StoreLeaseOwnerKey},
 };
-use etcd_client::{Client, LeaseGrantOptions, LeaseKeepAliveStream, LeaseKeeper, LockOptions};
+use etcd_client::{
+    Client, LeaseGrantOptions, LeaseKeepAliveStream, LeaseKeeper, LockOptions, LockResponse,
+};
 use std::{cmp::max, ops::Deref, sync::Arc, time::Duration};
 
 /// Worker that keeps an etcd lease lock alive by sending keep alives
@@ -32,6 +34,11 @@ impl LeaseLockInfo {
             }),
         }
     }
+    /// Check if the lease is currently active, ie: we hold the lease lock and have not been
+    /// disconnected from etcd for longer than the lease ttl.
+    pub(crate) fn is_active(&self) -> bool {
+        self.0.lock().lease_id.is_some()
+    }
     /// Revokes the lease and releases the associated lock
     pub(crate) async fn revoke(&self) {
         let info = self.lease_info_inner();
@@ -116,35 +123,64 @@ impl EtcdSingletonLock {
     /// A background thread will attempt to keep the lease alive, and will handle reconnections if
     /// the connection to etcd is lost.
     pub(crate) async fn start(
+        client: Client,
+        service_kind: ControlPlaneService,
+        lease_ttl: std::time::Duration,
+    ) -> Result<LeaseLockInfo, StoreError> {
+        Self::start_locking(client, service_kind, lease_ttl, false).await
+    }
+
+    /// Similar to `start`, but rather than failing when the `service_kind` lock is already held by
+    /// another instance, we wait as a standby until the lease of the current owner expires (or is
+    /// revoked) and the lock is handed over to us.
+    /// Whilst waiting, our own lease is kept alive as otherwise the lock request is dropped.
+    pub(crate) async fn start_standby(
+        client: Client,
+        service_kind: ControlPlaneService,
+        lease_ttl: std::time::Duration,
+    ) -> Result<LeaseLockInfo, StoreError> {
+        Self::start_locking(client, service_kind, lease_ttl, true).await
+    }
+
+    async fn start_locking(
         mut client: Client,
         service_kind: ControlPlaneService,
         lease_ttl: std::time::Duration,
+        standby: bool,
     ) -> Result<LeaseLockInfo, StoreError> {
         let lock_owner_key_prefix = EtcdSingletonLock::lock_key(&service_kind);
         let lease_resp = client
             .lease_grant(*LeaseTtl::from(lease_ttl), None)
             .await
-            .expect("Should init the lease");
+            .map_err(|e| StoreError::FailedLock {
+                reason: format!("Failed to get lease grant, error: '{}'", e),
+            })?;
         tracing::info!(
             lease.id = lease_resp.id(),
             lease.ttl = lease_resp.ttl(),
             "Granted new lease",
         );
-        let lock_resp = tokio::time::timeout(
-            lease_ttl,
-            client.lock(
-                lock_owner_key_prefix.as_str(),
-                Some(LockOptions::new().with_lease(lease_resp.id())),
-            ),
-        )
-        .await
-        .map_err(|_| StoreError::Timeout {
-            operation: format!("etcd lock '{}'", lock_owner_key_prefix),
-            timeout: lease_ttl,
-        })?
-        .map_err(|e| StoreError::FailedLock {
-            reason: e.to_string(),
-        })?;
+        let lock_resp = match standby {
+            false => tokio::time::timeout(
+                lease_ttl,
+                client.lock(
+                    lock_owner_key_prefix.as_str(),
+                    Some(LockOptions::new().with_lease(lease_resp.id())),
+                ),
+            )
+            .await
+            .map_err(|_| StoreError::Timeout {
+                operation: format!("etcd lock '{}'", lock_owner_key_prefix),
+                timeout: lease_ttl,
+            })?
+            .map_err(|e| StoreError::FailedLock {
+                reason: e.to_string(),
+            })?,
+            true => {
+                Self::standby_lock(&client, &lock_owner_key_prefix, lease_resp.id(), lease_ttl)
+                    .await?
+            }
+        };
 
         let lock_key =
             String::from_utf8(lock_resp.key().to_vec()).map_err(|e| StoreError::FailedLock {
@@ -179,6 +215,58 @@ impl EtcdSingletonLock {
         Ok(lease_info)
     }
 
+    /// Wait for the `lock_key_prefix` lock to be released to us, sending keep alives for our
+    /// `lease_id` at half the `lease_ttl`.
+    async fn standby_lock(
+        client: &Client,
+        lock_key_prefix: &str,
+        lease_id: i64,
+        lease_ttl: Duration,
+    ) -> Result<LockResponse, StoreError> {
+        let (mut keeper, mut stream) =
+            client
+                .clone()
+                .lease_keep_alive(lease_id)
+                .await
+                .map_err(|e| StoreError::FailedLock {
+                    reason: e.to_string(),
+                })?;
+        let mut lock_client = client.clone();
+        let lock = lock_client.lock(
+            lock_key_prefix,
+            Some(LockOptions::new().with_lease(lease_id)),
+        );
+        tokio::pin!(lock);
+
+        tracing::info!(
+            lock.name = %lock_key_prefix,
+            lease.id = lease_id,
+            "Waiting as a standby for the lock"
+        );
+        loop {
+            tokio::select! {
+                result = &mut lock => {
+                    return result.map_err(|e| StoreError::FailedLock {
+                        reason: e.to_string(),
+                    });
+                }
+                _ = tokio::time::sleep(lease_ttl / 2) => {
+                    keeper.keep_alive().await.map_err(|e| StoreError::FailedLock {
+                        reason: e.to_string(),
+                    })?;
+                    match stream.message().await {
+                        Ok(Some(resp)) if resp.ttl() > 0 => {}
+                        _ => {
+                            return Err(StoreError::FailedLock {
+                                reason: "Lease expired whilst waiting for the lock".to_string(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     fn lease_ttl(&self) -> LeaseTtl {
         LeaseTtl::from(self.lease_ttl)
     }
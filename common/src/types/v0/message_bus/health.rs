@@ -0,0 +1,69 @@
+use super::*;
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// Retrieve the health of the core agent
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCoreHealth {}
+
+/// Operating mode of the core agent, driven by the availability of the persistent store
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, strum_macros::ToString)]
+pub enum CoreAgentMode {
+    /// The persistent store is available and all requests are served
+    ReadWrite,
+    /// The persistent store is unavailable: reads are served from the registry cache whilst
+    /// any mutating requests are rejected
+    ReadOnly,
+}
+impl Default for CoreAgentMode {
+    fn default() -> Self {
+        Self::ReadWrite
+    }
+}
+impl From<CoreAgentMode> for models::CoreAgentMode {
+    fn from(src: CoreAgentMode) -> Self {
+        match src {
+            CoreAgentMode::ReadWrite => Self::ReadWrite,
+            CoreAgentMode::ReadOnly => Self::ReadOnly,
+        }
+    }
+}
+
+/// Health of the core agent
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoreHealth {
+    /// current operating mode
+    pub mode: CoreAgentMode,
+    /// the persistent store lease is held by this core agent instance
+    pub store_lease: bool,
+    /// the persistent store is reachable
+    pub store_online: bool,
+    /// leadership of the core agent
+    pub leader: CoreLeader,
+}
+impl From<CoreHealth> for models::CoreHealth {
+    fn from(src: CoreHealth) -> Self {
+        Self::new(src.leader, src.mode, src.store_lease, src.store_online)
+    }
+}
+
+/// Leadership of the core agent, ie: the instance which holds the persistent store lease lock.
+/// Any other core agent instance waits as a standby until the lease of the leader expires.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoreLeader {
+    /// name of the core agent instance which holds the leadership
+    pub instance_name: String,
+    /// the persistent store lease id, as a hex string
+    pub lease_id: String,
+    /// the leadership was taken over from another instance, after waiting as a standby
+    pub takeover: bool,
+}
+impl From<CoreLeader> for models::CoreLeader {
+    fn from(src: CoreLeader) -> Self {
+        Self::new(src.instance_name, src.lease_id, src.takeover)
+    }
+}
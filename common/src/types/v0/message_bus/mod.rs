@@ -1,10 +1,12 @@
 pub mod blockdevice;
 pub mod child;
+pub mod health;
 pub mod jsongrpc;
 pub mod misc;
 pub mod nexus;
 pub mod node;
 pub mod pool;
+pub mod reconciler;
 pub mod replica;
 pub mod spec;
 pub mod state;
@@ -13,11 +15,13 @@ pub mod watch;
 
 pub use blockdevice::*;
 pub use child::*;
+pub use health::*;
 pub use jsongrpc::*;
 pub use misc::*;
 pub use nexus::*;
 pub use node::*;
 pub use pool::*;
+pub use reconciler::*;
 pub use replica::*;
 pub use spec::*;
 pub use state::*;
@@ -95,6 +99,10 @@ pub enum MessageIdVs {
     /// Node Service
     /// Get all node information
     GetNodes,
+    /// Cordon a node
+    CordonNode,
+    /// Uncordon a node
+    UncordonNode,
     /// Pool Service
     ///
     /// Get pools with filter
@@ -103,6 +111,8 @@ pub enum MessageIdVs {
     CreatePool,
     /// Destroy Pool,
     DestroyPool,
+    /// Label Pool,
+    LabelPool,
     /// Get replicas with filter
     GetReplicas,
     /// Create Replica,
@@ -149,6 +159,12 @@ pub enum MessageIdVs {
     RemoveVolumeNexus,
     /// Set replica count
     SetVolumeReplica,
+    /// Add a replica to a volume on a given pool
+    CreateVolumeReplica,
+    /// Remove a given replica from a volume
+    RemoveVolumeReplica,
+    /// Get the capacity available for new volumes
+    GetVolumeCapacity,
     /// Generic JSON gRPC message
     JsonGrpc,
     /// Get block devices
@@ -163,6 +179,14 @@ pub enum MessageIdVs {
     GetSpecs,
     /// Get States
     GetStates,
+    /// Get the core agent health
+    GetCoreHealth,
+    /// Get the status of the core agent reconcilers
+    GetReconcilers,
+    /// Pause or resume a core agent reconciler
+    SetReconcilerPaused,
+    /// Trigger an immediate poll of the core agent reconcilers
+    PollReconcilers,
 }
 
 impl MessageIdTimeout for MessageIdVs {
@@ -50,6 +50,34 @@ impl GetNodes {
     }
 }
 
+/// Cordon a node, so it is not used for new replicas and volume targets
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CordonNode {
+    /// id of the io-engine instance
+    pub id: NodeId,
+}
+impl CordonNode {
+    /// Create new `Self` from the given node id
+    pub fn new(id: &NodeId) -> Self {
+        Self { id: id.clone() }
+    }
+}
+
+/// Uncordon a node, so it can be used for new replicas and volume targets again
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UncordonNode {
+    /// id of the io-engine instance
+    pub id: NodeId,
+}
+impl UncordonNode {
+    /// Create new `Self` from the given node id
+    pub fn new(id: &NodeId) -> Self {
+        Self { id: id.clone() }
+    }
+}
+
 /// Node information
 #[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
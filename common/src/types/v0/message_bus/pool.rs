@@ -271,6 +271,37 @@ impl CreatePool {
     }
 }
 
+/// Label Pool Request
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelPool {
+    /// id of the pool
+    pub id: PoolId,
+    /// labels to be set on the pool
+    pub labels: PoolLabel,
+    /// keys of the labels to be removed from the pool
+    pub remove_labels: Vec<String>,
+    /// overwrite the value of the labels which are already set on the pool
+    pub overwrite: bool,
+}
+
+impl LabelPool {
+    /// Create new `Self` from the given parameters
+    pub fn new(
+        id: &PoolId,
+        labels: PoolLabel,
+        remove_labels: Vec<String>,
+        overwrite: bool,
+    ) -> Self {
+        Self {
+            id: id.clone(),
+            labels,
+            remove_labels,
+            overwrite,
+        }
+    }
+}
+
 /// Destroy Pool Request
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
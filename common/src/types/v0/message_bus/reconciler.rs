@@ -0,0 +1,86 @@
+use super::*;
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// Retrieve the status of the reconcilers of the core agent
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetReconcilers {}
+
+/// Pause or resume a reconciler of the core agent
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetReconcilerPaused {
+    /// name of the reconciler
+    pub name: String,
+    /// pause the reconciler if true, otherwise resume it
+    pub paused: bool,
+}
+
+/// Trigger an immediate poll of all the reconcilers of the core agent
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PollReconcilers {}
+
+/// Result of the last poll of a reconciler
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, strum_macros::ToString)]
+pub enum ReconcilerPollResult {
+    /// No immediate work remains to be done
+    Idle,
+    /// There is still work outstanding
+    Busy,
+    /// The poll failed
+    Failed,
+}
+impl From<ReconcilerPollResult> for models::ReconcilerPollResult {
+    fn from(src: ReconcilerPollResult) -> Self {
+        match src {
+            ReconcilerPollResult::Idle => Self::Idle,
+            ReconcilerPollResult::Busy => Self::Busy,
+            ReconcilerPollResult::Failed => Self::Failed,
+        }
+    }
+}
+
+/// Status of a reconciler of the core agent
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Reconciler {
+    /// name of the reconciler
+    pub name: String,
+    /// the reconciler is paused and is not polled
+    pub paused: bool,
+    /// time of the last poll, in rfc3339 format
+    pub last_run: Option<String>,
+    /// result of the last poll
+    pub last_result: Option<ReconcilerPollResult>,
+    /// error chain of the last poll, outermost first, if it failed
+    pub errors: Vec<String>,
+}
+impl Reconciler {
+    /// The reconciler has work outstanding as of its last poll
+    pub fn busy(&self) -> bool {
+        self.last_result == Some(ReconcilerPollResult::Busy)
+    }
+}
+impl From<Reconciler> for models::Reconciler {
+    fn from(src: Reconciler) -> Self {
+        Self::new_all(
+            src.errors,
+            src.last_result.map(From::from),
+            src.last_run,
+            src.name,
+            src.paused,
+        )
+    }
+}
+
+/// Status of all the reconcilers of the core agent
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+pub struct Reconcilers(pub Vec<Reconciler>);
+impl From<Reconcilers> for Vec<models::Reconciler> {
+    fn from(src: Reconcilers) -> Self {
+        src.0.into_iter().map(From::from).collect()
+    }
+}
@@ -496,6 +496,70 @@ impl SetVolumeReplica {
     }
 }
 
+/// Add a replica to the volume on the given pool, increasing its replica count by 1
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVolumeReplica {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+    /// id of the pool where the replica is created
+    pub pool: PoolId,
+}
+impl CreateVolumeReplica {
+    /// Create new `Self` based on the provided arguments
+    pub fn new(uuid: VolumeId, pool: PoolId) -> Self {
+        Self { uuid, pool }
+    }
+}
+
+/// Remove the given replica from the volume, decreasing its replica count by 1
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveVolumeReplica {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+    /// uuid of the replica to remove
+    pub replica: ReplicaId,
+}
+impl RemoveVolumeReplica {
+    /// Create new `Self` based on the provided arguments
+    pub fn new(uuid: VolumeId, replica: ReplicaId) -> Self {
+        Self { uuid, replica }
+    }
+}
+
+/// Get the capacity available for the creation of new volumes
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVolumeCapacity {
+    /// number of replicas of the new volumes
+    pub replicas: u64,
+    /// replica placement topology of the new volumes
+    pub topology: Option<Topology>,
+}
+impl GetVolumeCapacity {
+    /// Create new `Self` based on the provided arguments
+    pub fn new(replicas: u64, topology: Option<Topology>) -> Self {
+        Self { replicas, topology }
+    }
+}
+
+/// Capacity available for the creation of new volumes
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeCapacity {
+    /// total size of the volumes which may be created, in bytes
+    pub available_capacity: u64,
+    /// size of the largest volume which may be created, in bytes
+    pub maximum_volume_size: u64,
+}
+
+impl From<VolumeCapacity> for models::VolumeCapacity {
+    fn from(src: VolumeCapacity) -> Self {
+        Self::new(src.available_capacity, src.maximum_volume_size)
+    }
+}
+
 /// Delete volume
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
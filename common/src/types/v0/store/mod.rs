@@ -72,6 +72,14 @@ pub trait SpecTransaction<Operation> {
     fn set_op_result(&mut self, result: bool);
 }
 
+/// Get the time at which a spec operation is started, in seconds since the UNIX epoch.
+pub(crate) fn operation_start_time() -> Option<u64> {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|time| time.as_secs())
+}
+
 /// Trait which allows a UUID to be returned as the associated type Id.
 pub trait ResourceUuid {
     type Id;
@@ -9,7 +9,7 @@ use crate::types::v0::{
     store::{
         definitions::{ObjectKey, StorableObject, StorableObjectType},
         nexus_child::NexusChild,
-        ResourceUuid, SpecStatus, SpecTransaction,
+        operation_start_time, ResourceUuid, SpecStatus, SpecTransaction,
     },
 };
 
@@ -239,6 +239,9 @@ pub struct NexusOperationState {
     pub operation: NexusOperation,
     /// Result of the operation
     pub result: Option<bool>,
+    /// Time at which the operation was started, in seconds since the UNIX epoch
+    #[serde(default)]
+    pub started: Option<u64>,
 }
 
 impl SpecTransaction<NexusOperation> for NexusSpec {
@@ -276,6 +279,7 @@ impl SpecTransaction<NexusOperation> for NexusSpec {
         self.operation = Some(NexusOperationState {
             operation,
             result: None,
+            started: operation_start_time(),
         })
     }
 
@@ -34,6 +34,9 @@ pub struct NodeSpec {
     endpoint: String,
     /// Node labels.
     labels: NodeLabels,
+    /// Cordoned nodes are not used for new replicas and volume targets.
+    #[serde(default)]
+    cordoned: bool,
 }
 impl NodeSpec {
     /// Return a new `Self`
@@ -42,6 +45,7 @@ impl NodeSpec {
             id,
             endpoint,
             labels,
+            cordoned: false,
         }
     }
     /// Node identification
@@ -60,11 +64,24 @@ impl NodeSpec {
     pub fn set_endpoint(&mut self, endpoint: String) {
         self.endpoint = endpoint
     }
+    /// Whether the node is cordoned
+    pub fn cordoned(&self) -> bool {
+        self.cordoned
+    }
+    /// Cordon or uncordon the node
+    pub fn set_cordoned(&mut self, cordoned: bool) {
+        self.cordoned = cordoned
+    }
+    /// Return `Self` with the given cordon state
+    pub fn with_cordoned(mut self, cordoned: bool) -> Self {
+        self.cordoned = cordoned;
+        self
+    }
 }
 
 impl From<NodeSpec> for models::NodeSpec {
     fn from(src: NodeSpec) -> Self {
-        Self::new(src.endpoint, src.id)
+        Self::new(src.cordoned, src.endpoint, src.id)
     }
 }
 
@@ -1,7 +1,7 @@
 //! Definition of pool types that can be saved to the persistent store.
 
 use crate::types::v0::{
-    message_bus::{self, CreatePool, NodeId, PoolDeviceUri, PoolId},
+    message_bus::{self, CreatePool, LabelPool, NodeId, PoolDeviceUri, PoolId},
     openapi::models,
     store::{
         definitions::{ObjectKey, StorableObject, StorableObjectType},
@@ -149,6 +149,9 @@ impl SpecTransaction<PoolOperation> for PoolSpec {
                 PoolOperation::Create => {
                     self.status = SpecStatus::Created(message_bus::PoolStatus::Online);
                 }
+                PoolOperation::Label(request) => {
+                    self.labels = self.labelled(&request);
+                }
             }
         }
         self.clear_op();
@@ -177,6 +180,19 @@ impl SpecTransaction<PoolOperation> for PoolSpec {
 pub enum PoolOperation {
     Create,
     Destroy,
+    Label(LabelPool),
+}
+
+impl PoolSpec {
+    /// Get the labels of the pool once the given `LabelPool` request is applied.
+    pub fn labelled(&self, request: &LabelPool) -> Option<PoolLabel> {
+        let mut labels = self.labels.clone().unwrap_or_default();
+        labels.extend(request.labels.clone());
+        request.remove_labels.iter().for_each(|key| {
+            labels.remove(key);
+        });
+        Some(labels).filter(|labels| !labels.is_empty())
+    }
 }
 
 impl PartialEq<message_bus::PoolState> for PoolSpec {
@@ -154,6 +154,10 @@ impl StoreLeaseOwner {
     pub fn lease_id(&self) -> &str {
         &self.lease_id
     }
+    /// Get the name of the service instance which owns the lease
+    pub fn instance_name(&self) -> &str {
+        &self.instance_name
+    }
 }
 impl StorableObject for StoreLeaseOwner {
     type Key = StoreLeaseOwnerKey;
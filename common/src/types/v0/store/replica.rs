@@ -8,7 +8,8 @@ use crate::types::v0::{
     openapi::models,
     store::{
         definitions::{ObjectKey, StorableObject, StorableObjectType},
-        OperationSequence, OperationSequencer, ResourceUuid, SpecStatus, SpecTransaction,
+        operation_start_time, OperationSequence, OperationSequencer, ResourceUuid, SpecStatus,
+        SpecTransaction,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -130,6 +131,9 @@ pub struct ReplicaOperationState {
     pub operation: ReplicaOperation,
     /// Result of the operation
     pub result: Option<bool>,
+    /// Time at which the operation was started, in seconds since the UNIX epoch
+    #[serde(default)]
+    pub started: Option<u64>,
 }
 
 impl SpecTransaction<ReplicaOperation> for ReplicaSpec {
@@ -165,6 +169,7 @@ impl SpecTransaction<ReplicaOperation> for ReplicaSpec {
         self.operation = Some(ReplicaOperationState {
             operation,
             result: None,
+            started: operation_start_time(),
         })
     }
 
@@ -4,7 +4,7 @@ use crate::types::v0::{
     message_bus::{self, CreateVolume, NexusId, NodeId, VolumeId, VolumeShareProtocol},
     store::{
         definitions::{ObjectKey, StorableObject, StorableObjectType},
-        SpecStatus, SpecTransaction,
+        operation_start_time, SpecStatus, SpecTransaction,
     },
 };
 
@@ -182,11 +182,14 @@ pub struct VolumeOperationState {
     pub operation: VolumeOperation,
     /// Result of the operation
     pub result: Option<bool>,
+    /// Time at which the operation was started, in seconds since the UNIX epoch
+    #[serde(default)]
+    pub started: Option<u64>,
 }
 
 impl From<VolumeOperationState> for models::VolumeSpecOperation {
     fn from(src: VolumeOperationState) -> Self {
-        models::VolumeSpecOperation::new_all(src.operation, src.result)
+        models::VolumeSpecOperation::new_all(src.operation, src.result, src.started)
     }
 }
 
@@ -236,6 +239,7 @@ impl SpecTransaction<VolumeOperation> for VolumeSpec {
         self.operation = Some(VolumeOperationState {
             operation,
             result: None,
+            started: operation_start_time(),
         })
     }
 
@@ -64,6 +64,12 @@ pub enum SvcError {
     NodeNotFound { node_id: NodeId },
     #[snafu(display("Pool '{}' not found", pool_id))]
     PoolNotFound { pool_id: PoolId },
+    #[snafu(display(
+        "Pool '{}' is not suitable for a replica of Volume '{}'",
+        pool_id,
+        vol_id
+    ))]
+    PoolNotSuitable { pool_id: PoolId, vol_id: String },
     #[snafu(display("Nexus '{}' not found", nexus_id))]
     NexusNotFound { nexus_id: String },
     #[snafu(display("{} '{}' not found", kind.to_string(), id))]
@@ -136,6 +142,10 @@ pub enum SvcError {
     Store { source: StoreError },
     #[snafu(display("Storage Error: {} Config for Resource id {} not committed to the store", kind.to_string(), id))]
     StoreSave { kind: ResourceKind, id: String },
+    #[snafu(display(
+        "Storage Error: the persistent store is unavailable, only read requests are permitted"
+    ))]
+    StoreUnavailable {},
     #[snafu(display("Watch Config Not Found"))]
     WatchNotFound {},
     #[snafu(display("{} Resource to be watched does not exist", kind.to_string()))]
@@ -203,6 +213,12 @@ pub enum SvcError {
         max_rebuilds
     ))]
     MaxRebuilds { max_rebuilds: u32 },
+    #[snafu(display(
+        "Label '{}' is already set on Pool '{}' with a different value",
+        key,
+        id
+    ))]
+    PoolLabelExists { key: String, id: String },
 }
 
 impl From<StoreError> for SvcError {
@@ -382,6 +398,12 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::StoreUnavailable { .. } => ReplyError {
+                kind: ReplyErrorKind::Unavailable,
+                resource: ResourceKind::Unknown,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::StoreMissingEntry { .. } => ReplyError {
                 kind: ReplyErrorKind::NotFound,
                 resource: ResourceKind::Unknown,
@@ -406,6 +428,12 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::PoolNotSuitable { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Pool,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::ReplicaNotFound { .. } => ReplyError {
                 kind: ReplyErrorKind::NotFound,
                 resource: ResourceKind::Replica,
@@ -557,6 +585,12 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::PoolLabelExists { .. } => ReplyError {
+                kind: ReplyErrorKind::AlreadyExists,
+                resource: ResourceKind::Pool,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
         }
     }
 }
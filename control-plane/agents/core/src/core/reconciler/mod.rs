@@ -6,11 +6,18 @@ mod replica;
 mod volume;
 
 pub(crate) use crate::core::task_poller::PollTriggerEvent;
-use crate::core::task_poller::{PollContext, PollEvent, TaskPoller};
+use crate::core::task_poller::{PollContext, PollEvent, PollResult, PollerState, TaskPoller};
 use poller::ReconcilerWorker;
 
 use crate::core::registry::Registry;
+use common::errors::SvcError;
+use common_lib::{
+    mbus_api::ResourceKind,
+    types::v0::message_bus::{Reconciler, ReconcilerPollResult, Reconcilers},
+};
+use indexmap::IndexMap;
 use parking_lot::Mutex;
+use std::time::SystemTime;
 
 /// Used to start and stop the reconcile pollers
 #[derive(Debug)]
@@ -18,16 +25,47 @@ pub(crate) struct ReconcilerControl {
     worker: Mutex<Option<ReconcilerWorker>>,
     event_channel: tokio::sync::mpsc::Sender<PollEvent>,
     shutdown_channel: tokio::sync::mpsc::Sender<()>,
+    /// status of each reconciler, by name, in the order in which they're first polled
+    status: Mutex<IndexMap<&'static str, ReconcilerStatus>>,
+}
+
+/// Status of a reconciler, as recorded by its polls
+#[derive(Debug, Default)]
+struct ReconcilerStatus {
+    paused: bool,
+    last_run: Option<SystemTime>,
+    last_result: Option<ReconcilerPollResult>,
+    errors: Vec<String>,
+}
+impl ReconcilerStatus {
+    fn to_reconciler(&self, name: &str) -> Reconciler {
+        Reconciler {
+            name: name.to_string(),
+            paused: self.paused,
+            last_run: self
+                .last_run
+                .map(|time| humantime::format_rfc3339_millis(time).to_string()),
+            last_result: self.last_result,
+            errors: self.errors.clone(),
+        }
+    }
 }
 
 impl ReconcilerControl {
     /// Return a new `Self`
     pub(crate) fn new() -> Self {
         let mut worker = ReconcilerWorker::new();
+        // the nested reconcilers are only registered once they're first polled
+        let status = worker
+            .names()
+            .into_iter()
+            .map(|name| (name, ReconcilerStatus::default()))
+            .collect();
         Self {
             event_channel: worker.take_event_channel(),
             shutdown_channel: worker.take_shutdown_channel(),
             worker: Mutex::new(Some(worker)),
+            status: Mutex::new(status),
         }
     }
 
@@ -54,4 +92,77 @@ impl ReconcilerControl {
             tracing::warn!(error=?error, "Failed to send event to reconcile worker");
         }
     }
+
+    /// Trigger an immediate poll of all the reconcilers which are not paused
+    pub(crate) async fn poll_now(&self) {
+        self.notify(PollEvent::Triggered(PollTriggerEvent::Requested))
+            .await
+    }
+
+    /// Whether the reconciler `name` is paused, registering it if not already registered
+    pub(crate) fn paused(&self, name: &'static str) -> bool {
+        self.status.lock().entry(name).or_default().paused
+    }
+
+    /// Record the `result` of a poll of the reconciler `name`
+    pub(crate) fn record(&self, name: &'static str, result: &PollResult) {
+        let mut status = self.status.lock();
+        let status = status.entry(name).or_default();
+        status.last_run = Some(SystemTime::now());
+        match result {
+            Ok(PollerState::Idle) => {
+                status.last_result = Some(ReconcilerPollResult::Idle);
+                status.errors.clear();
+            }
+            Ok(PollerState::Busy) => {
+                status.last_result = Some(ReconcilerPollResult::Busy);
+                status.errors.clear();
+            }
+            Err(error) => {
+                status.last_result = Some(ReconcilerPollResult::Failed);
+                status.errors = error_chain(error);
+            }
+        }
+    }
+
+    /// Get the status of all the registered reconcilers
+    pub(crate) fn reconcilers(&self) -> Reconcilers {
+        let status = self.status.lock();
+        Reconcilers(
+            status
+                .iter()
+                .map(|(name, status)| status.to_reconciler(name))
+                .collect(),
+        )
+    }
+
+    /// Pause or resume the reconciler `name`
+    /// A paused reconciler is not polled, and neither are any reconcilers nested within it
+    pub(crate) fn set_paused(&self, name: &str, paused: bool) -> Result<Reconciler, SvcError> {
+        let mut status = self.status.lock();
+        match status.get_full_mut(name) {
+            Some((_, name, status)) => {
+                if status.paused != paused {
+                    tracing::info!(reconciler = %name, paused, "Reconciler pause state changed");
+                }
+                status.paused = paused;
+                Ok(status.to_reconciler(name))
+            }
+            None => Err(SvcError::NotFound {
+                kind: ResourceKind::Unknown,
+                id: name.to_string(),
+            }),
+        }
+    }
+}
+
+/// Collect the chain of errors, from the outermost `error` to its innermost source
+fn error_chain(error: &SvcError) -> Vec<String> {
+    let mut chain = vec![error.to_string()];
+    let mut source = std::error::Error::source(error);
+    while let Some(error) = source {
+        chain.push(error.to_string());
+        source = error.source();
+    }
+    chain
 }
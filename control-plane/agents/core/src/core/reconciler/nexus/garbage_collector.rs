@@ -37,13 +37,19 @@ impl TaskPoller for GarbageCollector {
         PollResult::Ok(PollerState::Idle)
     }
 
+    fn name(&self) -> &'static str {
+        "NexusGarbageCollector"
+    }
+
     async fn poll_timer(&mut self, _context: &PollContext) -> bool {
         self.counter.poll()
     }
 
     async fn poll_event(&mut self, context: &PollContext) -> bool {
         match context.event() {
-            PollEvent::TimedRun | PollEvent::Triggered(PollTriggerEvent::Start) => true,
+            PollEvent::TimedRun
+            | PollEvent::Triggered(PollTriggerEvent::Start)
+            | PollEvent::Triggered(PollTriggerEvent::Requested) => true,
             PollEvent::Shutdown | PollEvent::Triggered(_) => false,
         }
     }
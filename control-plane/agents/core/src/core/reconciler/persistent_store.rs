@@ -30,4 +30,9 @@ impl TaskPoller for PersistentStoreReconciler {
 
         PollResult::Ok(PollerState::Idle)
     }
+
+    /// Not paused as it's responsible for flushing the dirty specs once the store is back
+    fn pause_when_read_only(&self) -> bool {
+        false
+    }
 }
@@ -1,10 +1,13 @@
-use crate::core::{
-    reconciler::{nexus, persistent_store::PersistentStoreReconciler, pool, replica, volume},
-    registry::Registry,
-    task_poller::{
-        squash_results, PollContext, PollEvent, PollResult, PollTriggerEvent, PollerState,
-        TaskPoller,
+use crate::{
+    core::{
+        reconciler::{nexus, persistent_store::PersistentStoreReconciler, pool, replica, volume},
+        registry::Registry,
+        task_poller::{
+            squash_results, PollContext, PollEvent, PollResult, PollTriggerEvent, PollerState,
+            TaskPoller,
+        },
     },
+    metrics,
 };
 
 /// Reconciliation worker that polls all reconciliation loops
@@ -46,6 +49,10 @@ impl ReconcilerWorker {
             shutdown_channel_sender: Some(shutdown_channel.0),
         }
     }
+    /// Names of the registered reconciliation loops
+    pub(super) fn names(&self) -> Vec<&'static str> {
+        self.poll_targets.iter().map(|t| t.name()).collect()
+    }
     /// Take the shutdown channel sender (can only be called once)
     pub(super) fn take_shutdown_channel(&mut self) -> tokio::sync::mpsc::Sender<()> {
         self.shutdown_channel_sender
@@ -100,7 +107,10 @@ impl ReconcilerWorker {
         tracing::trace!("Entering the reconcile loop...");
         let mut results = vec![];
         for target in &mut self.poll_targets {
-            results.push(target.try_poll(&context).await);
+            let start = std::time::Instant::now();
+            let result = target.try_poll(&context).await;
+            metrics::observe_poll(target.name(), start.elapsed(), &result);
+            results.push(result);
         }
         tracing::trace!("Leaving the reconcile loop...");
         squash_results(results)
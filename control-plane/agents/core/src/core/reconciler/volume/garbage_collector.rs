@@ -42,6 +42,10 @@ impl TaskPoller for GarbageCollector {
         Self::squash_results(results)
     }
 
+    fn name(&self) -> &'static str {
+        "VolumeGarbageCollector"
+    }
+
     async fn poll_timer(&mut self, _context: &PollContext) -> bool {
         self.counter.poll()
     }
@@ -50,7 +54,8 @@ impl TaskPoller for GarbageCollector {
         match context.event() {
             PollEvent::TimedRun
             | PollEvent::Triggered(PollTriggerEvent::VolumeDegraded)
-            | PollEvent::Triggered(PollTriggerEvent::Start) => true,
+            | PollEvent::Triggered(PollTriggerEvent::Start)
+            | PollEvent::Triggered(PollTriggerEvent::Requested) => true,
             PollEvent::Shutdown | PollEvent::Triggered(_) => false,
         }
     }
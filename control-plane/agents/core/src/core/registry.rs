@@ -14,19 +14,27 @@
 //! Each instance also contains the known nexus, pools and replicas that live in
 //! said instance.
 use super::{specs::*, wrapper::NodeWrapper};
-use crate::core::{
-    reconciler::ReconcilerControl,
-    task_poller::{PollEvent, PollTriggerEvent},
-    wrapper::InternalOps,
+use crate::{
+    core::{
+        reconciler::ReconcilerControl,
+        task_poller::{PollEvent, PollTriggerEvent},
+        wrapper::InternalOps,
+    },
+    metrics,
 };
 use common::errors::SvcError;
 use common_lib::{
     store::etcd::Etcd,
     types::v0::{
-        message_bus::NodeId,
+        message_bus::{CoreAgentMode, CoreHealth, CoreLeader, NodeId},
         store::{
-            definitions::{StorableObject, Store, StoreError, StoreKey},
-            registry::{ControlPlaneService, CoreRegistryConfig, NodeRegistration},
+            definitions::{
+                key_prefix, key_prefix_obj, ObjectKey, StorableObject, Store, StoreError, StoreKey,
+                StoreWatchReceiver,
+            },
+            registry::{
+                ControlPlaneService, CoreRegistryConfig, NodeRegistration, StoreLeaseLockKey,
+            },
         },
     },
 };
@@ -77,12 +85,27 @@ pub struct RegistryInner<S: Store> {
     config: CoreRegistryConfig,
     /// system-wide maximum number of concurrent rebuilds allowed
     max_rebuilds: Option<NumRebuilds>,
+    /// health of the core agent, as seen by the last persistent store probe
+    health: parking_lot::Mutex<CoreHealth>,
+}
+
+/// The persistent store lease lock, as acquired by the core agent on start up.
+struct StoreLease {
+    /// the leased store
+    store: Etcd,
+    /// another core agent instance held or was waiting for the lease lock before us
+    takeover: bool,
+    /// the resource specs loaded whilst on standby are still current
+    specs_current: bool,
 }
 
 impl Registry {
     /// Create a new registry with the `cache_period` to reload the cache, the
     /// `store_url` to connect to, a `store_timeout` for store operations
-    /// and a `reconcile_period` for reconcile operations
+    /// and a `reconcile_period` for reconcile operations.
+    /// If `standby` is set and another core agent instance holds the persistent store lease lock,
+    /// then we wait until its lease expires, keeping the resource specs cache warm in the meantime.
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         cache_period: std::time::Duration,
         store_url: String,
@@ -91,21 +114,37 @@ impl Registry {
         reconcile_period: std::time::Duration,
         reconcile_idle_period: std::time::Duration,
         max_rebuilds: Option<NumRebuilds>,
+        standby: bool,
     ) -> Self {
         let store_endpoint = Self::format_store_endpoint(&store_url);
         tracing::info!("Connecting to persistent store at {}", store_endpoint);
-        let store = Etcd::new_leased(
-            [&store_endpoint],
-            ControlPlaneService::CoreAgent,
-            store_lease_tll,
-        )
-        .await
-        .expect("Should connect to the persistent store");
+        let specs = ResourceSpecsLocked::new();
+        let StoreLease {
+            store,
+            takeover,
+            specs_current,
+        } = match standby {
+            false => StoreLease {
+                store: Etcd::new_leased(
+                    [&store_endpoint],
+                    ControlPlaneService::CoreAgent,
+                    store_lease_tll,
+                )
+                .await
+                .expect("Should connect to the persistent store"),
+                takeover: false,
+                specs_current: false,
+            },
+            true => Self::standby(&store_endpoint, store_lease_tll, &specs).await,
+        };
         tracing::info!("Connected to persistent store at {}", store_endpoint);
+        let leader = store
+            .lease_owner(&ControlPlaneService::CoreAgent)
+            .expect("Should hold the persistent store lease");
         let registry = Self {
             inner: Arc::new(RegistryInner {
                 nodes: Default::default(),
-                specs: ResourceSpecsLocked::new(),
+                specs,
                 cache_period,
                 store: Arc::new(Mutex::new(store.clone())),
                 store_timeout,
@@ -114,12 +153,153 @@ impl Registry {
                 reconciler: ReconcilerControl::new(),
                 config: Self::get_config_or_panic(store).await,
                 max_rebuilds,
+                health: parking_lot::Mutex::new(CoreHealth {
+                    mode: CoreAgentMode::ReadWrite,
+                    store_lease: true,
+                    store_online: true,
+                    leader: CoreLeader {
+                        instance_name: leader.instance_name().to_string(),
+                        lease_id: leader.lease_id().to_string(),
+                        takeover,
+                    },
+                }),
             }),
         };
-        registry.init().await;
+        match specs_current {
+            true => tracing::info!("Reusing the resource specs loaded whilst on standby"),
+            false => registry.init().await,
+        }
         registry
     }
 
+    /// Wait as a standby for the persistent store lease lock, whilst the current leader holds it.
+    /// In the meantime, the `specs` are kept warm by reloading them whenever the store changes.
+    /// Returns the leased store once the leader's lease expires and the lock is handed over to us.
+    async fn standby(
+        store_endpoint: &str,
+        lease_ttl: std::time::Duration,
+        specs: &ResourceSpecsLocked,
+    ) -> StoreLease {
+        let mut store = Etcd::new(store_endpoint)
+            .await
+            .expect("Should connect to the persistent store");
+        // every instance waiting on the lock, including the leader, holds a key with its prefix
+        let lock_prefix = StoreLeaseLockKey::new(&ControlPlaneService::CoreAgent).key();
+        let takeover = !store
+            .get_values_prefix(&lock_prefix)
+            .await
+            .expect("Should connect to the persistent store")
+            .is_empty();
+        let leased =
+            Etcd::new_leased_standby([store_endpoint], ControlPlaneService::CoreAgent, lease_ttl);
+        tokio::pin!(leased);
+
+        let mut watch = None;
+        let mut loaded = None;
+        loop {
+            if watch.is_none() {
+                // watch before loading so that we don't miss any changes in between
+                watch = match store.watch_prefix(&key_prefix()).await {
+                    Ok(watch) => Some(watch),
+                    Err(error) => {
+                        tracing::error!(%error, "Failed to watch the persistent store");
+                        None
+                    }
+                };
+                loaded = Self::standby_reload(&mut store, specs).await;
+                if loaded.is_none() {
+                    watch = None;
+                }
+            }
+            tokio::select! {
+                result = &mut leased => {
+                    let leased = result.expect("Should connect to the persistent store");
+                    tracing::info!("Acquired the persistent store lease, taking over as leader");
+                    // the previous leader can no longer write to the store, so if the specs
+                    // haven't changed since we last loaded them, then they're still current
+                    let specs_current = match loaded {
+                        Some(loaded) => {
+                            Self::specs_revision(&mut store).await.ok() == Some(loaded)
+                        }
+                        None => false,
+                    };
+                    return StoreLease {
+                        store: leased,
+                        takeover,
+                        specs_current,
+                    };
+                }
+                changed = Self::store_changed(&mut watch) => {
+                    if changed {
+                        loaded = Self::standby_reload(&mut store, specs).await;
+                        if loaded.is_none() {
+                            watch = None;
+                        }
+                    } else {
+                        watch = None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reload the `specs` from the `store` whilst on standby.
+    /// Returns the revision of the specs which were loaded, or None if they could not be loaded.
+    async fn standby_reload(
+        store: &mut Etcd,
+        specs: &ResourceSpecsLocked,
+    ) -> Option<Vec<(usize, i64)>> {
+        // get the revision before loading, so that any change in between yields a new revision
+        let revision = match Self::specs_revision(store).await {
+            Ok(revision) => revision,
+            Err(error) => {
+                tracing::error!(%error, "Failed to get the resource specs revision");
+                return None;
+            }
+        };
+        match specs.reload(store).await {
+            Ok(()) => Some(revision),
+            Err(error) => {
+                tracing::error!(%error, "Failed to load the resource specs");
+                None
+            }
+        }
+    }
+
+    /// Get the revision of each resource spec type in the persistent store.
+    async fn specs_revision(store: &mut Etcd) -> Result<Vec<(usize, i64)>, StoreError> {
+        let mut revisions = vec![];
+        for spec_type in &ResourceSpecsLocked::SPEC_TYPES {
+            revisions.push(store.prefix_revision(&key_prefix_obj(*spec_type)).await?);
+        }
+        Ok(revisions)
+    }
+
+    /// Wait for the next change from the store `watch`, draining any other pending changes so
+    /// that a burst of changes is handled as one.
+    /// Returns false if the watch is no longer usable and needs to be reestablished.
+    async fn store_changed(watch: &mut Option<StoreWatchReceiver>) -> bool {
+        let watch = match watch {
+            Some(watch) => watch,
+            None => {
+                // back off before attempting to reestablish the watch
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                return false;
+            }
+        };
+        match watch.recv().await {
+            Some(Ok(_)) => {
+                while let Ok(event) = watch.try_recv() {
+                    if event.is_err() {
+                        return false;
+                    }
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Formats the store endpoint with a default port if one isn't supplied.
     fn format_store_endpoint(endpoint: &str) -> String {
         match endpoint.contains(':') {
@@ -171,12 +351,15 @@ impl Registry {
     /// Serialized write to the persistent store
     pub async fn store_obj<O: StorableObject>(&self, object: &O) -> Result<(), SvcError> {
         let mut store = self.store.lock().await;
-        match tokio::time::timeout(
-            self.store_timeout,
-            async move { store.put_obj(object).await },
-        )
-        .await
-        {
+        let start = std::time::Instant::now();
+        let result =
+            tokio::time::timeout(
+                self.store_timeout,
+                async move { store.put_obj(object).await },
+            )
+            .await;
+        metrics::observe_store("Put", start, &result);
+        match result {
             Ok(result) => result.map_err(Into::into),
             Err(_) => Err(StoreError::Timeout {
                 operation: "Put".to_string(),
@@ -189,9 +372,11 @@ impl Registry {
     /// Serialized read from the persistent store
     pub async fn load_obj<O: StorableObject>(&self, key: &O::Key) -> Result<O, SvcError> {
         let mut store = self.store.lock().await;
-        match tokio::time::timeout(self.store_timeout, async move { store.get_obj(key).await })
-            .await
-        {
+        let start = std::time::Instant::now();
+        let result =
+            tokio::time::timeout(self.store_timeout, async move { store.get_obj(key).await }).await;
+        metrics::observe_store("Get", start, &result);
+        match result {
             Ok(obj) => Ok(obj?),
             Err(_) => Err(StoreError::Timeout {
                 operation: "Get".to_string(),
@@ -204,12 +389,14 @@ impl Registry {
     /// Serialized delete to the persistent store
     pub async fn delete_kv<K: StoreKey>(&self, key: &K) -> Result<(), SvcError> {
         let mut store = self.store.lock().await;
-        match tokio::time::timeout(
+        let start = std::time::Instant::now();
+        let result = tokio::time::timeout(
             self.store_timeout,
             async move { store.delete_kv(key).await },
         )
-        .await
-        {
+        .await;
+        metrics::observe_store("Delete", start, &result);
+        match result {
             Ok(result) => match result {
                 Ok(_) => Ok(()),
                 // already deleted, no problem
@@ -239,6 +426,70 @@ impl Registry {
             .unwrap_or(false)
     }
 
+    /// Get the health of the core agent, as seen by the last persistent store probe
+    pub(crate) fn core_health(&self) -> CoreHealth {
+        self.health.lock().clone()
+    }
+
+    /// Check if the core agent is in read-only mode, ie: the persistent store is not available
+    /// and so the registry cache can only be used to serve read requests
+    pub(crate) fn read_only(&self) -> bool {
+        self.health.lock().mode == CoreAgentMode::ReadOnly
+    }
+
+    /// Fail fast if the persistent store is not available for writing
+    pub(crate) fn writable(&self) -> Result<(), SvcError> {
+        match self.read_only() {
+            true => Err(SvcError::StoreUnavailable {}),
+            false => Ok(()),
+        }
+    }
+
+    /// Probe the persistent store, returning whether it's online and whether we still hold
+    /// its lease
+    async fn probe_store(&self) -> (bool, bool) {
+        tokio::time::timeout(self.store_timeout, async move {
+            let mut store = self.store.lock().await;
+            (store.online().await, store.lease_active())
+        })
+        .await
+        .unwrap_or((false, false))
+    }
+
+    /// Probe the persistent store at every reconciliation period, switching between the
+    /// read-write and the read-only modes accordingly
+    async fn store_monitor(&self) {
+        loop {
+            let (store_online, store_lease) = self.probe_store().await;
+            let mode = match store_online && store_lease {
+                true => CoreAgentMode::ReadWrite,
+                false => CoreAgentMode::ReadOnly,
+            };
+            let previous = {
+                let mut health = self.health.lock();
+                let previous = health.clone();
+                health.mode = mode;
+                health.store_lease = store_lease;
+                health.store_online = store_online;
+                previous
+            };
+            if previous.mode != mode {
+                match mode {
+                    CoreAgentMode::ReadOnly => tracing::warn!(
+                        store.online = store_online,
+                        store.lease = store_lease,
+                        "Persistent store unavailable, entering read-only mode"
+                    ),
+                    CoreAgentMode::ReadWrite => {
+                        tracing::info!("Persistent store available, leaving read-only mode");
+                        self.notify(PollTriggerEvent::StoreOnline).await;
+                    }
+                }
+            }
+            tokio::time::sleep(self.reconcile_period).await;
+        }
+    }
+
     /// Start the worker thread which updates the registry
     pub async fn start(&self) {
         let registry = self.clone();
@@ -246,6 +497,10 @@ impl Registry {
             registry.poller().await;
         });
         let registry = self.clone();
+        tokio::spawn(async move {
+            registry.store_monitor().await;
+        });
+        let registry = self.clone();
         self.reconciler.start(registry).await;
     }
 
@@ -265,6 +520,11 @@ impl Registry {
         self.specs.init(store.deref_mut()).await;
     }
 
+    /// Get a reference to the reconciler control, which records the status of each reconciler
+    pub(crate) fn reconcilers(&self) -> &ReconcilerControl {
+        &self.reconciler
+    }
+
     /// Send a triggered event signal to the reconciler module
     pub(crate) async fn notify(&self, event: PollTriggerEvent) {
         self.reconciler.notify(PollEvent::Triggered(event)).await
@@ -293,18 +553,26 @@ impl Registry {
         }
     }
 
+    /// Get the number of system-wide rebuilds currently in progress
+    pub(crate) async fn num_rebuilds(&self) -> NumRebuilds {
+        let mut num_rebuilds = 0;
+        for (_id, node_wrapper) in self.nodes.read().await.iter() {
+            num_rebuilds += node_wrapper.read().await.num_rebuilds();
+        }
+        num_rebuilds
+    }
+    /// Get the system-wide maximum number of concurrent rebuilds allowed, if any
+    pub(crate) fn max_rebuilds(&self) -> Option<NumRebuilds> {
+        self.max_rebuilds
+    }
+
     /// Determine if a rebuild is allowed to start.
     /// Constrain the number of system-wide rebuilds to the maximum specified.
     /// If a maximum is not specified, do not limit the number of rebuilds.
     pub(crate) async fn rebuild_allowed(&self) -> Result<(), SvcError> {
         match self.max_rebuilds {
             Some(max_rebuilds) => {
-                let mut num_rebuilds = 0;
-                for (_id, node_wrapper) in self.nodes.read().await.iter() {
-                    num_rebuilds += node_wrapper.read().await.num_rebuilds();
-                }
-
-                if num_rebuilds < max_rebuilds {
+                if self.num_rebuilds().await < max_rebuilds {
                     Ok(())
                 } else {
                     Err(SvcError::MaxRebuilds { max_rebuilds })
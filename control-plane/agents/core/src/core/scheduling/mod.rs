@@ -5,7 +5,7 @@ pub(crate) mod volume;
 use crate::core::scheduling::{
     nexus::GetPersistedNexusChildrenCtx,
     resources::{ChildItem, PoolItem, ReplicaItem},
-    volume::{GetSuitablePoolsContext, VolumeReplicasForNexusCtx},
+    volume::{GetChildForRemovalContext, GetSuitablePoolsContext, VolumeReplicasForNexusCtx},
 };
 use common_lib::types::v0::message_bus::{PoolStatus, PoolTopology};
 use std::{cmp::Ordering, collections::HashMap, future::Future};
@@ -49,6 +49,10 @@ impl NodeFilters {
     pub(crate) fn online(_request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
         item.node.is_online()
     }
+    /// Should not attempt to use cordoned nodes
+    pub(crate) fn cordoned(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
+        !request.registry().specs().node_cordoned(&item.pool.node)
+    }
     /// Should only attempt to use allowed nodes (by the topology)
     pub(crate) fn allowed(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
         request.allowed_nodes().is_empty() || request.allowed_nodes().contains(&item.pool.node)
@@ -138,6 +142,20 @@ impl ChildSorters {
             ord => ord,
         }
     }
+    /// Sort replicas by their node, removing the replicas on cordoned nodes first
+    pub(crate) fn sort_by_cordon(
+        request: &GetChildForRemovalContext,
+        a: &ReplicaItem,
+        b: &ReplicaItem,
+    ) -> std::cmp::Ordering {
+        let specs = request.registry().specs();
+        let cordoned = |item: &ReplicaItem| match specs.get_pool(&item.spec().pool) {
+            Ok(pool) => specs.node_cordoned(&pool.node),
+            Err(_) => false,
+        };
+        // the least preferred replicas are at the front
+        cordoned(b).cmp(&cordoned(a))
+    }
     // sort replicas by their health: prefer healthy replicas over unhealthy
     fn sort_by_health(a: &ReplicaItem, b: &ReplicaItem) -> std::cmp::Ordering {
         match a.child_info() {
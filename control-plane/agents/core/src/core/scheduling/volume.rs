@@ -9,7 +9,7 @@ use crate::core::{
 
 use common::errors::SvcError;
 use common_lib::types::v0::{
-    message_bus::{ChildUri, CreateVolume, VolumeState},
+    message_bus::{ChildUri, CreateVolume, GetVolumeCapacity, ReplicaId, VolumeId, VolumeState},
     store::{nexus::NexusSpec, nexus_persistence::NexusInfo, volume::VolumeSpec},
 };
 
@@ -28,6 +28,17 @@ impl From<&CreateVolume> for GetSuitablePools {
         }
     }
 }
+impl From<&GetVolumeCapacity> for GetSuitablePools {
+    fn from(request: &GetVolumeCapacity) -> Self {
+        // a new volume which doesn't exist yet, and so doesn't use any node
+        Self::from(&CreateVolume {
+            uuid: VolumeId::new(),
+            replicas: request.replicas,
+            topology: request.topology.clone(),
+            ..Default::default()
+        })
+    }
+}
 impl From<&VolumeSpec> for GetSuitablePools {
     fn from(spec: &VolumeSpec) -> Self {
         Self { spec: spec.clone() }
@@ -95,7 +106,9 @@ impl AddVolumeReplica {
             // 3. ideally use only healthy(online) pools with degraded pools as a
             // fallback
             // 4. only one replica per node
+            // 5. cordoned nodes can't be used.
             .filter(NodeFilters::online)
+            .filter(NodeFilters::cordoned)
             .filter(NodeFilters::allowed)
             .filter(NodeFilters::unused)
             .filter(PoolFilters::usable)
@@ -104,6 +117,20 @@ impl AddVolumeReplica {
             // sort pools in order of preference (from least to most number of replicas)
             .sort(PoolSorters::sort_by_replica_count)
     }
+    /// Rules for pool selection when calculating the capacity available for new volumes, which
+    /// are the default rules regardless of the free space of the pools
+    pub(crate) async fn builder_with_capacity_filters(
+        request: impl Into<GetSuitablePools>,
+        registry: &Registry,
+    ) -> Self {
+        Self::builder(request, registry)
+            .await
+            .filter(NodeFilters::online)
+            .filter(NodeFilters::cordoned)
+            .filter(NodeFilters::allowed)
+            .filter(PoolFilters::usable)
+            .filter(PoolFilters::topology)
+    }
 }
 
 #[async_trait::async_trait(?Send)]
@@ -189,6 +216,10 @@ impl std::fmt::Debug for GetChildForRemovalContext {
 }
 
 impl GetChildForRemovalContext {
+    /// Get the registry
+    pub(crate) fn registry(&self) -> &Registry {
+        &self.registry
+    }
     async fn new(registry: &Registry, request: &GetChildForRemoval) -> Result<Self, SvcError> {
         let nexus_info = registry
             .get_nexus_info(
@@ -282,7 +313,9 @@ impl DecreaseVolumeReplica {
     ) -> Result<Self, SvcError> {
         Ok(Self::builder(request, registry)
             .await?
-            .sort(ChildSorters::sort))
+            .sort(ChildSorters::sort)
+            // stable sort, so the replicas on cordoned nodes keep their relative order
+            .sort_ctx(ChildSorters::sort_by_cordon))
     }
     /// Get the `ReplicaRemovalCandidates` for this request, which splits the candidates into
     /// healthy and unhealthy candidates
@@ -330,6 +363,26 @@ impl ReplicaRemovalCandidates {
     pub fn next(&mut self) -> Option<ReplicaItem> {
         self.next_unhealthy().or_else(|| self.next_healthy())
     }
+    /// Get the given replica as the removal candidate.
+    /// As with `next`, a healthy replica is only a candidate if removing it does not compromise
+    /// the volume's redundancy
+    pub fn take(&mut self, replica: &ReplicaId) -> Option<ReplicaItem> {
+        if let Some(index) = self
+            .unhealthy
+            .iter()
+            .position(|r| &r.spec().uuid == replica)
+        {
+            return Some(self.unhealthy.remove(index));
+        }
+        let index = self
+            .healthy
+            .iter()
+            .position(|r| &r.spec().uuid == replica)?;
+        // the healthy candidates are popped from the back
+        let item = self.healthy.remove(index);
+        self.healthy.push(item);
+        self.next_healthy()
+    }
 
     fn new(context: GetChildForRemovalContext, items: Vec<ReplicaItem>) -> Self {
         let has_info = context.nexus_info.is_some();
@@ -371,6 +424,19 @@ impl ResourceFilter for DecreaseVolumeReplica {
         self
     }
 
+    fn sort_ctx<P: FnMut(&Self::Request, &Self::Item, &Self::Item) -> std::cmp::Ordering>(
+        mut self,
+        mut sort: P,
+    ) -> Self {
+        let context = self.context.clone();
+        self.list = self
+            .list
+            .into_iter()
+            .sorted_by(|a, b| sort(&context, a, b))
+            .collect();
+        self
+    }
+
     fn collect(self) -> Vec<Self::Item> {
         self.list
     }
@@ -27,7 +27,7 @@ use snafu::{ResultExt, Snafu};
 use std::{fmt::Debug, ops::Deref, sync::Arc};
 
 #[derive(Debug, Snafu)]
-enum SpecError {
+pub(crate) enum SpecError {
     /// Failed to get entries from the persistent store.
     #[snafu(display("Failed to get entries from store. Error {}", source))]
     StoreGet { source: Box<StoreError> },
@@ -65,6 +65,17 @@ pub trait SpecOperations: Clone + Debug + Sized + StorableObject + OperationSequ
         Self: SpecTransaction<O>,
         Self: StorableObject,
     {
+        if let Err(error) = registry.writable() {
+            // a spec which was never logged to the store only lives in the registry
+            let unlogged = {
+                let spec = locked_spec.lock();
+                spec.status().creating() && spec.operation_result().is_none()
+            };
+            if unlogged {
+                Self::remove_spec(locked_spec, registry);
+            }
+            return Err(error);
+        }
         let guard = locked_spec.operation_guard(mode)?;
         let spec_clone = {
             let mut spec = locked_spec.lock();
@@ -258,6 +269,7 @@ pub trait SpecOperations: Clone + Debug + Sized + StorableObject + OperationSequ
         Self: SpecTransaction<O>,
         Self: StorableObject,
     {
+        registry.writable()?;
         let guard = locked_spec.operation_guard_wait(mode).await?;
         {
             let mut spec = locked_spec.lock();
@@ -366,6 +378,7 @@ pub trait SpecOperations: Clone + Debug + Sized + StorableObject + OperationSequ
         Self: SpecTransaction<Self::UpdateOp>,
         Self: StorableObject,
     {
+        registry.writable()?;
         let guard = locked_spec.operation_guard_wait(mode).await?;
         let spec_clone = {
             let mut spec = locked_spec.lock().clone();
@@ -713,31 +726,44 @@ pub(crate) struct ResourceSpecs {
     pub(crate) nexuses: ResourceMap<NexusId, NexusSpec>,
     pub(crate) pools: ResourceMap<PoolId, PoolSpec>,
     pub(crate) replicas: ResourceMap<ReplicaId, ReplicaSpec>,
+    /// Serializes the updates of the node specs, as they're stored without holding their locks.
+    pub(crate) node_updates: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl ResourceSpecsLocked {
+    /// The resource spec types which are loaded from the persistent store.
+    pub(crate) const SPEC_TYPES: [StorableObjectType; 5] = [
+        StorableObjectType::VolumeSpec,
+        StorableObjectType::NodeSpec,
+        StorableObjectType::NexusSpec,
+        StorableObjectType::PoolSpec,
+        StorableObjectType::ReplicaSpec,
+    ];
+
     pub(crate) fn new() -> Self {
         ResourceSpecsLocked::default()
     }
 
     /// Initialise the resource specs with the content from the persistent store.
     pub(crate) async fn init<S: Store>(&self, store: &mut S) {
-        let spec_types = [
-            StorableObjectType::VolumeSpec,
-            StorableObjectType::NodeSpec,
-            StorableObjectType::NexusSpec,
-            StorableObjectType::PoolSpec,
-            StorableObjectType::ReplicaSpec,
-        ];
-        for spec in &spec_types {
-            if let Err(e) = self.populate_specs(store, *spec).await {
-                panic!("Failed to initialise resource specs. Err {}.", e);
-            }
+        if let Err(e) = self.reload(store).await {
+            panic!("Failed to initialise resource specs. Err {}.", e);
+        }
+    }
+
+    /// Reload the resource specs with the content from the persistent store, replacing the
+    /// current content only once all specs have been successfully loaded.
+    /// Should only be used whilst no references to the current specs are held elsewhere (eg: by a
+    /// standby core agent which is not serving requests), as new Arcs are created for every spec.
+    pub(crate) async fn reload<S: Store>(&self, store: &mut S) -> Result<(), SpecError> {
+        let specs = Self::new();
+        for spec in &Self::SPEC_TYPES {
+            specs.populate_specs(store, *spec).await?;
         }
 
         // patch up the missing replica nexus owners
-        let nexuses = self.get_nexuses();
-        for replica in self.get_replicas() {
+        let nexuses = specs.get_nexuses();
+        for replica in specs.get_replicas() {
             let replica_uuid = replica.lock().uuid.clone();
 
             nexuses
@@ -745,6 +771,9 @@ impl ResourceSpecsLocked {
                 .filter(|n| n.lock().contains_replica(&replica_uuid))
                 .for_each(|n| replica.lock().owners.add_owner(&n.lock().uuid));
         }
+
+        std::mem::swap(&mut *self.0.write(), &mut *specs.0.write());
+        Ok(())
     }
 
     /// Deserialise a vector of serde_json values into specific spec types.
@@ -24,6 +24,11 @@ pub(crate) enum PollTriggerEvent {
     VolumeDegraded,
     /// The Agent is starting up
     Start,
+    /// The persistent store is available again after an outage
+    /// eg: dirty specs may now be flushed to the store
+    StoreOnline,
+    /// An immediate poll was requested via the API
+    Requested,
 }
 
 /// State of a poller
@@ -118,8 +123,17 @@ pub(crate) trait TaskPoller: Send + Sync + std::fmt::Debug {
     #[tracing::instrument(skip(context), level = "trace")]
     async fn try_poll(&mut self, context: &PollContext) -> PollResult {
         tracing::trace!("Entering trace call");
-        let result = if self.poll_ready(context).await {
-            self.poll(context).await
+        let reconcilers = context.registry().reconcilers();
+        let result = if reconcilers.paused(self.name()) {
+            tracing::trace!("Paused via the API");
+            PollResult::Ok(PollerState::Idle)
+        } else if self.pause_when_read_only() && context.registry().read_only() {
+            tracing::trace!("Paused whilst in read-only mode");
+            PollResult::Ok(PollerState::Idle)
+        } else if self.poll_ready(context).await {
+            let result = self.poll(context).await;
+            reconcilers.record(self.name(), &result);
+            result
         } else {
             PollResult::Ok(PollerState::Idle)
         };
@@ -130,6 +144,18 @@ pub(crate) trait TaskPoller: Send + Sync + std::fmt::Debug {
     /// Force poll the poller
     async fn poll(&mut self, context: &PollContext) -> PollResult;
 
+    /// Name of the poller, as used to identify it in the metrics and via the API
+    fn name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
+    /// Whether this poller should be paused whilst the core agent is in read-only mode, which
+    /// is the case for all pollers which need to write to the persistent store
+    fn pause_when_read_only(&self) -> bool {
+        true
+    }
+
     /// Polls the ready state and returns true if ready
     async fn poll_ready(&mut self, context: &PollContext) -> bool {
         match context.event() {
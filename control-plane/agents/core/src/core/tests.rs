@@ -1,10 +1,13 @@
 #![cfg(test)]
 
 use common_lib::{
-    mbus_api::Message,
+    mbus_api::{Message, ReplyErrorKind},
     store::etcd::Etcd,
     types::v0::{
-        message_bus::{self, ChannelVs, Liveness},
+        message_bus::{
+            self, ChannelVs, CoreAgentMode, CreateVolume, Liveness, ReconcilerPollResult,
+            SetReconcilerPaused,
+        },
         openapi::models,
         store::{
             definitions::Store,
@@ -13,6 +16,8 @@ use common_lib::{
     },
 };
 use deployer_cluster::{etcd_client::Client, *};
+use grpc::operations::{registry::traits::RegistryOperations, volume::traits::VolumeOperations};
+use std::{convert::TryInto, time::Duration};
 
 /// Test that the content of the registry is correctly loaded from the persistent store on start up.
 #[tokio::test]
@@ -203,3 +208,266 @@ async fn core_agent_lease_lock() {
     tracing::info!("core: {:?}", core.state);
     assert_eq!(Some(false), core.state.unwrap().running);
 }
+
+/// Test that a standby waits for the store lease lock and takes over once the leader's lease
+/// expires
+#[tokio::test]
+async fn core_agent_standby() {
+    let lease_ttl = std::time::Duration::from_secs(2);
+    let lease_ttl_wait = lease_ttl + std::time::Duration::from_secs(1);
+    let cluster = ClusterBuilder::builder()
+        .with_io_engines(1)
+        .with_agents(vec!["core"])
+        .with_store_lease_ttl(lease_ttl)
+        .build()
+        .await
+        .unwrap();
+
+    let health = cluster
+        .grpc_client()
+        .registry()
+        .get_core_health(None)
+        .await
+        .unwrap();
+    assert!(!health.leader.takeover);
+
+    let mut standby = tokio::spawn(async move {
+        Etcd::new_leased_standby(["0.0.0.0:2379"], ControlPlaneService::CoreAgent, lease_ttl).await
+    });
+
+    // the leader keeps its lease alive, so the standby must keep waiting
+    tokio::time::timeout(lease_ttl_wait, &mut standby)
+        .await
+        .expect_err("The core-agent still holds the lease!");
+
+    // pause the core agent and let its lease expire
+    cluster.composer().pause("core").await.unwrap();
+
+    let standby = tokio::time::timeout(lease_ttl_wait * 2, standby)
+        .await
+        .expect("The standby should take over once the lease expires")
+        .unwrap()
+        .unwrap();
+
+    let svc = ControlPlaneService::CoreAgent;
+    let standby_owner = standby.lease_owner(&svc).expect("Should hold the lease");
+    let mut etcd = Etcd::new("0.0.0.0:2379").await.unwrap();
+    let obj: StoreLeaseOwner = etcd
+        .get_obj(&StoreLeaseOwnerKey::new(&svc))
+        .await
+        .expect("Should exist!");
+    assert_eq!(obj.lease_id(), standby_owner.lease_id());
+
+    // the previous leader has been replaced and should give up
+    cluster.composer().thaw("core").await.unwrap();
+    tokio::time::sleep(lease_ttl_wait).await;
+    let core = cluster.composer().inspect("core").await.unwrap();
+    assert_eq!(Some(false), core.state.unwrap().running);
+}
+
+/// Test that a standby core agent takes over as the leader once the lease of the core agent
+/// expires, serving the resource specs it kept warm whilst on standby
+#[tokio::test]
+async fn core_agent_standby_takeover() {
+    let lease_ttl = std::time::Duration::from_secs(2);
+    let lease_ttl_wait = lease_ttl + std::time::Duration::from_secs(1);
+    let cluster = ClusterBuilder::builder()
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_agents(vec!["core"])
+        .with_core_standby(true)
+        .with_store_lease_ttl(lease_ttl)
+        .build()
+        .await
+        .unwrap();
+
+    let leader = cluster
+        .grpc_client()
+        .registry()
+        .get_core_health(None)
+        .await
+        .unwrap()
+        .leader;
+    assert!(!leader.takeover);
+
+    let volume = cluster
+        .grpc_client()
+        .volume()
+        .create(
+            &CreateVolume {
+                uuid: "1e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    // pause the core agent and let its lease expire
+    cluster.composer().pause("core").await.unwrap();
+
+    let ip = cluster.composer().container_ip("core-standby");
+    let uri = format!("https://{}:50051", ip).parse().unwrap();
+    let standby = grpc::client::CoreClient::new(uri, None).await;
+    let timeout = grpc::context::TimeoutOptions::new()
+        .with_timeout(Duration::from_millis(250))
+        .with_max_retries((lease_ttl_wait * 2).as_millis() as u32 / 350);
+    standby
+        .wait_ready(Some(timeout))
+        .await
+        .expect("The standby should take over once the lease expires");
+
+    let health = standby.registry().get_core_health(None).await.unwrap();
+    assert!(health.leader.takeover);
+    assert_ne!(health.leader.lease_id, leader.lease_id);
+    assert_eq!(health.mode, CoreAgentMode::ReadWrite);
+
+    let volumes = standby
+        .volume()
+        .get(message_bus::Filter::None, None, None)
+        .await
+        .unwrap();
+    let volumes = volumes
+        .entries
+        .iter()
+        .map(|v| v.spec().uuid)
+        .collect::<Vec<_>>();
+    assert_eq!(volumes, vec![volume.spec().uuid]);
+}
+
+/// Test that the core agent switches to read-only mode whilst the persistent store is unavailable
+#[tokio::test]
+async fn core_agent_read_only_mode() {
+    let reconcile_period = Duration::from_millis(250);
+    let store_timeout = Duration::from_millis(250);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_agents(vec!["core"])
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .with_store_timeout(store_timeout)
+        .build()
+        .await
+        .unwrap();
+
+    let registry_client = cluster.grpc_client().registry();
+    let volume_client = cluster.grpc_client().volume();
+    let health = registry_client.get_core_health(None).await.unwrap();
+    assert_eq!(health.mode, CoreAgentMode::ReadWrite);
+
+    cluster.composer().pause("etcd").await.unwrap();
+    tokio::time::sleep((reconcile_period + store_timeout) * 2).await;
+
+    let health = registry_client.get_core_health(None).await.unwrap();
+    assert_eq!(health.mode, CoreAgentMode::ReadOnly);
+    assert!(!health.store_online);
+
+    // reads are still served from the registry cache
+    volume_client
+        .get(message_bus::Filter::None, None, None)
+        .await
+        .expect("Reads should be allowed in read-only mode");
+
+    // whilst writes are rejected straight away
+    let create_volume = CreateVolume {
+        uuid: "1e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap(),
+        size: 5242880,
+        replicas: 1,
+        ..Default::default()
+    };
+    let error = volume_client
+        .create(&create_volume, None)
+        .await
+        .expect_err("Writes should be rejected in read-only mode");
+    assert_eq!(error.kind, ReplyErrorKind::Unavailable);
+
+    cluster.composer().thaw("etcd").await.unwrap();
+    tokio::time::sleep((reconcile_period + store_timeout) * 2).await;
+
+    let health = registry_client.get_core_health(None).await.unwrap();
+    assert_eq!(health.mode, CoreAgentMode::ReadWrite);
+    volume_client.create(&create_volume, None).await.unwrap();
+}
+
+/// Test the introspection and the control of the reconcilers via the registry service
+#[tokio::test]
+async fn core_agent_reconcilers() {
+    let reconcile_period = Duration::from_millis(250);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_io_engines(1)
+        .with_agents(vec!["core"])
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+
+    let registry_client = cluster.grpc_client().registry();
+    tokio::time::sleep(reconcile_period * 2).await;
+
+    let reconcilers = registry_client.get_reconcilers(None).await.unwrap().0;
+    let names = reconcilers
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect::<Vec<_>>();
+    assert!(names.contains(&"VolumeReconciler"));
+    assert!(names.contains(&"NexusGarbageCollector"));
+    assert!(names.contains(&"VolumeGarbageCollector"));
+    let volume = reconcilers
+        .into_iter()
+        .find(|r| r.name == "VolumeReconciler")
+        .unwrap();
+    assert!(!volume.paused);
+    assert!(volume.last_run.is_some());
+    assert_eq!(volume.last_result, Some(ReconcilerPollResult::Idle));
+    assert!(volume.errors.is_empty());
+
+    let pause = |name: &str, paused| SetReconcilerPaused {
+        name: name.to_string(),
+        paused,
+    };
+    let paused = registry_client
+        .set_reconciler_paused(&pause("VolumeReconciler", true), None)
+        .await
+        .unwrap();
+    assert!(paused.paused);
+
+    // a paused reconciler is not polled, not even when a poll is requested
+    tokio::time::sleep(reconcile_period * 2).await;
+    let paused = volume_reconciler(&registry_client).await;
+    registry_client.poll_reconcilers(None).await.unwrap();
+    tokio::time::sleep(reconcile_period).await;
+    assert_eq!(
+        volume_reconciler(&registry_client).await.last_run,
+        paused.last_run
+    );
+
+    let resumed = registry_client
+        .set_reconciler_paused(&pause("VolumeReconciler", false), None)
+        .await
+        .unwrap();
+    assert!(!resumed.paused);
+    registry_client.poll_reconcilers(None).await.unwrap();
+    tokio::time::sleep(reconcile_period).await;
+    assert_ne!(
+        volume_reconciler(&registry_client).await.last_run,
+        paused.last_run
+    );
+
+    let error = registry_client
+        .set_reconciler_paused(&pause("Unknown", true), None)
+        .await
+        .expect_err("Unknown reconciler");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+}
+
+async fn volume_reconciler(client: &impl RegistryOperations) -> message_bus::Reconciler {
+    let reconcilers = client.get_reconcilers(None).await.unwrap().0;
+    reconcilers
+        .into_iter()
+        .find(|r| r.name == "VolumeReconciler")
+        .unwrap()
+}
@@ -4,6 +4,7 @@ use crate::{
         grpc::{GrpcClient, GrpcClientLocked},
         states::{ResourceStates, ResourceStatesLocked},
     },
+    metrics,
     node::service::NodeCommsTimeout,
     NumRebuilds,
 };
@@ -801,14 +802,16 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
 
     async fn create_pool(&self, request: &CreatePool) -> Result<PoolState, SvcError> {
         let mut ctx = self.grpc_client_locked(request.id()).await?;
-        let rpc_pool =
-            ctx.io_engine
-                .create_pool(request.to_rpc())
-                .await
-                .context(GrpcRequestError {
-                    resource: ResourceKind::Pool,
-                    request: "create_pool",
-                })?;
+        let rpc_pool = metrics::io_engine_request(
+            &request.node,
+            "create_pool",
+            ctx.io_engine.create_pool(request.to_rpc()),
+        )
+        .await
+        .context(GrpcRequestError {
+            resource: ResourceKind::Pool,
+            request: "create_pool",
+        })?;
         let pool = rpc_pool_to_bus(&rpc_pool.into_inner(), &request.node);
         let mut ctx = ctx.reconnect(GETS_TIMEOUT).await?;
         self.update_pool_states(ctx.deref_mut()).await?;
@@ -818,14 +821,16 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Destroy a pool on the node via gRPC
     async fn destroy_pool(&self, request: &DestroyPool) -> Result<(), SvcError> {
         let mut ctx = self.grpc_client_locked(request.id()).await?;
-        let _ = ctx
-            .io_engine
-            .destroy_pool(request.to_rpc())
-            .await
-            .context(GrpcRequestError {
-                resource: ResourceKind::Pool,
-                request: "destroy_pool",
-            })?;
+        let _ = metrics::io_engine_request(
+            &request.node,
+            "destroy_pool",
+            ctx.io_engine.destroy_pool(request.to_rpc()),
+        )
+        .await
+        .context(GrpcRequestError {
+            resource: ResourceKind::Pool,
+            request: "destroy_pool",
+        })?;
         let mut ctx = ctx.reconnect(GETS_TIMEOUT).await?;
         self.update_pool_states(ctx.deref_mut()).await?;
         Ok(())
@@ -840,14 +845,16 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
             });
         }
         let mut ctx = self.grpc_client_locked(request.id()).await?;
-        let rpc_replica = ctx
-            .io_engine
-            .create_replica_v2(request.to_rpc())
-            .await
-            .context(GrpcRequestError {
-                resource: ResourceKind::Replica,
-                request: "create_replica",
-            })?;
+        let rpc_replica = metrics::io_engine_request(
+            &request.node,
+            "create_replica_v2",
+            ctx.io_engine.create_replica_v2(request.to_rpc()),
+        )
+        .await
+        .context(GrpcRequestError {
+            resource: ResourceKind::Replica,
+            request: "create_replica",
+        })?;
 
         let replica = rpc_replica_to_bus(&rpc_replica.into_inner(), &request.node)?;
         let mut ctx = ctx.reconnect(GETS_TIMEOUT).await?;
@@ -859,16 +866,18 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Share a replica on the pool via gRPC
     async fn share_replica(&self, request: &ShareReplica) -> Result<String, SvcError> {
         let mut ctx = self.grpc_client_locked(request.id()).await?;
-        let share = ctx
-            .io_engine
-            .share_replica(request.to_rpc())
-            .await
-            .context(GrpcRequestError {
-                resource: ResourceKind::Replica,
-                request: "share_replica",
-            })?
-            .into_inner()
-            .uri;
+        let share = metrics::io_engine_request(
+            &request.node,
+            "share_replica",
+            ctx.io_engine.share_replica(request.to_rpc()),
+        )
+        .await
+        .context(GrpcRequestError {
+            resource: ResourceKind::Replica,
+            request: "share_replica",
+        })?
+        .into_inner()
+        .uri;
         let mut ctx = ctx.reconnect(GETS_TIMEOUT).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
         Ok(share)
@@ -877,16 +886,18 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Unshare a replica on the pool via gRPC
     async fn unshare_replica(&self, request: &UnshareReplica) -> Result<String, SvcError> {
         let mut ctx = self.grpc_client_locked(request.id()).await?;
-        let local_uri = ctx
-            .io_engine
-            .share_replica(request.to_rpc())
-            .await
-            .context(GrpcRequestError {
-                resource: ResourceKind::Replica,
-                request: "unshare_replica",
-            })?
-            .into_inner()
-            .uri;
+        let local_uri = metrics::io_engine_request(
+            &request.node,
+            "share_replica",
+            ctx.io_engine.share_replica(request.to_rpc()),
+        )
+        .await
+        .context(GrpcRequestError {
+            resource: ResourceKind::Replica,
+            request: "unshare_replica",
+        })?
+        .into_inner()
+        .uri;
         let mut ctx = ctx.reconnect(GETS_TIMEOUT).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
         Ok(local_uri)
@@ -895,14 +906,16 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Destroy a replica on the pool via gRPC
     async fn destroy_replica(&self, request: &DestroyReplica) -> Result<(), SvcError> {
         let mut ctx = self.grpc_client_locked(request.id()).await?;
-        let _ = ctx
-            .io_engine
-            .destroy_replica(request.to_rpc())
-            .await
-            .context(GrpcRequestError {
-                resource: ResourceKind::Replica,
-                request: "destroy_replica",
-            })?;
+        let _ = metrics::io_engine_request(
+            &request.node,
+            "destroy_replica",
+            ctx.io_engine.destroy_replica(request.to_rpc()),
+        )
+        .await
+        .context(GrpcRequestError {
+            resource: ResourceKind::Replica,
+            request: "destroy_replica",
+        })?;
         let mut ctx = ctx.reconnect(GETS_TIMEOUT).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
         // todo: remove when CAS-1107 is resolved
@@ -926,14 +939,16 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
             });
         }
         let mut ctx = self.grpc_client_locked(request.id()).await?;
-        let rpc_nexus = ctx
-            .io_engine
-            .create_nexus_v2(request.to_rpc())
-            .await
-            .context(GrpcRequestError {
-                resource: ResourceKind::Nexus,
-                request: "create_nexus",
-            })?;
+        let rpc_nexus = metrics::io_engine_request(
+            &request.node,
+            "create_nexus_v2",
+            ctx.io_engine.create_nexus_v2(request.to_rpc()),
+        )
+        .await
+        .context(GrpcRequestError {
+            resource: ResourceKind::Nexus,
+            request: "create_nexus",
+        })?;
         let mut nexus = rpc_nexus_to_bus(&rpc_nexus.into_inner(), &request.node)?;
         // CAS-1107 - create_nexus_v2 returns NexusV1...
         nexus.name = request.name();
@@ -946,14 +961,16 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Destroy a nexus on the node via gRPC
     async fn destroy_nexus(&self, request: &DestroyNexus) -> Result<(), SvcError> {
         let mut ctx = self.grpc_client_locked(request.id()).await?;
-        let _ = ctx
-            .io_engine
-            .destroy_nexus(request.to_rpc())
-            .await
-            .context(GrpcRequestError {
-                resource: ResourceKind::Nexus,
-                request: "destroy_nexus",
-            })?;
+        let _ = metrics::io_engine_request(
+            &request.node,
+            "destroy_nexus",
+            ctx.io_engine.destroy_nexus(request.to_rpc()),
+        )
+        .await
+        .context(GrpcRequestError {
+            resource: ResourceKind::Nexus,
+            request: "destroy_nexus",
+        })?;
         let mut ctx = ctx.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         Ok(())
@@ -962,14 +979,16 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Share a nexus on the node via gRPC
     async fn share_nexus(&self, request: &ShareNexus) -> Result<String, SvcError> {
         let mut ctx = self.grpc_client_locked(request.id()).await?;
-        let share = ctx
-            .io_engine
-            .publish_nexus(request.to_rpc())
-            .await
-            .context(GrpcRequestError {
-                resource: ResourceKind::Nexus,
-                request: "publish_nexus",
-            })?;
+        let share = metrics::io_engine_request(
+            &request.node,
+            "publish_nexus",
+            ctx.io_engine.publish_nexus(request.to_rpc()),
+        )
+        .await
+        .context(GrpcRequestError {
+            resource: ResourceKind::Nexus,
+            request: "publish_nexus",
+        })?;
         let share = share.into_inner().device_uri;
         let mut ctx = ctx.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
@@ -979,14 +998,16 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Unshare a nexus on the node via gRPC
     async fn unshare_nexus(&self, request: &UnshareNexus) -> Result<(), SvcError> {
         let mut ctx = self.grpc_client_locked(request.id()).await?;
-        let _ = ctx
-            .io_engine
-            .unpublish_nexus(request.to_rpc())
-            .await
-            .context(GrpcRequestError {
-                resource: ResourceKind::Nexus,
-                request: "unpublish_nexus",
-            })?;
+        let _ = metrics::io_engine_request(
+            &request.node,
+            "unpublish_nexus",
+            ctx.io_engine.unpublish_nexus(request.to_rpc()),
+        )
+        .await
+        .context(GrpcRequestError {
+            resource: ResourceKind::Nexus,
+            request: "unpublish_nexus",
+        })?;
         let mut ctx = ctx.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         Ok(())
@@ -995,7 +1016,12 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Add a child to a nexus via gRPC
     async fn add_child(&self, request: &AddNexusChild) -> Result<Child, SvcError> {
         let mut ctx = self.grpc_client_locked(request.id()).await?;
-        let result = ctx.io_engine.add_child_nexus(request.to_rpc()).await;
+        let result = metrics::io_engine_request(
+            &request.node,
+            "add_child_nexus",
+            ctx.io_engine.add_child_nexus(request.to_rpc()),
+        )
+        .await;
         let mut ctx = ctx.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         let rpc_child = match result {
@@ -1027,7 +1053,12 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Remove a child from its parent nexus via gRPC
     async fn remove_child(&self, request: &RemoveNexusChild) -> Result<(), SvcError> {
         let mut ctx = self.grpc_client_locked(request.id()).await?;
-        let result = ctx.io_engine.remove_child_nexus(request.to_rpc()).await;
+        let result = metrics::io_engine_request(
+            &request.node,
+            "remove_child_nexus",
+            ctx.io_engine.remove_child_nexus(request.to_rpc()),
+        )
+        .await;
 
         let mut ctx = ctx.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
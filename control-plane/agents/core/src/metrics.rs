@@ -0,0 +1,302 @@
+//! Prometheus metrics exported by the core agent via the `/metrics` http endpoint.
+//! The resource metrics are collected from the registry on every scrape, whereas the reconciler,
+//! io-engine gRPC and persistent store metrics are recorded as the operations complete.
+
+use crate::core::{
+    registry::Registry,
+    task_poller::{PollResult, PollerState},
+};
+use common_lib::types::v0::{message_bus::NodeId, store::definitions::StoreError};
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use once_cell::sync::Lazy;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge, register_int_gauge_vec,
+    Encoder, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, TextEncoder,
+};
+use std::{collections::HashMap, convert::Infallible, future::Future, net::SocketAddr};
+
+static VOLUMES: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!("core_volumes", "Number of volumes by status", &["status"])
+        .expect("Should register the volumes metric")
+});
+static NODES: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "core_nodes",
+        "Number of io-engine nodes by status",
+        &["status"]
+    )
+    .expect("Should register the nodes metric")
+});
+static POOLS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!("core_pools", "Number of pools by status", &["status"])
+        .expect("Should register the pools metric")
+});
+static POOL_CAPACITY: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "core_pool_capacity_bytes",
+        "Size of the pool in bytes",
+        &["node", "pool"]
+    )
+    .expect("Should register the pool capacity metric")
+});
+static POOL_USED: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "core_pool_used_bytes",
+        "Used bytes from the pool",
+        &["node", "pool"]
+    )
+    .expect("Should register the pool used metric")
+});
+static REBUILDS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "core_rebuilds",
+        "Number of system-wide rebuilds in progress"
+    )
+    .expect("Should register the rebuilds metric")
+});
+static REBUILDS_MAX: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "core_rebuilds_max",
+        "Maximum number of system-wide rebuilds permitted at any given time"
+    )
+    .expect("Should register the maximum rebuilds metric")
+});
+static READ_ONLY: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "core_read_only",
+        "Whether the core agent is in read-only mode as the persistent store is unavailable"
+    )
+    .expect("Should register the read-only metric")
+});
+static RECONCILER_POLL_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "core_reconciler_poll_duration_seconds",
+        "Duration of the reconciler polls",
+        &["reconciler"]
+    )
+    .expect("Should register the reconciler poll duration metric")
+});
+static RECONCILER_POLLS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "core_reconciler_polls_total",
+        "Number of reconciler polls by result",
+        &["reconciler", "result"]
+    )
+    .expect("Should register the reconciler polls metric")
+});
+static IO_ENGINE_REQUEST_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "core_io_engine_request_duration_seconds",
+        "Duration of the gRPC requests to the io-engine nodes, by response code",
+        &["node", "request", "code"]
+    )
+    .expect("Should register the io-engine request duration metric")
+});
+static STORE_OPERATION_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "core_store_operation_duration_seconds",
+        "Duration of the persistent store operations",
+        &["operation", "result"]
+    )
+    .expect("Should register the store operation duration metric")
+});
+
+/// Record the duration and the result of a poll of the `reconciler`
+pub(crate) fn observe_poll(reconciler: &str, duration: std::time::Duration, result: &PollResult) {
+    let result = match result {
+        Ok(PollerState::Idle) => "idle",
+        Ok(PollerState::Busy) => "busy",
+        Err(_) => "error",
+    };
+    RECONCILER_POLL_DURATION
+        .with_label_values(&[reconciler])
+        .observe(duration.as_secs_f64());
+    RECONCILER_POLLS
+        .with_label_values(&[reconciler, result])
+        .inc();
+}
+
+/// Issue the gRPC `request` to the io-engine `node`, recording its duration and response code
+pub(crate) async fn io_engine_request<T>(
+    node: &NodeId,
+    request: &str,
+    future: impl Future<Output = Result<T, tonic::Status>>,
+) -> Result<T, tonic::Status> {
+    let start = std::time::Instant::now();
+    let result = future.await;
+    let code = match &result {
+        Ok(_) => tonic::Code::Ok,
+        Err(status) => status.code(),
+    };
+    IO_ENGINE_REQUEST_DURATION
+        .with_label_values(&[node.as_str(), request, &format!("{:?}", code)])
+        .observe(start.elapsed().as_secs_f64());
+    result
+}
+
+/// Record the duration and the result of the persistent store `operation`
+pub(crate) fn observe_store<T>(
+    operation: &str,
+    start: std::time::Instant,
+    result: &Result<Result<T, StoreError>, tokio::time::error::Elapsed>,
+) {
+    let result = match result {
+        Ok(Ok(_)) => "ok",
+        Ok(Err(StoreError::MissingEntry { .. })) => "missing",
+        Ok(Err(_)) => "error",
+        Err(_) => "timeout",
+    };
+    STORE_OPERATION_DURATION
+        .with_label_values(&[operation, result])
+        .observe(start.elapsed().as_secs_f64());
+}
+
+/// Collect the current resource metrics from the `registry`
+async fn collect(registry: &Registry) {
+    fn set_counts(gauge: &IntGaugeVec, statuses: impl Iterator<Item = String>) {
+        let mut counts = HashMap::<String, i64>::new();
+        for status in statuses {
+            *counts.entry(status).or_default() += 1;
+        }
+        gauge.reset();
+        for (status, count) in counts {
+            gauge.with_label_values(&[&status]).set(count);
+        }
+    }
+
+    let volumes = registry.get_volumes().await;
+    set_counts(
+        &VOLUMES,
+        volumes.iter().map(|v| v.state().status.to_string()),
+    );
+
+    let nodes = registry.get_node_states().await;
+    set_counts(&NODES, nodes.iter().map(|n| n.status.to_string()));
+
+    let pools = registry.get_pool_states_inner().await;
+    set_counts(&POOLS, pools.iter().map(|p| p.status.to_string()));
+    POOL_CAPACITY.reset();
+    POOL_USED.reset();
+    for pool in &pools {
+        let labels = [pool.node.as_str(), pool.id.as_str()];
+        POOL_CAPACITY
+            .with_label_values(&labels)
+            .set(pool.capacity as i64);
+        POOL_USED.with_label_values(&labels).set(pool.used as i64);
+    }
+
+    REBUILDS.set(registry.num_rebuilds().await as i64);
+    if let Some(max_rebuilds) = registry.max_rebuilds() {
+        REBUILDS_MAX.set(max_rebuilds as i64);
+    }
+    READ_ONLY.set(registry.read_only() as i64);
+}
+
+/// Encode all the registered metrics in the prometheus text format
+async fn metrics(registry: &Registry) -> Response<Body> {
+    collect(registry).await;
+
+    let encoder = TextEncoder::new();
+    let mut buffer = vec![];
+    match encoder.encode(&prometheus::gather(), &mut buffer) {
+        Ok(_) => Response::builder()
+            .header(hyper::header::CONTENT_TYPE, encoder.format_type())
+            .body(Body::from(buffer)),
+        Err(error) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from(error.to_string())),
+    }
+    .unwrap_or_default()
+}
+
+/// Serve the prometheus metrics on the `/metrics` path of the http `endpoint`
+pub(crate) async fn serve(endpoint: SocketAddr, registry: Registry) {
+    let service = make_service_fn(move |_| {
+        let registry = registry.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                let registry = registry.clone();
+                async move {
+                    Ok::<_, Infallible>(match (request.method(), request.uri().path()) {
+                        (&Method::GET, "/metrics") => metrics(&registry).await,
+                        _ => Response::builder()
+                            .status(StatusCode::NOT_FOUND)
+                            .body(Body::empty())
+                            .unwrap_or_default(),
+                    })
+                }
+            }))
+        }
+    });
+
+    tracing::info!("Serving metrics on http://{}/metrics", endpoint);
+    if let Err(error) = Server::bind(&endpoint).serve(service).await {
+        tracing::error!(%error, "Failed to serve the metrics");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{io_engine_request, IO_ENGINE_REQUEST_DURATION};
+    use common_lib::types::v0::message_bus::NodeId;
+    use deployer_cluster::ClusterBuilder;
+
+    #[tokio::test]
+    async fn io_engine_request_metrics() {
+        let node = NodeId::from("io-engine-metrics");
+        let samples = |code: &str| {
+            IO_ENGINE_REQUEST_DURATION
+                .with_label_values(&[node.as_str(), "create_pool", code])
+                .get_sample_count()
+        };
+
+        let reply = io_engine_request(&node, "create_pool", async {
+            Ok::<_, tonic::Status>("pool")
+        })
+        .await;
+        assert_eq!(reply.unwrap(), "pool");
+        assert_eq!(samples("Ok"), 1);
+        assert_eq!(samples("Unavailable"), 0);
+
+        let reply = io_engine_request(&node, "create_pool", async {
+            Err::<(), _>(tonic::Status::unavailable("node is offline"))
+        })
+        .await;
+        assert_eq!(reply.unwrap_err().code(), tonic::Code::Unavailable);
+        assert_eq!(samples("Ok"), 1);
+        assert_eq!(samples("Unavailable"), 1);
+    }
+
+    #[tokio::test]
+    async fn metrics_endpoint() {
+        let cluster = ClusterBuilder::builder()
+            .with_io_engines(1)
+            .with_pools(1)
+            .with_agents(vec!["core"])
+            .build()
+            .await
+            .unwrap();
+
+        let endpoint = format!("http://{}:9502", cluster.composer().container_ip("core"));
+        let response = reqwest::get(format!("{}/metrics", endpoint)).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        let metrics = response.text().await.unwrap();
+        tracing::info!("metrics: {}", metrics);
+
+        assert!(metrics.contains("core_nodes{status=\"Online\"} 1\n"));
+        assert!(metrics.contains("core_pools{status=\"Online\"} 1\n"));
+        assert!(metrics.contains("core_read_only 0\n"));
+        // the pool was created by the core agent via the io-engine gRPC
+        let create_pool = format!(
+            "core_io_engine_request_duration_seconds_count{{code=\"Ok\",node=\"{}\",request=\"create_pool\"}} 1\n",
+            cluster.node(0)
+        );
+        assert!(metrics.contains(&create_pool));
+
+        let response = reqwest::get(format!("{}/health", endpoint)).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+    }
+}
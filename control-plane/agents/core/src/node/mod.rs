@@ -40,11 +40,14 @@ async fn create_node_service(builder: &Service) -> service::Service {
 mod tests {
     use super::*;
     use common_lib::types::v0::{
-        message_bus::{Filter, Node, NodeId, NodeState, NodeStatus},
+        message_bus::{
+            CordonNode, CreateVolume, Filter, Node, NodeId, NodeState, NodeStatus, PublishVolume,
+            UncordonNode,
+        },
         store::node::{NodeLabels, NodeSpec},
     };
     use deployer_cluster::ClusterBuilder;
-    use grpc::operations::node::traits::NodeOperations;
+    use grpc::operations::{node::traits::NodeOperations, volume::traits::VolumeOperations};
     use std::time::Duration;
 
     /// Get new `Node` from the given parameters
@@ -135,6 +138,70 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn node_cordon() {
+        let cluster = ClusterBuilder::builder()
+            .with_rest(false)
+            .with_agents(vec!["core"])
+            .with_pools(1)
+            .build()
+            .await
+            .unwrap();
+
+        let node_client = cluster.grpc_client().node();
+        let volume_client = cluster.grpc_client().volume();
+        let io_engine = cluster.node(0);
+        let create_volume = CreateVolume {
+            uuid: "1e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap(),
+            size: 5242880,
+            replicas: 1,
+            ..Default::default()
+        };
+
+        let node = node_client
+            .cordon(&CordonNode::new(&io_engine), None)
+            .await
+            .unwrap();
+        assert!(node.spec().unwrap().cordoned());
+
+        volume_client
+            .create(&create_volume, None)
+            .await
+            .expect_err("Should not create replicas on a cordoned node");
+
+        let node = node_client
+            .uncordon(&UncordonNode::new(&io_engine), None)
+            .await
+            .unwrap();
+        assert!(!node.spec().unwrap().cordoned());
+
+        volume_client.create(&create_volume, None).await.unwrap();
+
+        // a cordoned node is not selected as a volume target, unless it is requested
+        node_client
+            .cordon(&CordonNode::new(&io_engine), None)
+            .await
+            .unwrap();
+        let mut publish_volume = PublishVolume {
+            uuid: create_volume.uuid.clone(),
+            target_node: None,
+            share: None,
+        };
+        volume_client
+            .publish(&publish_volume, None)
+            .await
+            .expect_err("Should not select a cordoned node as the volume target");
+        publish_volume.target_node = Some(io_engine.clone());
+        let volume = volume_client.publish(&publish_volume, None).await.unwrap();
+        assert_eq!(volume.spec().target.unwrap().node(), &io_engine);
+
+        let error = node_client
+            .cordon(&CordonNode::new(&"missing".into()), None)
+            .await
+            .expect_err("Should not cordon a node which does not exist");
+        assert_eq!(error.kind, ReplyErrorKind::NotFound);
+    }
+
     #[tokio::test]
     async fn large_cluster() {
         let expected_nodes = 2;
@@ -8,14 +8,15 @@ use common::{
     v0::msg_translation::RpcToMessageBus,
 };
 use common_lib::types::v0::message_bus::{
-    Deregister, Filter, Node, NodeId, NodeState, NodeStatus, Register, States,
+    CordonNode, Deregister, Filter, Node, NodeId, NodeState, NodeStatus, Register, States,
+    UncordonNode,
 };
 
 use crate::core::wrapper::InternalOps;
 use grpc::{
     context::Context,
     operations::{
-        node::traits::{GetBlockDeviceInfo, NodeOperations},
+        node::traits::{CordonNodeInfo, GetBlockDeviceInfo, NodeOperations},
         registration::traits::{DeregisterInfo, RegisterInfo, RegistrationOperations},
     },
 };
@@ -78,6 +79,28 @@ impl NodeOperations for Service {
         let blockdevices = self.get_block_devices(&req).await?;
         Ok(blockdevices)
     }
+
+    async fn cordon(
+        &self,
+        node: &dyn CordonNodeInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Node, ReplyError> {
+        let req = node.into();
+        let service = self.clone();
+        let node = Context::spawn(async move { service.cordon_node(&req).await }).await??;
+        Ok(node)
+    }
+
+    async fn uncordon(
+        &self,
+        node: &dyn CordonNodeInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Node, ReplyError> {
+        let req = node.into();
+        let service = self.clone();
+        let node = Context::spawn(async move { service.uncordon_node(&req).await }).await??;
+        Ok(node)
+    }
 }
 
 #[tonic::async_trait]
@@ -267,6 +290,36 @@ impl Service {
         }
     }
 
+    /// Cordon a node, so it is not used for new replicas and volume targets
+    #[tracing::instrument(level = "info", skip(self), err, fields(node.id = %request.id))]
+    pub(super) async fn cordon_node(&self, request: &CordonNode) -> Result<Node, SvcError> {
+        self.specs()
+            .set_node_cordoned(&self.registry, &request.id, true)
+            .await?;
+        self.get_node(&request.id).await
+    }
+
+    /// Uncordon a node, so it can be used for new replicas and volume targets again
+    #[tracing::instrument(level = "info", skip(self), err, fields(node.id = %request.id))]
+    pub(super) async fn uncordon_node(&self, request: &UncordonNode) -> Result<Node, SvcError> {
+        self.specs()
+            .set_node_cordoned(&self.registry, &request.id, false)
+            .await?;
+        self.get_node(&request.id).await
+    }
+
+    /// Get a single node by its id
+    async fn get_node(&self, node_id: &NodeId) -> Result<Node, SvcError> {
+        let nodes = self.get_nodes(&GetNodes::from(node_id.clone())).await?;
+        nodes
+            .into_inner()
+            .into_iter()
+            .next()
+            .ok_or(SvcError::NodeNotFound {
+                node_id: node_id.clone(),
+            })
+    }
+
     /// Get block devices from a node
     pub(crate) async fn get_block_devices(
         &self,
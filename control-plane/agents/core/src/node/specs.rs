@@ -15,6 +15,8 @@ impl ResourceSpecsLocked {
         registry: &Registry,
         node: &Register,
     ) -> Result<NodeSpec, SvcError> {
+        let node_updates = self.read().node_updates.clone();
+        let _update = node_updates.lock().await;
         let (changed, node) = {
             let mut specs = self.write();
             match specs.nodes.get(&node.id) {
@@ -42,6 +44,38 @@ impl ResourceSpecsLocked {
         Ok(node)
     }
 
+    /// Cordon or uncordon the node with the given `NodeId`
+    /// Cordoned nodes are not used for new replicas and volume targets.
+    pub(crate) async fn set_node_cordoned(
+        &self,
+        registry: &Registry,
+        node_id: &NodeId,
+        cordoned: bool,
+    ) -> Result<NodeSpec, SvcError> {
+        let node_spec = self.get_locked_node(node_id)?;
+        registry.writable()?;
+        let node_updates = self.read().node_updates.clone();
+        let _update = node_updates.lock().await;
+
+        let spec_clone = {
+            let spec = node_spec.lock();
+            if spec.cordoned() == cordoned {
+                return Ok(spec.clone());
+            }
+            spec.clone().with_cordoned(cordoned)
+        };
+        registry.store_obj(&spec_clone).await?;
+        node_spec.lock().set_cordoned(cordoned);
+        Ok(spec_clone)
+    }
+
+    /// Check if the node with the given `NodeId` is cordoned
+    pub(crate) fn node_cordoned(&self, node_id: &NodeId) -> bool {
+        self.get_locked_node(node_id)
+            .map(|node| node.lock().cordoned())
+            .unwrap_or_default()
+    }
+
     /// Get node spec by its `NodeId`
     pub(crate) fn get_locked_node(
         &self,
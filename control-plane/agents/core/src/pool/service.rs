@@ -8,7 +8,7 @@ use common_lib::{
     types::v0::{
         message_bus::{
             CreatePool, CreateReplica, DestroyPool, DestroyReplica, Filter, GetPools, GetReplicas,
-            NodeId, Pool, PoolId, Replica, ShareReplica, UnshareReplica,
+            LabelPool, NodeId, Pool, PoolId, Replica, ShareReplica, UnshareReplica,
         },
         store::OperationMode,
     },
@@ -16,7 +16,7 @@ use common_lib::{
 use grpc::{
     context::Context,
     operations::{
-        pool::traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
+        pool::traits::{CreatePoolInfo, DestroyPoolInfo, LabelPoolInfo, PoolOperations},
         replica::traits::{
             CreateReplicaInfo, DestroyReplicaInfo, ReplicaOperations, ShareReplicaInfo,
             UnshareReplicaInfo,
@@ -59,6 +59,17 @@ impl PoolOperations for Service {
         let pools = self.get_pools(&req).await?;
         Ok(pools)
     }
+
+    async fn label(
+        &self,
+        pool: &dyn LabelPoolInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let req = pool.into();
+        let service = self.clone();
+        let pool = Context::spawn(async move { service.label_pool(&req).await }).await??;
+        Ok(pool)
+    }
 }
 
 #[tonic::async_trait]
@@ -251,6 +262,14 @@ impl Service {
             .await
     }
 
+    /// Label pool
+    #[tracing::instrument(level = "info", skip(self), err, fields(pool.uuid = %request.id))]
+    pub(super) async fn label_pool(&self, request: &LabelPool) -> Result<Pool, SvcError> {
+        self.specs()
+            .label_pool(&self.registry, request, OperationMode::Exclusive)
+            .await
+    }
+
     /// Create replica
     #[tracing::instrument(level = "info", skip(self), err, fields(replica.uuid = %request.uuid))]
     pub(super) async fn create_replica(
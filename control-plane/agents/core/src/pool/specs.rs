@@ -8,8 +8,8 @@ use common_lib::{
     mbus_api::ResourceKind,
     types::v0::{
         message_bus::{
-            CreatePool, CreateReplica, DestroyPool, DestroyReplica, Pool, PoolId, PoolState,
-            PoolStatus, Replica, ReplicaId, ReplicaOwners, ReplicaStatus, ShareReplica,
+            CreatePool, CreateReplica, DestroyPool, DestroyReplica, LabelPool, Pool, PoolId,
+            PoolState, PoolStatus, Replica, ReplicaId, ReplicaOwners, ReplicaStatus, ShareReplica,
             UnshareReplica,
         },
         store::{
@@ -28,8 +28,35 @@ impl SpecOperations for PoolSpec {
     type Owners = ();
     type Status = PoolStatus;
     type State = PoolState;
-    type UpdateOp = ();
+    type UpdateOp = PoolOperation;
 
+    async fn start_update_op(
+        &mut self,
+        _: &Registry,
+        _: &Self::State,
+        op: Self::UpdateOp,
+    ) -> Result<(), SvcError> {
+        match &op {
+            PoolOperation::Label(request) if !request.overwrite => {
+                let labels = self.labels.clone().unwrap_or_default();
+                match request
+                    .labels
+                    .iter()
+                    .find(|(key, value)| matches!(labels.get(*key), Some(v) if v != *value))
+                {
+                    Some((key, _)) => Err(SvcError::PoolLabelExists {
+                        key: key.clone(),
+                        id: self.id.to_string(),
+                    }),
+                    None => Ok(()),
+                }
+            }
+            PoolOperation::Label(_) => Ok(()),
+            _ => unreachable!(),
+        }?;
+        self.start_op(op);
+        Ok(())
+    }
     fn validate_destroy(
         locked_spec: &Arc<Mutex<Self>>,
         registry: &Registry,
@@ -227,6 +254,32 @@ impl ResourceSpecsLocked {
         }
     }
 
+    /// Sets and removes the labels of a pool on the given `LabelPool` request
+    /// A label which is already set with a different value is only changed if overwrite is set.
+    pub(crate) async fn label_pool(
+        &self,
+        registry: &Registry,
+        request: &LabelPool,
+        mode: OperationMode,
+    ) -> Result<Pool, SvcError> {
+        let pool_spec = self.get_locked_pool(&request.id).ok_or(PoolNotFound {
+            pool_id: request.id.clone(),
+        })?;
+        let state = registry.get_pool_state(&request.id).await?;
+
+        let (spec_clone, _guard) = SpecOperations::start_update(
+            registry,
+            &pool_spec,
+            &state,
+            PoolOperation::Label(request.clone()),
+            mode,
+        )
+        .await?;
+
+        SpecOperations::complete_update(registry, Ok(()), pool_spec, spec_clone).await?;
+        registry.get_pool(&request.id).await
+    }
+
     pub(crate) async fn create_replica(
         &self,
         registry: &Registry,
@@ -4,8 +4,8 @@ use common_lib::{
     mbus_api::{ReplyError, ReplyErrorKind, ResourceKind, TimeoutOptions},
     types::v0::{
         message_bus::{
-            CreatePool, CreateReplica, DestroyPool, DestroyReplica, Filter, GetSpecs, NodeId,
-            Protocol, Replica, ReplicaId, ReplicaName, ReplicaShareProtocol, ReplicaStatus,
+            CreatePool, CreateReplica, DestroyPool, DestroyReplica, Filter, GetSpecs, LabelPool,
+            NodeId, Protocol, Replica, ReplicaId, ReplicaName, ReplicaShareProtocol, ReplicaStatus,
             ShareReplica, UnshareReplica, VolumeId,
         },
         openapi::{
@@ -25,7 +25,7 @@ use grpc::{
     },
 };
 use itertools::Itertools;
-use std::{convert::TryFrom, time::Duration};
+use std::{collections::HashMap, convert::TryFrom, time::Duration};
 
 #[tokio::test]
 async fn pool() {
@@ -179,6 +179,82 @@ async fn pool() {
         .is_empty());
 }
 
+#[tokio::test]
+async fn pool_labels() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .build()
+        .await
+        .unwrap();
+
+    let pool_client = cluster.grpc_client().pool();
+    let io_engine = cluster.node(0);
+    let labels = |pairs: &[(&str, &str)]| {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>()
+    };
+
+    pool_client
+        .create(
+            &CreatePool {
+                node: io_engine.clone(),
+                id: "pooloop".into(),
+                disks: vec!["malloc:///disk0?size_mb=100".into()],
+                labels: Some(labels(&[("zone", "a")])),
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let pool = pool_client
+        .label(
+            &LabelPool::new(&"pooloop".into(), labels(&[("tier", "ssd")]), vec![], false),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        pool.spec().unwrap().labels,
+        Some(labels(&[("zone", "a"), ("tier", "ssd")]))
+    );
+
+    let error = pool_client
+        .label(
+            &LabelPool::new(&"pooloop".into(), labels(&[("zone", "b")]), vec![], false),
+            None,
+        )
+        .await
+        .expect_err("Should not change the value of an existing label without overwrite");
+    assert_eq!(error.kind, ReplyErrorKind::AlreadyExists);
+
+    let pool = pool_client
+        .label(
+            &LabelPool::new(
+                &"pooloop".into(),
+                labels(&[("zone", "b")]),
+                vec!["tier".to_string()],
+                true,
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(pool.spec().unwrap().labels, Some(labels(&[("zone", "b")])));
+
+    let error = pool_client
+        .label(
+            &LabelPool::new(&"missing".into(), labels(&[("zone", "b")]), vec![], true),
+            None,
+        )
+        .await
+        .expect_err("Should not label a pool which does not exist");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+}
+
 /// The tests below revolve around transactions and are dependent on the core agent's command line
 /// arguments for timeouts.
 /// This is required because as of now, we don't have a good mocking strategy
@@ -2,11 +2,11 @@ use crate::{core, core::specs::ResourceSpecsLocked};
 use common::errors::SvcError;
 use common_lib::{
     mbus_api::ReplyError,
-    types::v0::message_bus::{GetSpecs, Specs},
+    types::v0::message_bus::{CoreHealth, GetSpecs, Reconciler, Reconcilers, Specs},
 };
 use grpc::{
     context::Context,
-    operations::registry::traits::{GetSpecsInfo, RegistryOperations},
+    operations::registry::traits::{GetSpecsInfo, RegistryOperations, SetReconcilerPausedInfo},
 };
 
 /// Registry Service
@@ -26,6 +26,31 @@ impl RegistryOperations for Service {
         let specs = self.get_specs(&req).await?;
         Ok(specs)
     }
+
+    async fn get_core_health(&self, _ctx: Option<Context>) -> Result<CoreHealth, ReplyError> {
+        Ok(self.registry.core_health())
+    }
+
+    async fn get_reconcilers(&self, _ctx: Option<Context>) -> Result<Reconcilers, ReplyError> {
+        Ok(self.registry.reconcilers().reconcilers())
+    }
+
+    async fn set_reconciler_paused(
+        &self,
+        request: &dyn SetReconcilerPausedInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Reconciler, ReplyError> {
+        let reconciler = self
+            .registry
+            .reconcilers()
+            .set_paused(&request.name(), request.paused())?;
+        Ok(reconciler)
+    }
+
+    async fn poll_reconcilers(&self, _ctx: Option<Context>) -> Result<(), ReplyError> {
+        self.registry.reconcilers().poll_now().await;
+        Ok(())
+    }
 }
 
 impl Service {
@@ -1,6 +1,8 @@
 pub mod core;
 /// Services to launch the grpc server
 pub mod lib;
+/// Prometheus metrics
+pub mod metrics;
 pub mod nexus;
 pub mod node;
 pub mod pool;
@@ -55,6 +57,14 @@ pub(crate) struct CliArgs {
     #[structopt(long, default_value = utils::STORE_LEASE_LOCK_TTL)]
     pub(crate) store_lease_ttl: humantime::Duration,
 
+    /// Wait as a standby if another core agent instance holds the persistent store lease lock,
+    /// rather than failing to start.
+    /// The standby keeps its registry cache warm and takes over, starting the reconcilers, the
+    /// gRPC server and the message bus subscriptions, once the leader's lease expires, ie: within
+    /// the `store-lease-ttl`.
+    #[structopt(long)]
+    pub(crate) standby: bool,
+
     /// The timeout for every node connection (gRPC)
     #[structopt(long, default_value = utils::DEFAULT_CONN_TIMEOUT)]
     pub(crate) connect_timeout: humantime::Duration,
@@ -77,6 +87,12 @@ pub(crate) struct CliArgs {
     /// (supports the http/https schema)
     #[structopt(long, short, default_value = DEFAULT_GRPC_SERVER_ADDR)]
     pub(crate) grpc_server_addr: Uri,
+    /// The http endpoint on which the prometheus metrics are served, on the `/metrics` path
+    #[structopt(long, default_value = "[::]:9502")]
+    metrics_endpoint: std::net::SocketAddr,
+    /// Don't serve the prometheus metrics
+    #[structopt(long)]
+    disable_metrics: bool,
     /// The maximum number of system-wide rebuilds permitted at any given time.
     /// If `None` do not limit the number of rebuilds.
     #[structopt(long)]
@@ -111,6 +127,7 @@ async fn server(cli_args: CliArgs) {
         cli_args.reconcile_period.into(),
         cli_args.reconcile_idle_period.into(),
         cli_args.max_rebuilds,
+        cli_args.standby,
     )
     .await;
 
@@ -132,6 +149,10 @@ async fn server(cli_args: CliArgs) {
         .configure(watcher::configure)
         .configure(registry::configure);
 
+    if !cli_args.disable_metrics {
+        tokio::spawn(metrics::serve(cli_args.metrics_endpoint, registry.clone()));
+    }
+
     let service = lib::Service::new(base_service);
     registry.start().await;
     service.run().await;
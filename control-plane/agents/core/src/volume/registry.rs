@@ -101,7 +101,7 @@ impl Registry {
     }
 
     /// Get all volumes
-    pub(super) async fn get_volumes(&self) -> Vec<Volume> {
+    pub(crate) async fn get_volumes(&self) -> Vec<Volume> {
         let volume_specs = self.specs().get_volumes();
         let replicas = self.specs().get_cloned_replicas();
         let mut volumes = Vec::with_capacity(volume_specs.len());
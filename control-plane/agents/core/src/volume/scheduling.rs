@@ -11,7 +11,11 @@ use crate::core::{
     wrapper::PoolWrapper,
 };
 use common::errors::SvcError;
-use common_lib::types::v0::store::{nexus::NexusSpec, volume::VolumeSpec};
+use common_lib::types::v0::{
+    message_bus::{GetVolumeCapacity, NodeId, VolumeCapacity},
+    store::{nexus::NexusSpec, volume::VolumeSpec},
+};
+use std::collections::HashMap;
 
 /// Return a list of pre sorted pools to be used by a volume
 pub(crate) async fn get_volume_pool_candidates(
@@ -26,6 +30,49 @@ pub(crate) async fn get_volume_pool_candidates(
         .collect()
 }
 
+/// Return the capacity available for the creation of new volumes, as calculated from the free
+/// space of the pools which may be used for their replicas
+pub(crate) async fn get_volume_capacity(
+    request: &GetVolumeCapacity,
+    registry: &Registry,
+) -> VolumeCapacity {
+    let pools = volume::AddVolumeReplica::builder_with_capacity_filters(request, registry)
+        .await
+        .collect();
+
+    // the replicas of a volume must be placed on different nodes, so only the pool with the
+    // most free space is considered for each node
+    let mut node_free_space = HashMap::<NodeId, u64>::new();
+    for item in pools {
+        let free_space = node_free_space.entry(item.pool.node.clone()).or_default();
+        *free_space = (*free_space).max(item.pool.free_space());
+    }
+    let mut free_space = node_free_space
+        .into_iter()
+        .map(|(_, free_space)| free_space)
+        .collect::<Vec<_>>();
+    free_space.sort_unstable_by(|a, b| b.cmp(a));
+
+    let replicas = request.replicas.max(1) as usize;
+    if free_space.len() < replicas {
+        return VolumeCapacity::default();
+    }
+    // the nodes with more free space than the total size of the volumes can only hold one replica
+    // of each volume, so their free space is capped at that size
+    let available_capacity = (0 .. replicas)
+        .map(|capped| {
+            let uncapped = free_space[capped ..].iter().sum::<u64>();
+            (capped, uncapped / (replicas - capped) as u64)
+        })
+        .find(|(capped, capacity)| free_space[*capped] <= *capacity)
+        .map(|(_, capacity)| capacity)
+        .unwrap_or_default();
+    VolumeCapacity {
+        available_capacity,
+        maximum_volume_size: free_space[replicas - 1],
+    }
+}
+
 /// Return a volume child candidate to be removed from a volume
 /// This list includes healthy and non_healthy candidates, so care must be taken to
 /// make sure we don't remove "too many healthy" candidates and make the volume degraded
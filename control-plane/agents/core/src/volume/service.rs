@@ -1,11 +1,15 @@
-use crate::core::{registry::Registry, specs::ResourceSpecsLocked};
+use crate::{
+    core::{registry::Registry, specs::ResourceSpecsLocked},
+    volume::scheduling,
+};
 use common::errors::SvcError;
 use common_lib::{
     mbus_api::{message_bus::v0::Volumes, ReplyError},
     types::v0::{
         message_bus::{
-            CreateVolume, DestroyVolume, Filter, GetVolumes, PublishVolume, SetVolumeReplica,
-            ShareVolume, UnpublishVolume, UnshareVolume, Volume,
+            CreateVolume, CreateVolumeReplica, DestroyVolume, Filter, GetVolumeCapacity,
+            GetVolumes, PublishVolume, RemoveVolumeReplica, SetVolumeReplica, ShareVolume,
+            UnpublishVolume, UnshareVolume, Volume, VolumeCapacity,
         },
         store::OperationMode,
     },
@@ -14,8 +18,9 @@ use grpc::{
     context::Context,
     operations::{
         volume::traits::{
-            CreateVolumeInfo, DestroyVolumeInfo, PublishVolumeInfo, SetVolumeReplicaInfo,
-            ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo, VolumeOperations,
+            CreateVolumeInfo, CreateVolumeReplicaInfo, DestroyVolumeInfo, GetVolumeCapacityInfo,
+            PublishVolumeInfo, RemoveVolumeReplicaInfo, SetVolumeReplicaInfo, ShareVolumeInfo,
+            UnpublishVolumeInfo, UnshareVolumeInfo, VolumeOperations,
         },
         Pagination,
     },
@@ -123,6 +128,46 @@ impl VolumeOperations for Service {
         Ok(volume)
     }
 
+    async fn create_replica(
+        &self,
+        req: &dyn CreateVolumeReplicaInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let create_volume_replica = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(
+                async move { service.create_volume_replica(&create_volume_replica).await },
+            )
+            .await??;
+        Ok(volume)
+    }
+
+    async fn remove_replica(
+        &self,
+        req: &dyn RemoveVolumeReplicaInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let remove_volume_replica = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(
+                async move { service.remove_volume_replica(&remove_volume_replica).await },
+            )
+            .await??;
+        Ok(volume)
+    }
+
+    async fn get_capacity(
+        &self,
+        req: &dyn GetVolumeCapacityInfo,
+        _ctx: Option<Context>,
+    ) -> Result<VolumeCapacity, ReplyError> {
+        let req = req.into();
+        let capacity = self.get_volume_capacity(&req).await?;
+        Ok(capacity)
+    }
+
     async fn probe(&self, _ctx: Option<Context>) -> Result<bool, ReplyError> {
         return Ok(true);
     }
@@ -237,4 +282,35 @@ impl Service {
             .set_volume_replica(&self.registry, request, OperationMode::Exclusive)
             .await
     }
+
+    /// Add a replica to the volume on the given pool
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn create_volume_replica(
+        &self,
+        request: &CreateVolumeReplica,
+    ) -> Result<Volume, SvcError> {
+        self.specs()
+            .create_volume_pool_replica(&self.registry, request, OperationMode::Exclusive)
+            .await
+    }
+
+    /// Remove the given replica from the volume
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn remove_volume_replica(
+        &self,
+        request: &RemoveVolumeReplica,
+    ) -> Result<Volume, SvcError> {
+        self.specs()
+            .remove_volume_replica(&self.registry, request, OperationMode::Exclusive)
+            .await
+    }
+
+    /// Get the capacity available for new volumes
+    #[tracing::instrument(level = "info", skip(self), err)]
+    pub(super) async fn get_volume_capacity(
+        &self,
+        request: &GetVolumeCapacity,
+    ) -> Result<VolumeCapacity, SvcError> {
+        Ok(scheduling::get_volume_capacity(request, &self.registry).await)
+    }
 }
@@ -26,11 +26,12 @@ use common_lib::{
     mbus_api::{ErrorChain, ResourceKind},
     types::v0::{
         message_bus::{
-            AddNexusReplica, ChildUri, CreateNexus, CreateReplica, CreateVolume, DestroyNexus,
-            DestroyReplica, DestroyVolume, Nexus, NexusId, NodeId, PoolId, Protocol, PublishVolume,
-            RemoveNexusReplica, Replica, ReplicaId, ReplicaName, ReplicaOwners, SetVolumeReplica,
-            ShareNexus, ShareVolume, UnpublishVolume, UnshareNexus, UnshareVolume, Volume,
-            VolumeId, VolumeShareProtocol, VolumeState, VolumeStatus,
+            AddNexusReplica, ChildUri, CreateNexus, CreateReplica, CreateVolume,
+            CreateVolumeReplica, DestroyNexus, DestroyReplica, DestroyVolume, Nexus, NexusId,
+            NodeId, PoolId, Protocol, PublishVolume, RemoveNexusReplica, RemoveVolumeReplica,
+            Replica, ReplicaId, ReplicaName, ReplicaOwners, SetVolumeReplica, ShareNexus,
+            ShareVolume, UnpublishVolume, UnshareNexus, UnshareVolume, Volume, VolumeId,
+            VolumeShareProtocol, VolumeState, VolumeStatus,
         },
         store::{
             definitions::ObjectKey,
@@ -68,6 +69,29 @@ pub(crate) async fn get_volume_replica_remove_candidate(
         .context(errors::ReplicaRemovalNoCandidates { id: spec.uuid() })
 }
 
+/// Select the given replica to be removed from the volume
+/// Fails if the replica is the last healthy replica which the volume needs
+pub(crate) async fn get_volume_replica_remove_target(
+    spec: &VolumeSpec,
+    state: &VolumeState,
+    replica: &ReplicaId,
+    registry: &Registry,
+) -> Result<ReplicaItem, SvcError> {
+    let mut candidates = scheduling::get_volume_replica_remove_candidates(
+        &GetChildForRemoval::new(spec, state, false),
+        registry,
+    )
+    .await?
+    .candidates();
+
+    candidates
+        .take(replica)
+        .context(errors::LastHealthyReplica {
+            replica: replica.to_string(),
+            volume: spec.uuid(),
+        })
+}
+
 /// Get replica candidates to be removed from the volume
 /// This list includes healthy and non_healthy candidates, so care must be taken to
 /// make sure we don't remove "too many healthy" candidates
@@ -166,6 +190,33 @@ pub(crate) async fn get_volume_replica_candidates(
         .collect::<Vec<_>>())
 }
 
+/// Return the request which can be used to create a replica for the volume on the given pool
+/// The pool must be suitable for the volume's replicas, eg: it can't be on a node which is
+/// already used by the volume
+pub(crate) async fn get_volume_pool_replica_candidate(
+    registry: &Registry,
+    spec: &VolumeSpec,
+    pool: &PoolId,
+) -> Result<Vec<CreateReplica>, SvcError> {
+    registry.get_pool(pool).await?;
+
+    let candidates = get_volume_replica_candidates(registry, spec)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|candidate| &candidate.pool == pool)
+        .collect::<Vec<_>>();
+
+    if candidates.is_empty() {
+        Err(SvcError::PoolNotSuitable {
+            pool_id: pool.clone(),
+            vol_id: spec.uuid(),
+        })
+    } else {
+        Ok(candidates)
+    }
+}
+
 /// Return a list of appropriate requests which can be used to create a a replica on a pool
 /// This can be used when creating a volume
 async fn get_create_volume_replicas(
@@ -849,7 +900,7 @@ impl ResourceSpecsLocked {
     }
 
     /// Increase the replica count of the given volume by 1
-    /// Creates a new data replica from a list of candidates
+    /// Creates a new data replica from a list of candidates, or on the given pool
     /// Adds the replica to the volume nexuses (if any)
     async fn increase_volume_replica(
         &self,
@@ -857,10 +908,14 @@ impl ResourceSpecsLocked {
         spec: Arc<Mutex<VolumeSpec>>,
         state: VolumeState,
         spec_clone: VolumeSpec,
+        pool: Option<&PoolId>,
         mode: OperationMode,
     ) -> Result<Volume, SvcError> {
         // Prepare a list of candidates (based on some criteria)
-        let result = get_volume_replica_candidates(registry, &spec_clone).await;
+        let result = match pool {
+            None => get_volume_replica_candidates(registry, &spec_clone).await,
+            Some(pool) => get_volume_pool_replica_candidate(registry, &spec_clone, pool).await,
+        };
         let candidates =
             SpecOperations::validate_update_step(registry, result, &spec, &spec_clone).await?;
 
@@ -918,17 +973,23 @@ impl ResourceSpecsLocked {
     }
 
     /// Decrement the replica count of the given volume by 1
-    /// Removes the replica from all volume nexuses
+    /// Removes the most suitable replica, or the given replica, from all volume nexuses
     async fn decrease_volume_replica(
         &self,
         registry: &Registry,
         spec: Arc<Mutex<VolumeSpec>>,
         state: VolumeState,
         spec_clone: VolumeSpec,
+        replica: Option<&ReplicaId>,
         mode: OperationMode,
     ) -> Result<Volume, SvcError> {
         // Determine which replica is most suitable to be removed
-        let result = get_volume_replica_remove_candidate(&spec_clone, &state, registry).await;
+        let result = match replica {
+            None => get_volume_replica_remove_candidate(&spec_clone, &state, registry).await,
+            Some(replica) => {
+                get_volume_replica_remove_target(&spec_clone, &state, replica, registry).await
+            }
+        };
 
         if let Err(ReplicaRemovalNoCandidates { .. }) = result {
             // The desired number of replicas is already met. This can occur if a replica has been
@@ -983,16 +1044,80 @@ impl ResourceSpecsLocked {
 
         assert_ne!(request.replicas, spec_clone.num_replicas);
         if request.replicas > spec_clone.num_replicas {
-            self.increase_volume_replica(registry, spec, state, spec_clone.clone(), mode)
+            self.increase_volume_replica(registry, spec, state, spec_clone.clone(), None, mode)
                 .await?
         } else {
-            self.decrease_volume_replica(registry, spec, state, spec_clone.clone(), mode)
+            self.decrease_volume_replica(registry, spec, state, spec_clone.clone(), None, mode)
                 .await?
         };
 
         registry.get_volume(&request.uuid).await
     }
 
+    /// Adds a replica to the volume on the pool of the given `CreateVolumeReplica` request,
+    /// increasing the volume's replica count by 1
+    pub(crate) async fn create_volume_pool_replica(
+        &self,
+        registry: &Registry,
+        request: &CreateVolumeReplica,
+        mode: OperationMode,
+    ) -> Result<Volume, SvcError> {
+        let spec = self
+            .get_locked_volume(&request.uuid)
+            .context(errors::VolumeNotFound {
+                vol_id: request.uuid.to_string(),
+            })?;
+        let state = registry.get_volume_state(&request.uuid).await?;
+
+        let replicas = spec.lock().num_replicas.saturating_add(1);
+        let operation = VolumeOperation::SetReplica(replicas);
+        let (spec_clone, _guard) =
+            SpecOperations::start_update(registry, &spec, &state, operation, mode).await?;
+
+        self.increase_volume_replica(registry, spec, state, spec_clone, Some(&request.pool), mode)
+            .await
+    }
+
+    /// Removes the replica of the given `RemoveVolumeReplica` request from the volume,
+    /// decreasing the volume's replica count by 1
+    pub(crate) async fn remove_volume_replica(
+        &self,
+        registry: &Registry,
+        request: &RemoveVolumeReplica,
+        mode: OperationMode,
+    ) -> Result<Volume, SvcError> {
+        let spec = self
+            .get_locked_volume(&request.uuid)
+            .context(errors::VolumeNotFound {
+                vol_id: request.uuid.to_string(),
+            })?;
+        if !self
+            .get_volume_replicas(&request.uuid)
+            .iter()
+            .any(|replica| replica.lock().uuid == request.replica)
+        {
+            return Err(SvcError::ReplicaNotFound {
+                replica_id: request.replica.clone(),
+            });
+        }
+        let state = registry.get_volume_state(&request.uuid).await?;
+
+        let replicas = spec.lock().num_replicas.saturating_sub(1);
+        let operation = VolumeOperation::SetReplica(replicas);
+        let (spec_clone, _guard) =
+            SpecOperations::start_update(registry, &spec, &state, operation, mode).await?;
+
+        self.decrease_volume_replica(
+            registry,
+            spec,
+            state,
+            spec_clone,
+            Some(&request.replica),
+            mode,
+        )
+        .await
+    }
+
     /// Make the replica accessible on the specified `NodeId`
     /// This means the replica might have to be shared/unshared so it can be open through
     /// the correct protocol (loopback locally, and nvmf remotely)
@@ -1474,19 +1599,26 @@ async fn get_volume_target_node(
 
     match request.target_node.as_ref() {
         None => {
-            // auto select a node
-            let nodes = registry.get_node_wrappers().await;
-            for locked_node in nodes {
+            // auto select an online node which is not cordoned, preferring the nodes which are
+            // local to the replicas of the volume and then the nodes with the fewest nexuses
+            let data_nodes = registry.specs().get_volume_data_nodes(&request.uuid);
+            let mut candidates = vec![];
+            for locked_node in registry.get_node_wrappers().await {
                 let node = locked_node.read().await;
-                // todo: use other metrics in order to make the "best" choice
-                if node.is_online() {
-                    return Ok(node.id().clone());
+                if node.is_online() && !registry.specs().node_cordoned(node.id()) {
+                    let remote = !data_nodes.contains(node.id());
+                    candidates.push(((remote, node.nexus_states().len()), node.id().clone()));
                 }
             }
-            Err(SvcError::NoNodes {})
+            candidates
+                .into_iter()
+                .min_by_key(|(key, _)| *key)
+                .map(|(_, node)| node)
+                .ok_or(SvcError::NoNodes {})
         }
         Some(node) => {
-            // make sure the requested node is available
+            // make sure the requested node is available, a cordoned node is only excluded from
+            // the automatic selection
             // todo: check the max number of nexuses per node is respected
             let node = registry.get_node_wrapper(node).await?;
             let node = node.read().await;
@@ -1529,25 +1661,18 @@ impl SpecOperations for VolumeSpec {
         }
 
         match &operation {
-            VolumeOperation::Share(protocol) => match protocol {
-                VolumeShareProtocol::Nvmf => match &self.target {
-                    None => Err(SvcError::VolumeNotPublished {
-                        vol_id: self.uuid(),
+            VolumeOperation::Share(_) => match &self.target {
+                None => Err(SvcError::VolumeNotPublished {
+                    vol_id: self.uuid(),
+                }),
+                Some(target) => match target.protocol() {
+                    None => Ok(()),
+                    Some(protocol) => Err(SvcError::AlreadyShared {
+                        kind: self.kind(),
+                        id: self.uuid(),
+                        share: protocol.to_string(),
                     }),
-                    Some(target) => match target.protocol() {
-                        None => Ok(()),
-                        Some(protocol) => Err(SvcError::AlreadyShared {
-                            kind: self.kind(),
-                            id: self.uuid(),
-                            share: protocol.to_string(),
-                        }),
-                    },
                 },
-                VolumeShareProtocol::Iscsi => Err(SvcError::InvalidShareProtocol {
-                    kind: ResourceKind::Volume,
-                    id: self.uuid(),
-                    share: format!("{:?}", protocol),
-                }),
             },
             VolumeOperation::Unshare => match &self.target {
                 None => Err(SvcError::NotShared {
@@ -1562,24 +1687,17 @@ impl SpecOperations for VolumeSpec {
             },
             VolumeOperation::Publish((_, _, protocol)) => match protocol {
                 None => Ok(()),
-                Some(protocol) => match protocol {
-                    VolumeShareProtocol::Nvmf => {
-                        if let Some(target) = &self.target {
-                            Err(SvcError::VolumeAlreadyPublished {
-                                vol_id: self.uuid(),
-                                node: target.node().to_string(),
-                                protocol: format!("{:?}", target.protocol()),
-                            })
-                        } else {
-                            Ok(())
-                        }
+                Some(_) => {
+                    if let Some(target) = &self.target {
+                        Err(SvcError::VolumeAlreadyPublished {
+                            vol_id: self.uuid(),
+                            node: target.node().to_string(),
+                            protocol: format!("{:?}", target.protocol()),
+                        })
+                    } else {
+                        Ok(())
                     }
-                    VolumeShareProtocol::Iscsi => Err(SvcError::InvalidShareProtocol {
-                        kind: ResourceKind::Volume,
-                        id: self.uuid(),
-                        share: format!("{:?}", protocol),
-                    }),
-                },
+                }
             },
             VolumeOperation::Unpublish if self.target.is_none() => {
                 Err(SvcError::VolumeNotPublished {
@@ -5,10 +5,11 @@ use common_lib::{
     store::etcd::Etcd,
     types::v0::{
         message_bus::{
-            Child, ChildState, CreateReplica, CreateVolume, DestroyVolume, Filter, GetNexuses,
-            GetReplicas, GetVolumes, Nexus, NodeId, PublishVolume, SetVolumeReplica, ShareVolume,
-            Topology, UnpublishVolume, UnshareVolume, Volume, VolumeShareProtocol, VolumeState,
-            VolumeStatus,
+            Child, ChildState, CreateReplica, CreateVolume, CreateVolumeReplica, DestroyVolume,
+            Filter, GetNexuses, GetReplicas, GetVolumeCapacity, GetVolumes, Nexus, NodeId,
+            PublishVolume, RemoveVolumeReplica, SetVolumeReplica, ShareVolume, Topology,
+            UnpublishVolume, UnshareVolume, Volume, VolumeCapacity, VolumeShareProtocol,
+            VolumeState, VolumeStatus,
         },
         openapi::apis::{StatusCode, Uuid},
         store::{
@@ -68,7 +69,11 @@ async fn volume() {
 async fn test_volume(cluster: &Cluster) {
     smoke_test(cluster).await;
     publishing_test(cluster).await;
+    iscsi_publish_test(cluster).await;
+    target_node_selection_test(cluster).await;
+    volume_capacity_test(cluster).await;
     replica_count_test(cluster).await;
+    volume_replica_test(cluster).await;
     nexus_persistence_test(cluster).await;
 }
 
@@ -1508,7 +1513,7 @@ async fn publishing_test(cluster: &Cluster) {
             None,
         )
         .await
-        .expect_err("The volume publish should fail with Invalid protocol error");
+        .expect_err("The volume is already published");
 
     volume_client
         .publish(
@@ -1866,6 +1871,130 @@ async fn replica_count_test(cluster: &Cluster) {
         .is_empty());
 }
 
+async fn volume_replica_test(cluster: &Cluster) {
+    let volume_client = cluster.grpc_client().volume();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::try_from("359b7e1a-b724-443b-98b4-e6d97fabbb40").unwrap(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let volume = volume_client
+        .publish(
+            &PublishVolume {
+                uuid: volume.spec().uuid.clone(),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let volume_state = volume.state();
+    let (replica, topology) = volume_state.replica_topology.iter().next().unwrap();
+    let (replica, used_pool) = (replica.clone(), topology.pool().clone().unwrap());
+    let free_pool = (0 .. 3)
+        .map(|node| cluster.pool(node, 0))
+        .find(|pool| pool != &used_pool)
+        .unwrap();
+
+    let error = volume_client
+        .create_replica(
+            &CreateVolumeReplica::new(volume_state.uuid.clone(), used_pool),
+            None,
+        )
+        .await
+        .expect_err("The node of the pool already has a replica of the volume");
+    tracing::error!("error: {:?}", error);
+    assert!(matches!(
+        error,
+        ReplyError {
+            kind: ReplyErrorKind::FailedPrecondition,
+            resource: ResourceKind::Pool,
+            ..
+        },
+    ));
+
+    let volume = volume_client
+        .create_replica(
+            &CreateVolumeReplica::new(volume_state.uuid.clone(), free_pool.clone()),
+            None,
+        )
+        .await
+        .expect("Should be able to add a replica on the free pool");
+    tracing::info!("Volume: {:?}", volume);
+    assert_eq!(volume.spec().num_replicas, 2);
+
+    let volume_state = wait_for_volume_online(&volume.state(), &volume_client)
+        .await
+        .unwrap();
+
+    let error = volume_client
+        .remove_replica(
+            &RemoveVolumeReplica::new(volume_state.uuid.clone(), ReplicaId::new()),
+            None,
+        )
+        .await
+        .expect_err("The replica does not belong to the volume");
+    assert!(matches!(
+        error,
+        ReplyError {
+            kind: ReplyErrorKind::NotFound,
+            resource: ResourceKind::Replica,
+            ..
+        },
+    ));
+
+    let volume = volume_client
+        .remove_replica(
+            &RemoveVolumeReplica::new(volume_state.uuid.clone(), replica),
+            None,
+        )
+        .await
+        .expect("Should be able to remove the original replica");
+    tracing::info!("Volume: {:?}", volume);
+    assert_eq!(volume.spec().num_replicas, 1);
+
+    let volume_state = volume.state();
+    let (replica, topology) = volume_state.replica_topology.iter().next().unwrap();
+    assert_eq!(volume_state.replica_topology.len(), 1);
+    assert_eq!(topology.pool(), &Some(free_pool));
+
+    let error = volume_client
+        .remove_replica(
+            &RemoveVolumeReplica::new(volume_state.uuid.clone(), replica.clone()),
+            None,
+        )
+        .await
+        .expect_err("Can't remove the last replica");
+    tracing::error!("error: {:?}", error);
+    assert!(matches!(
+        error,
+        ReplyError {
+            kind: ReplyErrorKind::FailedPrecondition,
+            resource: ResourceKind::Volume,
+            ..
+        },
+    ));
+
+    volume_client
+        .destroy(
+            &DestroyVolume {
+                uuid: volume_state.uuid.clone(),
+            },
+            None,
+        )
+        .await
+        .expect("Should be able to destroy the volume");
+}
+
 async fn smoke_test(cluster: &Cluster) {
     let replica_client = cluster.grpc_client().replica();
     let volume_client = cluster.grpc_client().volume();
@@ -1916,3 +2045,117 @@ async fn smoke_test(cluster: &Cluster) {
         .0
         .is_empty());
 }
+
+async fn iscsi_publish_test(cluster: &Cluster) {
+    let volume_client = cluster.grpc_client().volume();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: "1b2a6e1c-94d5-4e41-b5e2-7a9e6c3f0d84".try_into().unwrap(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let uuid = volume.spec().uuid;
+
+    let volume = volume_client
+        .publish(
+            &PublishVolume {
+                uuid: uuid.clone(),
+                target_node: Some(cluster.node(0)),
+                share: Some(VolumeShareProtocol::Iscsi),
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        volume.state().target_protocol(),
+        Some(VolumeShareProtocol::Iscsi)
+    );
+
+    volume_client
+        .destroy(&DestroyVolume { uuid }, None)
+        .await
+        .expect("Should be able to destroy the volume");
+}
+
+async fn target_node_selection_test(cluster: &Cluster) {
+    let volumes_api = cluster.rest_v00().volumes_api();
+
+    let volume = volumes_api
+        .put_volume(
+            &"2a4d6e8f-1b3c-4d5e-8f7a-9b0c1d2e3f40".parse().unwrap(),
+            models::CreateVolumeBody::new(models::VolumePolicy::default(), 1, 5242880u64),
+        )
+        .await
+        .unwrap();
+    let replica_node = volume
+        .state
+        .replica_topology
+        .values()
+        .find_map(|r| r.node.clone())
+        .unwrap();
+
+    // the target node is selected by the control plane, preferably local to the replica
+    let volume = volumes_api
+        .put_volume_target_auto(&volume.spec.uuid, models::VolumeShareProtocol::Nvmf)
+        .await
+        .expect("Should be able to publish without a target node");
+    tracing::info!("Volume: {:?}", volume);
+    assert_eq!(volume.state.target.unwrap().node, replica_node);
+
+    volumes_api.del_volume(&volume.spec.uuid).await.unwrap();
+}
+
+async fn volume_capacity_test(cluster: &Cluster) {
+    let volume_client = cluster.grpc_client().volume();
+    let pools = cluster.rest_v00().pools_api().get_pools().await.unwrap();
+    // each node has a single pool
+    let mut free_space = pools
+        .into_iter()
+        .filter_map(|pool| pool.state)
+        .map(|state| state.capacity - state.used)
+        .collect::<Vec<_>>();
+    free_space.sort_unstable();
+    assert_eq!(free_space.len(), 3);
+
+    let capacity = volume_client
+        .get_capacity(&GetVolumeCapacity::new(1, None), None)
+        .await
+        .unwrap();
+    tracing::info!("Capacity: {:?}", capacity);
+    assert_eq!(capacity.maximum_volume_size, free_space[2]);
+    assert_eq!(capacity.available_capacity, free_space.iter().sum::<u64>());
+
+    // the replicas must be placed on different nodes
+    let capacity = volume_client
+        .get_capacity(&GetVolumeCapacity::new(3, None), None)
+        .await
+        .unwrap();
+    assert_eq!(capacity.maximum_volume_size, free_space[0]);
+    assert_eq!(capacity.available_capacity, free_space[0]);
+
+    let capacity = volume_client
+        .get_capacity(&GetVolumeCapacity::new(4, None), None)
+        .await
+        .unwrap();
+    assert_eq!(capacity, VolumeCapacity::default());
+
+    // only the pools of the allowed nodes may be used
+    let topology = Topology::from(models::Topology::new_all(
+        Some(models::NodeTopology::explicit(
+            models::ExplicitNodeTopology::new(vec![cluster.node(0).to_string()], vec![]),
+        )),
+        None,
+    ));
+    let capacity = volume_client
+        .get_capacity(&GetVolumeCapacity::new(2, Some(topology)), None)
+        .await
+        .unwrap();
+    assert_eq!(capacity, VolumeCapacity::default());
+}
@@ -1,10 +1,11 @@
-use crate::CsiControllerConfig;
+use crate::{controller::volume_opts, CsiControllerConfig};
 use common_lib::types::v0::openapi::{
     clients,
     clients::tower::StatusCode,
     models::{
-        CreateVolumeBody, ExplicitNodeTopology, LabelledTopology, Node, NodeTopology, Pool,
-        PoolTopology, RestJsonError, Topology, Volume, VolumePolicy, VolumeShareProtocol, Volumes,
+        CreateVolumeBody, ExplicitNodeTopology, LabelledTopology, Node, NodeTopology, PoolTopology,
+        RestJsonError, Topology, Volume, VolumeCapacity, VolumePolicy, VolumeShareProtocol,
+        Volumes,
     },
 };
 
@@ -38,6 +39,7 @@ pub struct CreateVolumeTopology {
     inclusive_label_topology: HashMap<String, String>,
     allowed_nodes: Vec<String>,
     preferred_nodes: Vec<String>,
+    node_segments: HashMap<String, String>,
 }
 
 impl CreateVolumeTopology {
@@ -45,11 +47,13 @@ impl CreateVolumeTopology {
         allowed_nodes: Vec<String>,
         preferred_nodes: Vec<String>,
         inclusive_label_topology: HashMap<String, String>,
+        node_segments: HashMap<String, String>,
     ) -> Self {
         Self {
             allowed_nodes,
             preferred_nodes,
             inclusive_label_topology,
+            node_segments,
         }
     }
 }
@@ -141,12 +145,6 @@ impl IoEngineApiClient {
         Ok(response.into_body())
     }
 
-    /// List all pools available in IoEngine cluster.
-    pub async fn list_pools(&self) -> Result<Vec<Pool>, ApiClientError> {
-        let response = self.rest_client.pools_api().get_pools().await?;
-        Ok(response.into_body())
-    }
-
     /// List all volumes available in IoEngine cluster.
     pub async fn list_volumes(
         &self,
@@ -172,10 +170,32 @@ impl IoEngineApiClient {
         Ok(response.into_body())
     }
 
-    /// List pools available on target IoEngine node.
-    pub async fn get_node_pools(&self, node: &str) -> Result<Vec<Pool>, ApiClientError> {
-        let pools = self.rest_client.pools_api().get_node_pools(node).await?;
-        Ok(pools.into_body())
+    /// Get the capacity available for new volumes with the given number of replicas.
+    /// The replicas are placed on the allowed nodes, if any, or else on any node, and on the
+    /// pools with the given labels.
+    pub async fn get_volume_capacity(
+        &self,
+        replicas: u8,
+        allowed_nodes: Option<&[String]>,
+        pool_labels: &HashMap<String, String>,
+    ) -> Result<VolumeCapacity, ApiClientError> {
+        let allowed_nodes = allowed_nodes.map(|nodes| nodes.join(","));
+        let pool_labels = pool_labels
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let capacity = self
+            .rest_client
+            .volumes_api()
+            .get_volume_capacity(
+                replicas,
+                allowed_nodes.as_deref(),
+                Some(pool_labels.as_str()).filter(|labels| !labels.is_empty()),
+            )
+            .await?;
+        Ok(capacity.into_body())
     }
 
     /// Create a volume of target size and provision storage resources for it.
@@ -188,7 +208,7 @@ impl IoEngineApiClient {
         replicas: u8,
         size: u64,
         volume_topology: CreateVolumeTopology,
-        _pinned_volume: bool,
+        pinned_volume: bool,
     ) -> Result<Volume, ApiClientError> {
         let topology = Topology::new_all(
             Some(NodeTopology::explicit(ExplicitNodeTopology::new(
@@ -201,12 +221,35 @@ impl IoEngineApiClient {
             ))),
         );
 
+        // The topology segments and whether the volume is pinned are recorded so that the
+        // accessible topology of the volume can be reported.
+        let mut labels = volume_topology
+            .node_segments
+            .into_iter()
+            .map(|(key, value)| {
+                (
+                    format!("{}{}", volume_opts::TOPOLOGY_SEGMENT_PREFIX, key),
+                    value,
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        if !pinned_volume {
+            labels.insert(
+                volume_opts::LOCAL_VOLUME.to_string(),
+                pinned_volume.to_string(),
+            );
+        }
+
         let req = CreateVolumeBody {
             replicas,
             size,
             topology: Some(topology),
             policy: VolumePolicy::new_all(true),
-            labels: None,
+            labels: if labels.is_empty() {
+                None
+            } else {
+                Some(labels)
+            },
         };
 
         let result = self
@@ -282,18 +325,27 @@ impl IoEngineApiClient {
     }
 
     /// Publish volume (i.e. make it accessible via specified protocol by creating a target).
+    /// The target is created on the given node, or else on a node selected by the control plane.
     #[instrument(fields(volume.uuid = %volume_id), skip(volume_id))]
     pub async fn publish_volume(
         &self,
         volume_id: &uuid::Uuid,
-        node: &str,
+        node: Option<&str>,
         protocol: VolumeShareProtocol,
     ) -> Result<Volume, ApiClientError> {
-        let volume = self
-            .rest_client
-            .volumes_api()
-            .put_volume_target(volume_id, node, protocol)
-            .await?;
+        let volumes_api = self.rest_client.volumes_api();
+        let volume = match node {
+            Some(node) => {
+                volumes_api
+                    .put_volume_target(volume_id, node, protocol)
+                    .await?
+            }
+            None => {
+                volumes_api
+                    .put_volume_target_auto(volume_id, protocol)
+                    .await?
+            }
+        };
         Ok(volume.into_body())
     }
 }
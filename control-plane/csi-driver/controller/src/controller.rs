@@ -1,32 +1,47 @@
 use crate::{ApiClientError, CreateVolumeTopology, CsiControllerConfig, IoEngineApiClient};
 use regex::Regex;
 use rpc::csi::*;
-use std::collections::HashMap;
+use std::{collections::HashMap, env};
 use tonic::{Response, Status};
-use tracing::{debug, error, instrument, warn};
+use tracing::{debug, error, instrument};
 use uuid::Uuid;
 
-use common_lib::types::v0::openapi::models::{
-    Pool, PoolStatus, SpecStatus, Volume, VolumeShareProtocol,
-};
+use common_lib::types::v0::openapi::models::{SpecStatus, Volume, VolumeShareProtocol};
 use utils::{CREATED_BY_KEY, DSP_OPERATOR};
 
+use k8s_openapi::api::core::v1::Node as K8sNode;
+use kube::{api::ListParams, Api, Client, Resource};
+
 use rpc::csi::Topology as CsiTopology;
 
 const K8S_HOSTNAME: &str = "kubernetes.io/hostname";
 const VOLUME_NAME_PATTERN: &str =
     r"pvc-([0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})";
-const SUPPORTED_FS_TYPES: [&str; 2] = ["ext4", "xfs"];
+const SUPPORTED_FS_TYPES: [&str; 3] = ["ext4", "xfs", "btrfs"];
 const CSI_NODE_PREFIX: &str = "csi-node://";
 
 #[derive(Debug, Default)]
 pub struct CsiControllerSvc {}
 
 // TODO: Implement VolumeOpts
-mod volume_opts {
+pub(crate) mod volume_opts {
+    pub use csi_driver::context::{
+        ISCSI_OPTIONS, NVME_CTRL_LOSS_TMO, NVME_NR_IO_QUEUES, NVME_OPTIONS, NVME_RECONNECT_DELAY,
+    };
+
     pub const IO_TIMEOUT: &str = "ioTimeout";
     pub const LOCAL_VOLUME: &str = "local";
-
+    pub const FS_TYPE: &str = "fsType";
+    pub const MKFS_OPTIONS: &str = "mkfsOptions";
+    pub const MOUNT_OPTIONS: &str = "mountOptions";
+    pub const FS_CHECK: &str = "fsCheck";
+    pub const NVME_KEEP_ALIVE_TMO: &str = "nvmeKeepAliveTmo";
+    pub const NVME_QUEUE_SIZE: &str = "nvmeQueueSize";
+    /// Nvme connection parameters which are rejected, as the nvme connect arguments of the node
+    /// plugin can't apply them.
+    pub const NVME_UNSUPPORTED_OPTIONS: [&str; 2] = [NVME_KEEP_ALIVE_TMO, NVME_QUEUE_SIZE];
+    /// Prefix of the volume labels which record the topology segments of the volume.
+    pub const TOPOLOGY_SEGMENT_PREFIX: &str = "topology-segment/";
     const YAML_TRUE_VALUE: [&str; 11] = [
         "y", "Y", "yes", "Yes", "YES", "true", "True", "TRUE", "on", "On", "ON",
     ];
@@ -49,6 +64,167 @@ pub fn valid_fs_type(fs_type: Option<&String>) -> bool {
     }
 }
 
+/// Mount options which may not be used as default mount options, as they're managed by the
+/// node plugin itself.
+const RESERVED_MOUNT_OPTIONS: [&str; 4] = ["ro", "rw", "bind", "remount"];
+
+/// Policies for checking the filesystem of a volume before it's staged by the node plugin.
+const FS_CHECK_POLICIES: [&str; 3] = ["none", "warn", "refuse"];
+
+/// Validate the mkfs and the default mount options of the storage class parameters, which
+/// are returned normalised, so that they can be recorded as such in the volume context.
+/// The mkfs options are whitespace separated and, being specific to a filesystem type, may
+/// only be used with an explicit fsType.
+/// The default mount options are comma separated and may not contain any of the mount
+/// options which the node plugin manages itself.
+/// The filesystem check policy must be one of the known policies.
+fn parse_fs_options(
+    parameters: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, Status> {
+    let mut options = HashMap::new();
+
+    if let Some(mkfs_options) = parameters.get(volume_opts::MKFS_OPTIONS) {
+        let mkfs_options = mkfs_options.split_whitespace().collect::<Vec<_>>();
+        if !mkfs_options.is_empty() {
+            if parameters.get(volume_opts::FS_TYPE).is_none() {
+                return Err(Status::invalid_argument(
+                    "mkfs options require an explicit filesystem type",
+                ));
+            }
+            // the device is appended by the node plugin
+            if let Some(path) = mkfs_options.iter().find(|option| option.starts_with('/')) {
+                return Err(Status::invalid_argument(format!(
+                    "Invalid mkfs option: {}",
+                    path
+                )));
+            }
+        }
+        options.insert(
+            volume_opts::MKFS_OPTIONS.to_string(),
+            mkfs_options.join(" "),
+        );
+    }
+
+    if let Some(mount_options) = parameters
+        .get(volume_opts::MOUNT_OPTIONS)
+        .filter(|options| !options.trim().is_empty())
+    {
+        let mount_options = mount_options
+            .split(',')
+            .map(|option| option.trim())
+            .collect::<Vec<_>>();
+        if mount_options.iter().any(|option| option.is_empty()) {
+            return Err(Status::invalid_argument("Empty mount option"));
+        }
+        if let Some(option) = mount_options.iter().find(|option| {
+            option.contains(char::is_whitespace) || RESERVED_MOUNT_OPTIONS.contains(*option)
+        }) {
+            return Err(Status::invalid_argument(format!(
+                "Invalid mount option: {}",
+                option
+            )));
+        }
+        options.insert(
+            volume_opts::MOUNT_OPTIONS.to_string(),
+            mount_options.join(","),
+        );
+    }
+
+    if let Some(policy) = parameters.get(volume_opts::FS_CHECK) {
+        if !FS_CHECK_POLICIES.contains(&policy.as_str()) {
+            return Err(Status::invalid_argument(format!(
+                "Invalid filesystem check policy: {}",
+                policy
+            )));
+        }
+    }
+
+    Ok(options)
+}
+
+/// Validate the nvme connection parameters, which are only valid for the nvmf protocol.
+fn check_nvme_options(
+    parameters: &HashMap<String, String>,
+    protocol: VolumeShareProtocol,
+) -> Result<(), Status> {
+    if let Some(name) = volume_opts::NVME_UNSUPPORTED_OPTIONS
+        .iter()
+        .find(|name| parameters.contains_key(**name))
+    {
+        return Err(Status::invalid_argument(format!(
+            "{} is not supported by the nvme connection of the node plugin",
+            name
+        )));
+    }
+
+    let mut options = HashMap::new();
+    for name in volume_opts::NVME_OPTIONS {
+        if let Some(value) = parameters.get(name) {
+            if protocol != VolumeShareProtocol::Nvmf {
+                return Err(Status::invalid_argument(format!(
+                    "{} is valid only for nvmf protocol",
+                    name
+                )));
+            }
+            let value = value
+                .parse::<u32>()
+                .map_err(|_| Status::invalid_argument(format!("Invalid {}: {}", name, value)))?;
+            options.insert(name, value);
+        }
+    }
+
+    if options.get(volume_opts::NVME_NR_IO_QUEUES) == Some(&0) {
+        return Err(Status::invalid_argument(
+            "The number of nvme IO queues must be at least 1",
+        ));
+    }
+    if options.get(volume_opts::NVME_RECONNECT_DELAY) == Some(&0) {
+        return Err(Status::invalid_argument(
+            "The nvme reconnect delay must be at least 1s",
+        ));
+    }
+    if let (Some(reconnect_delay), Some(ctrl_loss_tmo)) = (
+        options.get(volume_opts::NVME_RECONNECT_DELAY),
+        options.get(volume_opts::NVME_CTRL_LOSS_TMO),
+    ) {
+        if reconnect_delay > ctrl_loss_tmo {
+            return Err(Status::invalid_argument(
+                "The nvme reconnect delay may not exceed the controller loss timeout",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate the iscsi session parameters, which are only valid for the iscsi protocol.
+fn check_iscsi_options(
+    parameters: &HashMap<String, String>,
+    protocol: VolumeShareProtocol,
+) -> Result<(), Status> {
+    for name in volume_opts::ISCSI_OPTIONS {
+        if let Some(value) = parameters.get(name) {
+            if protocol != VolumeShareProtocol::Iscsi {
+                return Err(Status::invalid_argument(format!(
+                    "{} is valid only for iscsi protocol",
+                    name
+                )));
+            }
+            match value.parse::<u32>() {
+                Ok(value) if value > 0 => {}
+                _ => {
+                    return Err(Status::invalid_argument(format!(
+                        "Invalid {}: {}",
+                        name, value
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Check whether target volume capabilities are valid. As of now, only
 /// SingleNodeWriter capability is supported.
 fn check_volume_capabilities(capabilities: &[VolumeCapability]) -> Result<(), tonic::Status> {
@@ -69,6 +245,7 @@ fn check_volume_capabilities(capabilities: &[VolumeCapability]) -> Result<(), to
 fn parse_protocol(proto: Option<&String>) -> Result<VolumeShareProtocol, Status> {
     match proto.map(|s| s.as_str()) {
         None | Some("nvmf") => Ok(VolumeShareProtocol::Nvmf),
+        Some("iscsi") => Ok(VolumeShareProtocol::Iscsi),
         _ => Err(Status::invalid_argument(format!(
             "Invalid protocol: {:?}",
             proto
@@ -111,12 +288,13 @@ fn check_existing_volume(
     volume: &Volume,
     replica_count: u8,
     size: u64,
-    _pinned_volume: bool,
+    pinned_volume: bool,
 ) -> Result<(), Status> {
     // Check if the existing volume is compatible, which means
     //  - number of replicas is equal or greater
     //  - size is equal or greater
     //  - volume is fully created
+    //  - volume is pinned, or not, as requested
     let spec = &volume.spec;
 
     if spec.status != SpecStatus::Created {
@@ -140,9 +318,169 @@ fn check_existing_volume(
         )));
     }
 
+    if VolumeTopologyMapper::is_volume_pinned(volume) != pinned_volume {
+        return Err(Status::already_exists(format!(
+            "Existing volume {} has incompatible pinning: local = {} ({} requested)",
+            spec.uuid, !pinned_volume, pinned_volume
+        )));
+    }
+
     Ok(())
 }
 
+/// Topology of a volume, as requested by the accessibility requirements of its creation.
+#[derive(Debug, Default)]
+struct RequestedTopology {
+    /// Nodes which the volume may be placed on.
+    allowed_nodes: Vec<String>,
+    /// Nodes which the volume should preferably be placed on.
+    preferred_nodes: Vec<String>,
+    /// Topology segments other than the hostname, eg the zone or the region, which the nodes of
+    /// the volume must have.
+    segments: HashMap<String, String>,
+}
+
+impl RequestedTopology {
+    /// Translate the accessibility requirements into the nodes of the volume and into the
+    /// segments which these nodes must have in common.
+    /// For explanation of accessibilityRequirements refer to a table at
+    /// https://github.com/kubernetes-csi/external-provisioner.
+    /// Our case is WaitForFirstConsumer = true, strict-topology = false.
+    ///
+    /// The first topology in preferred array is the one of the node that was chosen for running
+    /// the app by the k8s scheduler. The rest of the entries are in random order and perhaps
+    /// don't even run the csi node plugin.
+    ///
+    /// The requisite array contains all topologies in the cluster irrespective of what node was
+    /// chosen for running the app.
+    ///
+    /// The segments of the volume are the ones of the chosen node, other than its hostname, or
+    /// failing that the ones which all requisite topologies have in common. Only the nodes with
+    /// these segments are then allowed or preferred.
+    fn new(requirements: Option<&TopologyRequirement>) -> Self {
+        let requirements = match requirements {
+            Some(requirements) => requirements,
+            None => return Self::default(),
+        };
+
+        let segments = match requirements.preferred.first() {
+            Some(preferred) => Self::node_segments(preferred),
+            None => {
+                let mut requisite = requirements.requisite.iter().map(Self::node_segments);
+                let first = requisite.next().unwrap_or_default();
+                requisite.fold(first, |mut common, segments| {
+                    common.retain(|key, value| segments.get(key) == Some(value));
+                    common
+                })
+            }
+        };
+
+        let hostnames = |topologies: &[CsiTopology]| {
+            topologies
+                .iter()
+                .filter(|topology| {
+                    segments
+                        .iter()
+                        .all(|(key, value)| topology.segments.get(key) == Some(value))
+                })
+                .filter_map(|topology| topology.segments.get(K8S_HOSTNAME).cloned())
+                .collect::<Vec<_>>()
+        };
+
+        Self {
+            allowed_nodes: hostnames(&requirements.requisite),
+            preferred_nodes: hostnames(&requirements.preferred),
+            segments,
+        }
+    }
+
+    /// Get the segments of the topology other than the hostname.
+    fn node_segments(topology: &CsiTopology) -> HashMap<String, String> {
+        topology
+            .segments
+            .iter()
+            .filter(|(key, _)| key.as_str() != K8S_HOSTNAME)
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+}
+
+/// Get the replica count from the storage class parameters, which defaults to a single replica.
+fn parse_replica_count(parameters: &HashMap<String, String>) -> Result<u8, Status> {
+    match parameters.get("repl") {
+        Some(c) => match c.parse::<u8>() {
+            Ok(c) => {
+                if c == 0 {
+                    return Err(Status::invalid_argument(
+                        "Replica count must be greater than zero",
+                    ));
+                }
+                Ok(c)
+            }
+            Err(_) => Err(Status::invalid_argument("Invalid replica count")),
+        },
+        None => Ok(1),
+    }
+}
+
+/// Check whether no node may hold the replicas of volumes with the given topology segments, ie: no
+/// node has the segments or the segments can't be resolved to nodes.
+/// An empty list of allowed nodes must not be passed on, as it means any node.
+fn no_capacity_nodes(
+    segments: &HashMap<String, String>,
+    allowed_nodes: &Option<Vec<String>>,
+) -> bool {
+    match allowed_nodes {
+        Some(nodes) => nodes.is_empty(),
+        None => !segments.is_empty(),
+    }
+}
+
+/// Get the hostnames of the Kubernetes nodes which have all the given topology segments.
+/// Returns None if there are no segments, or if no Kubernetes API server is available to resolve
+/// them.
+async fn segment_nodes(segments: &HashMap<String, String>) -> Result<Option<Vec<String>>, Status> {
+    if segments.is_empty() || env::var("KUBERNETES_SERVICE_HOST").is_err() {
+        return Ok(None);
+    }
+
+    let k8s = Client::try_default().await.map_err(|e| {
+        Status::internal(format!(
+            "Failed to initialize k8s API client, error = {}",
+            e
+        ))
+    })?;
+    let nodes: Api<K8sNode> = Api::all(k8s);
+    let selector = segments
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join(",");
+    let nodes = nodes
+        .list(&ListParams::default().labels(&selector))
+        .await
+        .map_err(|e| {
+            Status::internal(format!(
+                "Failed to list k8s nodes with labels '{}', error = {}",
+                selector, e
+            ))
+        })?;
+
+    Ok(Some(
+        nodes
+            .items
+            .iter()
+            .filter_map(|node| {
+                node.meta()
+                    .labels
+                    .as_ref()
+                    .and_then(|labels| labels.get(K8S_HOSTNAME))
+                    .cloned()
+            })
+            .collect(),
+    ))
+}
+
 struct VolumeTopologyMapper {}
 
 impl VolumeTopologyMapper {
@@ -155,16 +493,39 @@ impl VolumeTopologyMapper {
     /// must be placed on the same node, which in fact means running workloads only on IO Engine
     /// daemonset nodes.
     /// For non-pinned volumes, workload can be put on any node in the cluster.
-    pub fn volume_accessible_topology(&self, pinned_volume: bool) -> Vec<CsiTopology> {
+    /// In both cases the workload is also restricted to the nodes with the topology segments of
+    /// the volume, eg its zone.
+    pub fn volume_accessible_topology(
+        &self,
+        pinned_volume: bool,
+        segments: &HashMap<String, String>,
+    ) -> Vec<CsiTopology> {
+        let mut segments = segments.clone();
         if pinned_volume {
-            vec![rpc::csi::Topology {
-                segments: CsiControllerConfig::get_config().io_engine_selector(),
-            }]
-        } else {
+            segments.extend(CsiControllerConfig::get_config().io_engine_selector());
+        }
+        if segments.is_empty() {
             Vec::new()
+        } else {
+            vec![rpc::csi::Topology { segments }]
         }
     }
 
+    /// Get the topology segments which the volume was created with, which are recorded as its
+    /// labels with the topology segment prefix.
+    pub fn volume_segments(volume: &Volume) -> HashMap<String, String> {
+        volume
+            .spec
+            .labels
+            .iter()
+            .flatten()
+            .filter_map(|(key, value)| {
+                key.strip_prefix(volume_opts::TOPOLOGY_SEGMENT_PREFIX)
+                    .map(|key| (key.to_string(), value.clone()))
+            })
+            .collect()
+    }
+
     /// Determines whether target volume is pinned.
     pub fn is_volume_pinned(volume: &Volume) -> bool {
         if let Some(labels) = &volume.spec.labels {
@@ -225,83 +586,49 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         };
 
         // Check filesystem type.
-        if !valid_fs_type(args.parameters.get("fsType")) {
+        if !valid_fs_type(args.parameters.get(volume_opts::FS_TYPE)) {
             return Err(Status::invalid_argument("Invalid filesystem type"));
         }
 
+        // Check filesystem options.
+        let fs_options = parse_fs_options(&args.parameters)?;
+
         // Check storage protocol.
         let protocol = parse_protocol(args.parameters.get("protocol"))?;
 
         // Check I/O timeout.
         if let Some(io_timeout) = args.parameters.get(volume_opts::IO_TIMEOUT) {
-            if protocol != VolumeShareProtocol::Nvmf {
-                return Err(Status::invalid_argument(
-                    "I/O timeout is valid only for nvmf protocol",
-                ));
-            }
             if io_timeout.parse::<u64>().is_err() {
                 return Err(Status::invalid_argument("Invalid I/O timeout"));
             }
         }
 
-        let replica_count: u8 = match args.parameters.get("repl") {
-            Some(c) => match c.parse::<u8>() {
-                Ok(c) => {
-                    if c == 0 {
-                        return Err(Status::invalid_argument(
-                            "Replica count must be greater than zero",
-                        ));
-                    }
-                    c
-                }
-                Err(_) => return Err(Status::invalid_argument("Invalid replica count")),
-            },
-            None => 1,
-        };
+        // Check nvme connection parameters.
+        check_nvme_options(&args.parameters, protocol)?;
 
-        // Currently we only support pinned volumes
-        let pinned_volume = true;
-
-        // For explanation of accessibilityRequirements refer to a table at
-        // https://github.com/kubernetes-csi/external-provisioner.
-        // Our case is WaitForFirstConsumer = true, strict-topology = false.
-        //
-        // The first node in preferred array the node that was chosen for running
-        // the app by the k8s scheduler. The rest of the entries are in random
-        // order and perhaps don't even run the csi node plugin.
-        //
-        // The requisite array contains all nodes in the cluster irrespective
-        // of what node was chosen for running the app.
-        let mut allowed_nodes: Vec<String> = Vec::new();
-        let mut preferred_nodes: Vec<String> = Vec::new();
-        let mut inclusive_label_topology: HashMap<String, String> = HashMap::new();
+        // Check iscsi session parameters.
+        check_iscsi_options(&args.parameters, protocol)?;
 
-        inclusive_label_topology.insert(String::from(CREATED_BY_KEY), String::from(DSP_OPERATOR));
+        let replica_count = parse_replica_count(&args.parameters)?;
 
-        if let Some(reqs) = args.accessibility_requirements {
-            for r in reqs.requisite.iter() {
-                for (k, v) in r.segments.iter() {
-                    // We are not able to evaluate any other topology requirements than
-                    // the hostname req. Reject all others.
-                    if k != K8S_HOSTNAME {
-                        return Err(Status::invalid_argument(
-                            "Volume topology other than hostname not supported",
-                        ));
-                    }
-                    allowed_nodes.push(v.to_string());
-                }
-            }
+        // Pinned volumes have their target on the node of the workload, whereas the target of
+        // unpinned volumes is placed on any node by the control plane.
+        let pinned_volume =
+            volume_opts::decode_local_volume_flag(args.parameters.get(volume_opts::LOCAL_VOLUME));
 
-            for p in reqs.preferred.iter() {
-                for (k, v) in p.segments.iter() {
-                    // Ignore others than hostname (it's only preferred)
-                    if k == K8S_HOSTNAME {
-                        preferred_nodes.push(v.to_string());
-                    }
-                }
-            }
+        let mut requested_topology =
+            RequestedTopology::new(args.accessibility_requirements.as_ref());
+        debug!("Requested volume topology: {:?}", requested_topology);
+
+        // The replicas of unpinned volumes need not be placed on the nodes of the workload.
+        if !pinned_volume {
+            requested_topology.allowed_nodes.clear();
         }
 
+        let mut inclusive_label_topology: HashMap<String, String> = HashMap::new();
+
+        inclusive_label_topology.insert(String::from(CREATED_BY_KEY), String::from(DSP_OPERATOR));
+
         let u = Uuid::parse_str(&volume_uuid).map_err(|_e| {
             Status::invalid_argument(format!("Malformed volume UUID: {}", volume_uuid))
         })?;
@@ -319,10 +646,34 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
             }
             // If the volume doesn't exist, create it.
             Err(ApiClientError::ResourceNotExists(_)) => {
+                // When no node is explicitly allowed, the volume may still only be placed on the
+                // nodes with its topology segments, eg in its zone.
+                if requested_topology.allowed_nodes.is_empty()
+                    && !requested_topology.segments.is_empty()
+                {
+                    requested_topology.allowed_nodes =
+                        match segment_nodes(&requested_topology.segments).await? {
+                            Some(nodes) if !nodes.is_empty() => nodes,
+                            Some(_) => {
+                                return Err(Status::resource_exhausted(format!(
+                                    "No node has the topology segments {:?}",
+                                    requested_topology.segments
+                                )))
+                            }
+                            None => {
+                                return Err(Status::invalid_argument(format!(
+                                    "Can't resolve the topology segments {:?} without k8s",
+                                    requested_topology.segments
+                                )))
+                            }
+                        };
+                }
+
                 let volume_topology = CreateVolumeTopology::new(
-                    allowed_nodes,
-                    preferred_nodes,
+                    requested_topology.allowed_nodes,
+                    requested_topology.preferred_nodes,
                     inclusive_label_topology,
+                    requested_topology.segments.clone(),
                 );
 
                 IoEngineApiClient::get_client()
@@ -337,12 +688,17 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
             Err(e) => return Err(e.into()),
         }
 
+        // Record the normalised filesystem options, so that restaging is consistent.
+        let mut volume_context = args.parameters.clone();
+        volume_context.extend(fs_options);
+
         let volume = rpc::csi::Volume {
             capacity_bytes: size as i64,
             volume_id: volume_uuid,
-            volume_context: args.parameters.clone(),
+            volume_context,
             content_source: None,
-            accessible_topology: vt_mapper.volume_accessible_topology(pinned_volume),
+            accessible_topology: vt_mapper
+                .volume_accessible_topology(pinned_volume, &requested_topology.segments),
         };
 
         debug!("Created volume: {:?}", volume);
@@ -417,6 +773,10 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
             .get_volume(&volume_id)
             .await?;
 
+        // The target of a pinned volume must be on the node of the workload, whereas the target of
+        // an unpinned volume is placed by the control plane, and is reachable from any node.
+        let pinned_volume = VolumeTopologyMapper::is_volume_pinned(&volume);
+
         let uri =
             // Volume is already published, make sure the protocol matches and get URI.
             match &volume.spec.target {
@@ -431,8 +791,8 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
                     }
 
                     if let Some((node, uri)) = get_volume_share_location(&volume) {
-                        // Make sure volume is published at the same node.
-                        if node_id != node {
+                        // Make sure a pinned volume is published at the same node.
+                        if pinned_volume && node_id != node {
                             let m = format!(
                                 "Volume {} already published on a different node: {}",
                                 volume_id, node,
@@ -455,7 +815,7 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
             _ => {
                 // Volume is not published.
                 let v = IoEngineApiClient::get_client()
-                    .publish_volume(&volume_id, &node_id, protocol)
+                    .publish_volume(&volume_id, pinned_volume.then(|| node_id.as_str()), protocol)
                     .await?;
 
                 if let Some((node, uri)) = get_volume_share_location(&v) {
@@ -482,6 +842,14 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         if let Some(io_timeout) = args.volume_context.get(volume_opts::IO_TIMEOUT) {
             publish_context.insert(volume_opts::IO_TIMEOUT.to_string(), io_timeout.to_string());
         }
+        for name in volume_opts::NVME_OPTIONS
+            .iter()
+            .chain(volume_opts::ISCSI_OPTIONS.iter())
+        {
+            if let Some(value) = args.volume_context.get(*name) {
+                publish_context.insert(name.to_string(), value.to_string());
+            }
+        }
 
         debug!(
             "Publish context for volume {}: {:?}",
@@ -628,8 +996,10 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
                     capacity_bytes: v.spec.size as i64,
                     volume_context: HashMap::new(),
                     content_source: None,
-                    accessible_topology: vt_mapper
-                        .volume_accessible_topology(VolumeTopologyMapper::is_volume_pinned(&v)),
+                    accessible_topology: vt_mapper.volume_accessible_topology(
+                        VolumeTopologyMapper::is_volume_pinned(&v),
+                        &VolumeTopologyMapper::volume_segments(&v),
+                    ),
                 };
 
                 list_volumes_response::Entry {
@@ -658,51 +1028,49 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         // Check capabilities.
         check_volume_capabilities(&args.volume_capabilities)?;
 
-        // Determine target node, if requested.
-        let node: Option<&String> = if let Some(topology) = args.accessible_topology.as_ref() {
-            topology.segments.get(K8S_HOSTNAME)
-        } else {
-            None
-        };
+        let replica_count = parse_replica_count(&args.parameters)?;
+
+        // The replicas of a volume need not be placed on the node of its workload, hence the
+        // hostname is of no consequence, unlike the other segments, eg the zone, of the topology.
+        let segments = args
+            .accessible_topology
+            .as_ref()
+            .map(RequestedTopology::node_segments)
+            .unwrap_or_default();
+        let allowed_nodes = segment_nodes(&segments).await?;
+        if no_capacity_nodes(&segments, &allowed_nodes) {
+            debug!(
+                "No node has the topology segments {:?}, hence no capacity",
+                segments
+            );
+            return Ok(Response::new(GetCapacityResponse {
+                available_capacity: 0,
+                maximum_volume_size: Some(0),
+                minimum_volume_size: None,
+            }));
+        }
 
-        let pools: Vec<Pool> = if let Some(node) = node {
-            debug!("Calculating pool capacity for node {}", node);
-            IoEngineApiClient::get_client()
-                .get_node_pools(node)
-                .await
-                .map_err(|e| {
-                    Status::internal(format!(
-                        "Failed to list pools for node {}, error = {:?}",
-                        node, e,
-                    ))
-                })?
-        } else {
-            debug!("Calculating overall pool capacity");
-            IoEngineApiClient::get_client()
-                .list_pools()
-                .await
-                .map_err(|e| {
-                    Status::internal(format!("Failed to list all pools, error = {:?}", e,))
-                })?
-        };
+        // Volumes are only created on the pools which are managed by the operator.
+        let mut pool_labels: HashMap<String, String> = HashMap::new();
+        pool_labels.insert(String::from(CREATED_BY_KEY), String::from(DSP_OPERATOR));
 
-        let available_capacity: i64 = pools.into_iter().fold(0, |acc, p| match p.state {
-            Some(state) => match state.status {
-                PoolStatus::Online | PoolStatus::Degraded => acc + state.capacity as i64,
-                _ => {
-                    warn!(
-                        "Pool {} on node {} is in '{:?}' state, not accounting it for capacity",
-                        p.id, state.node, state.status,
-                    );
-                    acc
-                }
-            },
-            None => 0,
-        });
+        debug!(
+            "Calculating the capacity for {} replica(s) with topology segments {:?}",
+            replica_count, segments
+        );
+        let capacity = IoEngineApiClient::get_client()
+            .get_volume_capacity(replica_count, allowed_nodes.as_deref(), &pool_labels)
+            .await
+            .map_err(|e| {
+                Status::internal(format!(
+                    "Failed to get the volume capacity, error = {:?}",
+                    e
+                ))
+            })?;
 
         Ok(Response::new(GetCapacityResponse {
-            available_capacity,
-            maximum_volume_size: None,
+            available_capacity: capacity.available_capacity as i64,
+            maximum_volume_size: Some(capacity.maximum_volume_size as i64),
             minimum_volume_size: None,
         }))
     }
@@ -771,3 +1139,29 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         Err(Status::unimplemented("Not implemented"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(zone: &str) -> HashMap<String, String> {
+        let mut segments = HashMap::new();
+        segments.insert("topology.kubernetes.io/zone".to_string(), zone.to_string());
+        segments
+    }
+
+    #[test]
+    fn no_capacity_nodes_test() {
+        // no node has the zone
+        assert!(no_capacity_nodes(&zone("zone-a"), &Some(vec![])));
+        // the zone can't be resolved to nodes
+        assert!(no_capacity_nodes(&zone("zone-a"), &None));
+        // some nodes have the zone
+        assert!(!no_capacity_nodes(
+            &zone("zone-a"),
+            &Some(vec!["node-1".to_string()])
+        ));
+        // no segments, hence any node
+        assert!(!no_capacity_nodes(&HashMap::new(), &None));
+    }
+}
@@ -11,7 +11,7 @@ macro_rules! failure {
 
 use crate::{
     csi::*,
-    dev::Device,
+    dev::{Device, DeviceError},
     findmnt,
     mount::{self},
 };
@@ -140,3 +140,67 @@ pub fn unpublish_block_volume(msg: &NodeUnpublishVolumeRequest) -> Result<(), St
     info!("Volume {} unpublished from {}", volume_id, target_path);
     Ok(())
 }
+
+/// Get the size of the raw block volume published at the volume path.
+/// The condition of a block volume depends solely on its device, which is
+/// checked by the caller.
+pub fn block_volume_stats(
+    msg: &NodeGetVolumeStatsRequest,
+) -> Result<NodeGetVolumeStatsResponse, Status> {
+    let volume_path = &msg.volume_path;
+    let volume_id = &msg.volume_id;
+
+    let device_path = match findmnt::get_devicepath(volume_path) {
+        Ok(Some(device_path)) => device_path,
+        Ok(None) => {
+            return Err(failure!(
+                Code::NotFound,
+                "Failed to get stats for volume {}: no device mounted at {}",
+                volume_id,
+                volume_path
+            ));
+        }
+        Err(error) => {
+            return Err(failure!(
+                Code::Internal,
+                "Failed to get stats for volume {}: error whilst checking mount on {}: {}",
+                volume_id,
+                volume_path,
+                error
+            ));
+        }
+    };
+
+    let size = device_size(&device_path).map_err(|error| {
+        failure!(
+            Code::Internal,
+            "Failed to get stats for volume {}: error reading the size of device {}: {}",
+            volume_id,
+            device_path,
+            error
+        )
+    })?;
+
+    Ok(NodeGetVolumeStatsResponse {
+        usage: vec![VolumeUsage {
+            total: size as i64,
+            unit: volume_usage::Unit::Bytes as i32,
+            ..Default::default()
+        }],
+        volume_condition: None,
+    })
+}
+
+/// Size in bytes of the block device, as reported by sysfs in 512 byte sectors.
+fn device_size(device_path: &str) -> Result<u64, DeviceError> {
+    let name = Path::new(device_path)
+        .file_name()
+        .ok_or_else(|| DeviceError::new("invalid device path"))?;
+    let path = Path::new("/sys/class/block").join(name).join("size");
+    let sectors = std::fs::read_to_string(path)?;
+    let sectors = sectors
+        .trim()
+        .parse::<u64>()
+        .map_err(|error| DeviceError::from(error.to_string()))?;
+    Ok(sectors * 512)
+}
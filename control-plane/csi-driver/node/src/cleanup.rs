@@ -0,0 +1,351 @@
+//! Cleanup of the devices and mounts which are left over on the node, eg after a restart of the
+//! node plugin, for volumes which are no longer published by the control plane.
+//!
+//! The cleanup is performed when the node plugin starts, as follows:
+//!  - the devices attached by us are enumerated from udev and their volumes are checked against the
+//!    volumes of the control plane. The devices of the volumes which no longer exist are unmounted
+//!    from the kubelet paths and detached. The devices of the volumes which still exist are only
+//!    detached when the kubelet has neither staged nor published them on this node, and when the
+//!    volume has a target. The node of the target doesn't matter, as the target may be on any node.
+//!  - the mounts under the kubelet paths of the volumes of the control plane are checked for their
+//!    source device. The mounts whose source device no longer exists are unmounted.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    time::Duration,
+};
+
+use common_lib::types::v0::openapi::clients;
+use devinfo::mountinfo::MountIter;
+use regex::Regex;
+
+use crate::{dev::Device, findmnt, mount};
+
+/// Pattern of the names of the persistent volumes, which are part of their kubelet paths.
+const VOLUME_NAME_PATTERN: &str =
+    r"pvc-([0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})";
+/// Directory of the kubelet staging paths of the block volumes, relative to the kubelet directory.
+/// Block volumes are not mounted when they're staged, so only their staging path remains.
+const BLOCK_STAGING_DIR: &str = "plugins/kubernetes.io/csi/volumeDevices/staging";
+
+/// Cleanup of the orphaned devices and mounts of the node.
+#[derive(Debug)]
+pub(crate) struct OrphanCleanup {
+    rest_endpoint: String,
+    io_timeout: Duration,
+    kubelet_dir: String,
+    dry_run: bool,
+}
+
+impl OrphanCleanup {
+    /// Create a new `Self` which checks the volumes of the node against the control plane's REST
+    /// `rest_endpoint` and which cleans up the mounts under `kubelet_dir`.
+    /// With `dry_run` the orphans are only reported.
+    pub(crate) fn new(
+        rest_endpoint: &str,
+        io_timeout: Duration,
+        kubelet_dir: &str,
+        dry_run: bool,
+    ) -> Self {
+        Self {
+            rest_endpoint: rest_endpoint.to_string(),
+            io_timeout,
+            kubelet_dir: kubelet_dir.trim_end_matches('/').to_string(),
+            dry_run,
+        }
+    }
+
+    /// Cleanup the orphaned devices and then any stale mounts.
+    pub(crate) async fn run(&self) -> Result<(), String> {
+        info!(
+            "Cleaning up orphaned devices and mounts (dry run: {})",
+            self.dry_run
+        );
+
+        let volumes = self.volume_targets().await?;
+        self.cleanup_devices(&volumes).await?;
+        self.cleanup_mounts(&volumes);
+
+        info!("Cleanup of orphaned devices and mounts completed");
+        Ok(())
+    }
+
+    /// Get the volumes of the control plane, by uuid, along with whether they have a target.
+    async fn volume_targets(&self) -> Result<HashMap<String, bool>, String> {
+        let url = clients::tower::Url::parse(&self.rest_endpoint).map_err(|error| {
+            format!(
+                "Invalid API endpoint URL {}: {:?}",
+                self.rest_endpoint, error
+            )
+        })?;
+        let config = clients::tower::Configuration::new(url, self.io_timeout, None, None, true)
+            .map_err(|error| {
+                format!(
+                    "Failed to create openapi configuration, Error: '{:?}'",
+                    error
+                )
+            })?;
+        let client = clients::tower::ApiClient::new(config);
+
+        let mut volumes = HashMap::new();
+        let mut starting_token = Some(0);
+        while let Some(token) = starting_token {
+            let response = client
+                .volumes_api()
+                .get_volumes(0, Some(token))
+                .await
+                .map_err(|error| format!("Failed to list volumes: {:?}", error))?
+                .into_body();
+            for volume in response.entries {
+                volumes.insert(volume.spec.uuid.to_string(), volume.state.target.is_some());
+            }
+            starting_token = response.next_token;
+        }
+
+        Ok(volumes)
+    }
+
+    /// Unmount and detach the devices of the volumes which don't exist, or which are not used by
+    /// the kubelet on this node.
+    async fn cleanup_devices(&self, volumes: &HashMap<String, bool>) -> Result<(), String> {
+        let devices = Device::list()
+            .await
+            .map_err(|error| format!("Failed to list the attached devices: {}", error))?;
+        let kubelet_volumes = self.kubelet_volumes()?;
+
+        for (uuid, device) in devices {
+            let devname = device.devname();
+            let mounts = match findmnt::get_mountpaths(&devname) {
+                Ok(mounts) => mounts,
+                Err(error) => {
+                    error!("Failed to find the mounts of device {}: {}", devname, error);
+                    continue;
+                }
+            };
+            let staged = kubelet_volumes.contains(&uuid.to_string())
+                || mounts
+                    .iter()
+                    .any(|mount| self.is_kubelet_path(&mount.mount_path));
+            let target = volumes.get(&uuid.to_string()).copied();
+            if !is_orphaned(target, staged) {
+                debug!("Device {} of volume {} is not orphaned", devname, uuid);
+                continue;
+            }
+            match target {
+                Some(_) => warn!(
+                    "Device {} of volume {} is not staged on this node",
+                    devname, uuid
+                ),
+                None => warn!("Device {} of unknown volume {} is orphaned", devname, uuid),
+            }
+            if let Some(mount) = mounts
+                .iter()
+                .find(|mount| !self.is_kubelet_path(&mount.mount_path))
+            {
+                warn!(
+                    "Not cleaning up device {} of volume {} as it's mounted outside of {}: {}",
+                    devname, uuid, self.kubelet_dir, mount.mount_path
+                );
+                continue;
+            }
+
+            let mut unmounted = true;
+            for mount in &mounts {
+                unmounted &= self.unmount(&mount.mount_path);
+            }
+            if !unmounted {
+                continue;
+            }
+
+            if self.dry_run {
+                info!("Would detach device {} of volume {}", devname, uuid);
+            } else if let Err(error) = device.detach().await {
+                error!(
+                    "Failed to detach device {} of volume {}: {}",
+                    devname, uuid, error
+                );
+            } else {
+                info!("Detached device {} of volume {}", devname, uuid);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the uuids of the volumes which the kubelet has staged or published on this node, from
+    /// the paths of the mounts and of the block volume staging paths under the kubelet directory.
+    fn kubelet_volumes(&self) -> Result<HashSet<String>, String> {
+        let mut volumes = HashSet::new();
+
+        let mounts =
+            MountIter::new().map_err(|error| format!("Failed to list the mounts: {}", error))?;
+        for mount in mounts.flatten() {
+            let dest = mount.dest.to_string_lossy();
+            if self.is_kubelet_path(&dest) {
+                volumes.extend(path_volume(&dest));
+            }
+        }
+
+        let block_staging = Path::new(&self.kubelet_dir).join(BLOCK_STAGING_DIR);
+        match std::fs::read_dir(&block_staging) {
+            Ok(entries) => {
+                for entry in entries.flatten() {
+                    volumes.extend(path_volume(&entry.file_name().to_string_lossy()));
+                }
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => {
+                return Err(format!(
+                    "Failed to list the block volume staging paths {}: {}",
+                    block_staging.display(),
+                    error
+                ))
+            }
+        }
+
+        Ok(volumes)
+    }
+
+    /// Unmount the kubelet mounts of the volumes whose source device no longer exists.
+    fn cleanup_mounts(&self, volumes: &HashMap<String, bool>) {
+        let mounts = match MountIter::new() {
+            Ok(mounts) => mounts,
+            Err(error) => {
+                error!("Failed to list the mounts: {}", error);
+                return;
+            }
+        };
+
+        for mount in mounts.flatten() {
+            let dest = mount.dest.to_string_lossy();
+            let source = mount.source.to_string_lossy();
+            if !self.is_kubelet_path(&dest) {
+                continue;
+            }
+            let uuid = match path_volume(&dest) {
+                Some(uuid) => uuid,
+                None => continue,
+            };
+            // only the mounts of our own volumes may be cleaned up
+            if !volumes.contains_key(&uuid) {
+                continue;
+            }
+            // the source of a block volume's bind mount is not the device itself
+            if !source.starts_with("/dev/") || Path::new(&*source).exists() {
+                continue;
+            }
+
+            warn!(
+                "Mount {} of volume {} is stale as its device {} no longer exists",
+                dest, uuid, source
+            );
+            self.unmount(&dest);
+        }
+    }
+
+    /// Unmount the `mount_path`, returning whether it is (or would be) unmounted.
+    fn unmount(&self, mount_path: &str) -> bool {
+        if self.dry_run {
+            info!("Would unmount {}", mount_path);
+            return true;
+        }
+
+        let result = if Path::new(mount_path).is_dir() {
+            mount::filesystem_unmount(mount_path)
+        } else {
+            mount::blockdevice_unmount(mount_path)
+        };
+        match result {
+            Ok(_) => {
+                info!("Unmounted {}", mount_path);
+                true
+            }
+            Err(error) => {
+                error!("Failed to unmount {}: {}", mount_path, error);
+                false
+            }
+        }
+    }
+
+    /// Check whether the `path` is under the kubelet directory.
+    fn is_kubelet_path(&self, path: &str) -> bool {
+        Path::new(path).starts_with(&self.kubelet_dir)
+    }
+}
+
+/// Get the uuid of the volume whose name is part of the kubelet `path`, if any.
+fn path_volume(path: &str) -> Option<String> {
+    lazy_static! {
+        static ref VOLUME_NAME: Regex = Regex::new(VOLUME_NAME_PATTERN).unwrap();
+    }
+    VOLUME_NAME
+        .captures(path)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Check whether the device of a volume is orphaned, given whether the volume has a target, if it
+/// exists, and whether it's `staged` or published by the kubelet on this node.
+/// The target isn't necessarily on this node, eg for unpinned volumes, and it may be briefly
+/// missing, eg while it's being recreated, so the devices of existing volumes are only orphaned
+/// when the kubelet doesn't use them and the volume has a target.
+fn is_orphaned(target: Option<bool>, staged: bool) -> bool {
+    match target {
+        None => true,
+        Some(false) => false,
+        Some(true) => !staged,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_orphaned, path_volume};
+
+    const UUID: &str = "ec4e66fd-3b33-4439-b504-d49aba53da26";
+
+    #[test]
+    fn unknown_volume() {
+        assert!(is_orphaned(None, false));
+        assert!(is_orphaned(None, true));
+    }
+
+    #[test]
+    fn unpinned_volume_with_remote_target() {
+        // the target of an unpinned volume is placed on another node, while its device is attached
+        // and staged on this one
+        assert!(!is_orphaned(Some(true), true));
+    }
+
+    #[test]
+    fn volume_not_staged() {
+        assert!(is_orphaned(Some(true), false));
+    }
+
+    #[test]
+    fn volume_without_target() {
+        assert!(!is_orphaned(Some(false), false));
+        assert!(!is_orphaned(Some(false), true));
+    }
+
+    #[test]
+    fn kubelet_path_volume() {
+        let paths = [
+            format!(
+                "/var/lib/kubelet/plugins/kubernetes.io/csi/pv/pvc-{}/globalmount",
+                UUID
+            ),
+            format!(
+                "/var/lib/kubelet/pods/a1/volumes/kubernetes.io~csi/pvc-{}/mount",
+                UUID
+            ),
+            format!("pvc-{}", UUID),
+        ];
+        for path in &paths {
+            assert_eq!(path_volume(path).as_deref(), Some(UUID));
+        }
+        assert_eq!(
+            path_volume("/var/lib/kubelet/plugins/kubernetes.io/csi/io.openebs/a1/globalmount"),
+            None
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use clap::ArgMatches;
+use csi_driver::context;
 use std::{
     collections::HashMap,
     sync::{Arc, Mutex, MutexGuard},
@@ -6,6 +7,17 @@ use std::{
 
 /// Number of Nvme IO Queues.
 pub(crate) const NVME_NR_IO_QUEUES_NAME: &str = "nvme-nr-io-queues";
+/// Controller loss timeout, in seconds.
+pub(crate) const NVME_CTRL_LOSS_TMO_NAME: &str = "nvme-ctrl-loss-tmo";
+/// Delay between reconnect attempts, in seconds.
+pub(crate) const NVME_RECONNECT_DELAY_NAME: &str = "nvme-reconnect-delay";
+
+/// Storage class parameters of the Nvme configuration, along with their config names.
+const NVME_PARAMETERS: [(&str, &str); 3] = [
+    (context::NVME_NR_IO_QUEUES, NVME_NR_IO_QUEUES_NAME),
+    (context::NVME_CTRL_LOSS_TMO, NVME_CTRL_LOSS_TMO_NAME),
+    (context::NVME_RECONNECT_DELAY, NVME_RECONNECT_DELAY_NAME),
+];
 
 /// Global configuration parameters.
 #[derive(Debug, Default)]
@@ -27,15 +39,22 @@ impl Config {
 #[derive(Debug, Default)]
 pub(crate) struct NvmeConfig {
     nr_io_queues: Option<u32>,
+    ctrl_loss_tmo: Option<u32>,
+    reconnect_delay: Option<u32>,
 }
 impl NvmeConfig {
-    fn new(nr_io_queues: Option<u32>) -> Self {
-        Self { nr_io_queues }
-    }
     /// Number of IO Queues.
     pub(crate) fn nr_io_queues(&self) -> Option<u32> {
         self.nr_io_queues
     }
+    /// Controller loss timeout, in seconds.
+    pub(crate) fn ctrl_loss_tmo(&self) -> Option<u32> {
+        self.ctrl_loss_tmo
+    }
+    /// Delay between reconnect attempts, in seconds.
+    pub(crate) fn reconnect_delay(&self) -> Option<u32> {
+        self.reconnect_delay
+    }
 }
 
 /// Get a mutex guard over the `Config`.
@@ -52,28 +71,36 @@ pub(crate) struct NvmeArgValues(HashMap<String, String>);
 impl TryFrom<NvmeArgValues> for NvmeConfig {
     type Error = String;
     fn try_from(src: NvmeArgValues) -> Result<Self, Self::Error> {
-        let nvme_nr_ioq = match src.0.get(NVME_NR_IO_QUEUES_NAME) {
-            None => None,
-            Some(value) => {
-                let value = value.parse::<u32>().map_err(|error| {
-                    format!(
-                        "Invalid value for {}, error = {}",
-                        NVME_NR_IO_QUEUES_NAME, error
-                    )
-                })?;
-                Some(value)
+        let parse = |name: &str| -> Result<Option<u32>, String> {
+            match src.0.get(name) {
+                None => Ok(None),
+                Some(value) => {
+                    let value = value.parse::<u32>().map_err(|error| {
+                        format!("Invalid value for {}, error = {}", name, error)
+                    })?;
+                    Ok(Some(value))
+                }
             }
         };
-        Ok(Self::new(nvme_nr_ioq))
+        Ok(Self {
+            nr_io_queues: parse(NVME_NR_IO_QUEUES_NAME)?,
+            ctrl_loss_tmo: parse(NVME_CTRL_LOSS_TMO_NAME)?,
+            reconnect_delay: parse(NVME_RECONNECT_DELAY_NAME)?,
+        })
     }
 }
 /// Nvme Arguments taken from the CSI volume calls (storage class parameters)
 pub type NvmeParseParams<'a> = &'a HashMap<String, String>;
 impl TryFrom<NvmeParseParams<'_>> for NvmeArgValues {
     type Error = String;
-    fn try_from(_value: NvmeParseParams) -> Result<Self, Self::Error> {
-        // nothing parsed for now
-        Ok(Default::default())
+    fn try_from(value: NvmeParseParams) -> Result<Self, Self::Error> {
+        let mut map = NvmeArgValues::default();
+        for (parameter, name) in NVME_PARAMETERS {
+            if let Some(value) = value.get(parameter) {
+                map.0.insert(name.into(), value.to_string());
+            }
+        }
+        Ok(map)
     }
 }
 impl TryFrom<NvmeParseParams<'_>> for NvmeConfig {
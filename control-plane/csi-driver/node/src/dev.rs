@@ -66,6 +66,15 @@ pub trait Detach: Sync + Send {
 
 pub struct Device;
 
+/// Get the NQN of the nvmf target of the volume.
+fn nvmf_nqn(uuid: &Uuid) -> String {
+    if std::env::var("MOAC").is_ok() {
+        format!("{}:nexus-{}", NVME_NQN_PREFIX, uuid)
+    } else {
+        format!("{}:{}", NVME_NQN_PREFIX, uuid)
+    }
+}
+
 impl Device {
     /// Main dispatch function for parsing URIs in order
     /// to obtain a device implementing the Attach trait.
@@ -105,14 +114,9 @@ impl Device {
             }
 
             if let Some(devname) = match_dev::match_nvmf_device(&device, &nvmf_key) {
-                let nqn = if std::env::var("MOAC").is_ok() {
-                    format!("{}:nexus-{}", NVME_NQN_PREFIX, uuid)
-                } else {
-                    format!("{}:{}", NVME_NQN_PREFIX, uuid)
-                };
                 return Ok(Some(Box::new(nvmf::NvmfDetach::new(
                     devname.to_string(),
-                    nqn,
+                    nvmf_nqn(uuid),
                 ))));
             }
         }
@@ -120,6 +124,40 @@ impl Device {
         Ok(None)
     }
 
+    /// Enumerate all the existing devices in udev which were attached by us,
+    /// along with the UUID of their volume, to obtain devices implementing
+    /// the Detach trait.
+    pub async fn list() -> Result<Vec<(Uuid, Box<dyn Detach>)>, DeviceError> {
+        let mut devices: Vec<(Uuid, Box<dyn Detach>)> = Vec::new();
+
+        let mut enumerator = Enumerator::new()?;
+
+        enumerator.match_subsystem("block")?;
+        enumerator.match_property("DEVTYPE", "disk")?;
+
+        for device in enumerator.scan_devices()? {
+            if let Some((devname, path)) = match_dev::match_iscsi_device(&device) {
+                match iscsi::IscsiDetach::from_path(devname.to_string(), path) {
+                    Ok(value) => devices.push((*value.uuid(), Box::new(value))),
+                    Err(error) => warn!("Ignoring iscsi device {}: {}", devname, error),
+                }
+                continue;
+            }
+
+            if let Some((devname, wwn)) = match_dev::match_nvmf_device_wwn(&device) {
+                match Uuid::parse_str(wwn.trim_start_matches("uuid.")) {
+                    Ok(uuid) => devices.push((
+                        uuid,
+                        Box::new(nvmf::NvmfDetach::new(devname.to_string(), nvmf_nqn(&uuid))),
+                    )),
+                    Err(error) => warn!("Ignoring nvmf device {}: {}", devname, error),
+                }
+            }
+        }
+
+        Ok(devices)
+    }
+
     /// Wait for a device to show up in udev
     /// once attach() has been called.
     pub async fn wait_for_device(
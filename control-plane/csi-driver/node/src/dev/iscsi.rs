@@ -1,5 +1,6 @@
-use std::{collections::HashMap, convert::TryFrom};
+use std::{collections::HashMap, convert::TryFrom, path::Path};
 
+use csi_driver::context;
 use regex::Regex;
 use udev::Enumerator;
 use url::Url;
@@ -17,6 +18,10 @@ pub(super) struct IscsiDevice {
     iqn: String,
     uuid: Uuid,
     lun: u16,
+    io_timeout: Option<u32>,
+    replacement_timeout: Option<u32>,
+    noop_out_interval: Option<u32>,
+    noop_out_timeout: Option<u32>,
 }
 
 impl IscsiDevice {
@@ -26,9 +31,36 @@ impl IscsiDevice {
             iqn,
             uuid,
             lun,
+            io_timeout: None,
+            replacement_timeout: None,
+            noop_out_interval: None,
+            noop_out_timeout: None,
         }
     }
 
+    /// Apply the session parameters to the node record of the target, so that they're used when
+    /// logging in.
+    fn configure(&self) -> Result<(), DeviceError> {
+        let mut settings = Vec::new();
+        if let Some(timeout) = self.replacement_timeout {
+            settings.push((
+                "node.session.timeo.replacement_timeout",
+                timeout.to_string(),
+            ));
+        }
+        if let Some(interval) = self.noop_out_interval {
+            settings.push(("node.conn[0].timeo.noop_out_interval", interval.to_string()));
+        }
+        if let Some(timeout) = self.noop_out_timeout {
+            settings.push(("node.conn[0].timeo.noop_out_timeout", timeout.to_string()));
+        }
+
+        for (name, value) in settings {
+            IscsiAdmin::update(&self.portal, &self.iqn, name, &value)?;
+        }
+        Ok(())
+    }
+
     fn to_path(&self) -> String {
         format!("ip-{}-iscsi-{}-lun-{}", self.portal, self.iqn, self.lun)
     }
@@ -113,8 +145,22 @@ impl TryFrom<&Url> for IscsiDevice {
 impl Attach for IscsiAttach {
     async fn parse_parameters(
         &mut self,
-        _context: &HashMap<String, String>,
+        context: &HashMap<String, String>,
     ) -> Result<(), DeviceError> {
+        let parse = |name: &str| -> Result<Option<u32>, DeviceError> {
+            match context.get(name) {
+                Some(value) => value.parse::<u32>().map(Some).map_err(|_| {
+                    DeviceError::new(&format!("Invalid {} value: \"{}\"", name, value))
+                }),
+                None => Ok(None),
+            }
+        };
+        // The IO timeout of the scsi device, which is independent of how long the session may
+        // take to recover, ie: the replacement timeout.
+        self.io_timeout = parse("ioTimeout")?;
+        self.replacement_timeout = parse(context::ISCSI_REPLACEMENT_TIMEOUT)?;
+        self.noop_out_interval = parse(context::ISCSI_NOOP_OUT_INTERVAL)?;
+        self.noop_out_timeout = parse(context::ISCSI_NOOP_OUT_TIMEOUT)?;
         Ok(())
     }
 
@@ -141,6 +187,14 @@ impl Attach for IscsiAttach {
             )));
         }
 
+        if let Err(error) = self.configure() {
+            let _ = IscsiAdmin::delete(&self.portal, &self.iqn);
+            return Err(DeviceError::from(format!(
+                "iscsiadm command (update) failed: {}",
+                error
+            )));
+        }
+
         if let Err(error) = IscsiAdmin::login(&self.portal, &self.iqn) {
             let _ = IscsiAdmin::delete(&self.portal, &self.iqn);
             return Err(DeviceError::from(format!(
@@ -172,6 +226,22 @@ impl Attach for IscsiAttach {
     }
 
     async fn fixup(&self) -> Result<(), DeviceError> {
+        if let Some(io_timeout) = self.io_timeout {
+            let devname = self
+                .find()
+                .await?
+                .ok_or_else(|| DeviceError::new("iSCSI device not found"))?;
+            let name = Path::new(&devname)
+                .file_name()
+                .ok_or_else(|| DeviceError::new(&format!("invalid device \"{}\"", devname)))?;
+            let path = Path::new("/sys/block").join(name).join("device");
+            debug!(
+                "Setting IO timeout on \"{}\" to {}s",
+                path.to_string_lossy(),
+                io_timeout
+            );
+            sysfs::write_value(&path, "timeout", io_timeout)?;
+        }
         Ok(())
     }
 }
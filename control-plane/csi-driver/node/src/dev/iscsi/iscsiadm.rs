@@ -78,6 +78,43 @@ impl IscsiAdmin {
         IscsiAdmin::execute(&args)
     }
 
+    /// Update the `name` setting of the node record of the target to `value`.
+    pub(super) fn update(
+        portal: &str,
+        iqn: &str,
+        name: &str,
+        value: &str,
+    ) -> Result<(), DeviceError> {
+        let iscsiadm = IscsiAdmin::get_binary()?;
+
+        let args = [
+            "--mode",
+            "node",
+            "--targetname",
+            iqn,
+            "--portal",
+            portal,
+            "--interface",
+            "default",
+            "--op",
+            "update",
+            "--name",
+            name,
+            "--value",
+            value,
+        ];
+
+        trace!("iscsiadm {:?}", &args);
+
+        let output = Command::new(iscsiadm).args(&args).output()?;
+
+        if output.status.success() {
+            return Ok(());
+        }
+
+        Err(DeviceError::from(String::from_utf8(output.stderr).unwrap()))
+    }
+
     pub(super) fn logout(portal: &str, iqn: &str) -> Result<(), DeviceError> {
         let args = [
             "--mode",
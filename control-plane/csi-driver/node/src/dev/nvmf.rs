@@ -34,6 +34,8 @@ pub(super) struct NvmfAttach {
     nqn: String,
     io_timeout: Option<u32>,
     nr_io_queues: Option<u32>,
+    ctrl_loss_tmo: Option<u32>,
+    reconnect_delay: Option<u32>,
 }
 
 impl NvmfAttach {
@@ -51,6 +53,8 @@ impl NvmfAttach {
             nqn,
             io_timeout: None,
             nr_io_queues,
+            ctrl_loss_tmo: None,
+            reconnect_delay: None,
         }
     }
 
@@ -130,6 +134,8 @@ impl Attach for NvmfAttach {
         if let Some(nr_io_queues) = nvme_config.nr_io_queues() {
             self.nr_io_queues = Some(nr_io_queues);
         }
+        self.ctrl_loss_tmo = nvme_config.ctrl_loss_tmo();
+        self.reconnect_delay = nvme_config.reconnect_delay();
         Ok(())
     }
 
@@ -146,12 +152,14 @@ impl Attach for NvmfAttach {
             }
             None => None,
         };
+        // Any explicitly requested timeouts take precedence over the ones derived from the
+        // IO timeout.
         let ca = ConnectArgsBuilder::default()
             .traddr(&self.host)
             .trsvcid(self.port.to_string())
             .nqn(&self.nqn)
-            .ctrl_loss_tmo(self.io_timeout)
-            .reconnect_delay(reconnect_delay)
+            .ctrl_loss_tmo(self.ctrl_loss_tmo.or(self.io_timeout))
+            .reconnect_delay(self.reconnect_delay.or(reconnect_delay))
             .nr_io_queues(self.nr_io_queues)
             .build()?;
         match ca.connect() {
@@ -1,6 +1,7 @@
 //! Functions for CSI stage, unstage, publish and unpublish filesystem volumes.
 
-use std::{fs, io::ErrorKind, path::PathBuf};
+use nix::sys::statvfs::{statvfs, FsFlags};
+use std::{collections::HashMap, fs, io::ErrorKind, path::PathBuf};
 
 use tonic::{Code, Status};
 
@@ -12,9 +13,47 @@ macro_rules! failure {
 use crate::{
     csi::{volume_capability::MountVolume, *},
     format::prepare_device,
+    fsck::{self, FsCheckOutcome, FsCheckPolicy},
     mount::{self, subset, ReadOnly},
 };
 
+/// Volume context key of the filesystem type requested by the storage class.
+const FS_TYPE: &str = "fsType";
+/// Volume context key of the whitespace separated mkfs options.
+const MKFS_OPTIONS: &str = "mkfsOptions";
+/// Volume context key of the comma separated default mount options.
+const MOUNT_OPTIONS: &str = "mountOptions";
+
+/// Get the mkfs options from the volume context.
+fn mkfs_options(volume_context: &HashMap<String, String>) -> Vec<String> {
+    match volume_context.get(MKFS_OPTIONS) {
+        Some(options) => options.split_whitespace().map(String::from).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Get the mount options for staging, ie the default mount options from the
+/// volume context followed by any additional mount flags of the capability.
+fn stage_mount_options(
+    volume_context: &HashMap<String, String>,
+    mount_flags: &[String],
+) -> Vec<String> {
+    let mut options: Vec<String> = match volume_context.get(MOUNT_OPTIONS) {
+        Some(options) => options
+            .split(',')
+            .filter(|option| !option.is_empty())
+            .map(String::from)
+            .collect(),
+        None => Vec::new(),
+    };
+    for flag in mount_flags {
+        if !options.contains(flag) {
+            options.push(flag.clone());
+        }
+    }
+    options
+}
+
 pub async fn stage_fs_volume(
     msg: &NodeStageVolumeRequest,
     device_path: String,
@@ -39,22 +78,56 @@ pub async fn stage_fs_volume(
 
     debug!("Staging volume {} to {}", volume_id, fs_staging_path);
 
-    let fstype = if mnt.fs_type.is_empty() {
-        String::from(&filesystems[0])
+    // the filesystem type of the capability takes precedence over the one from the storage
+    // class, which is recorded in the volume context
+    let requested_fstype = if mnt.fs_type.is_empty() {
+        msg.volume_context
+            .get(FS_TYPE)
+            .filter(|fstype| !fstype.is_empty())
     } else {
-        match filesystems.iter().find(|&entry| entry == &mnt.fs_type) {
+        Some(&mnt.fs_type)
+    };
+    let fstype = match requested_fstype {
+        None => String::from(&filesystems[0]),
+        Some(requested) => match filesystems.iter().find(|&entry| entry == requested) {
             Some(fstype) => String::from(fstype),
             None => {
                 return Err(failure!(
                     Code::InvalidArgument,
                     "Failed to stage volume {}: unsupported filesystem type: {}",
                     volume_id,
-                    mnt.fs_type
+                    requested
                 ));
             }
-        }
+        },
     };
 
+    let mkfs_options = mkfs_options(&msg.volume_context);
+    if !mkfs_options.is_empty() {
+        // mkfs options are specific to the filesystem type they were validated against
+        if let Some(context_fstype) = msg.volume_context.get(FS_TYPE) {
+            if context_fstype != &fstype {
+                return Err(failure!(
+                    Code::InvalidArgument,
+                    "Failed to stage volume {}: filesystem type ({}) does not match the type ({}) of the mkfs options",
+                    volume_id,
+                    fstype,
+                    context_fstype
+                ));
+            }
+        }
+    }
+    let mount_options = stage_mount_options(&msg.volume_context, &mnt.mount_flags);
+
+    let fs_check = FsCheckPolicy::from_volume_context(&msg.volume_context).map_err(|error| {
+        failure!(
+            Code::InvalidArgument,
+            "Failed to stage volume {}: {}",
+            volume_id,
+            error
+        )
+    })?;
+
     if mount::find_mount(Some(&device_path), Some(fs_staging_path)).is_some() {
         debug!(
             "Device {} is already mounted onto {}",
@@ -87,7 +160,11 @@ pub async fn stage_fs_volume(
         ));
     }
 
-    if let Err(error) = prepare_device(&device_path, &fstype).await {
+    if fs_check != FsCheckPolicy::None {
+        check_fs_volume(volume_id, &device_path, fs_check).await?;
+    }
+
+    if let Err(error) = prepare_device(&device_path, &fstype, &mkfs_options).await {
         return Err(failure!(
             Code::Internal,
             "Failed to stage volume {}: error preparing device {}: {}",
@@ -100,7 +177,7 @@ pub async fn stage_fs_volume(
     debug!("Mounting device {} onto {}", device_path, fs_staging_path);
 
     if let Err(error) =
-        mount::filesystem_mount(&device_path, fs_staging_path, &fstype, &mnt.mount_flags)
+        mount::filesystem_mount(&device_path, fs_staging_path, &fstype, &mount_options)
     {
         return Err(failure!(
             Code::Internal,
@@ -117,11 +194,70 @@ pub async fn stage_fs_volume(
     Ok(())
 }
 
+/// Check the filesystem of the device of the volume before it's staged, according to the
+/// `policy`, and refuse to stage the volume when required.
+/// Any errors which were found are reported as the volume condition once it's staged.
+async fn check_fs_volume(
+    volume_id: &str,
+    device_path: &str,
+    policy: FsCheckPolicy,
+) -> Result<(), Status> {
+    fsck::clear_outcome(volume_id);
+
+    match fsck::check_device(device_path).await {
+        Ok(FsCheckOutcome::Clean) => {
+            debug!("Filesystem on device {} is clean", device_path);
+        }
+        Ok(FsCheckOutcome::Repaired(summary)) => {
+            warn!(
+                "Volume {}: repaired errors on the filesystem of device {}: {}",
+                volume_id, device_path, summary
+            );
+            fsck::record_outcome(volume_id, FsCheckOutcome::Repaired(summary));
+        }
+        Ok(FsCheckOutcome::Unrecoverable(summary)) if policy == FsCheckPolicy::Refuse => {
+            return Err(failure!(
+                Code::FailedPrecondition,
+                "Failed to stage volume {}: filesystem on device {} has unrecoverable errors: {}",
+                volume_id,
+                device_path,
+                summary
+            ));
+        }
+        Ok(FsCheckOutcome::Unrecoverable(summary)) => {
+            warn!(
+                "Volume {}: staging despite unrecoverable errors on the filesystem of device {}: {}",
+                volume_id, device_path, summary
+            );
+            fsck::record_outcome(volume_id, FsCheckOutcome::Unrecoverable(summary));
+        }
+        Err(error) if policy == FsCheckPolicy::Refuse => {
+            return Err(failure!(
+                Code::Internal,
+                "Failed to stage volume {}: error checking the filesystem of device {}: {}",
+                volume_id,
+                device_path,
+                error
+            ));
+        }
+        Err(error) => {
+            warn!(
+                "Volume {}: staging despite the failure to check the filesystem of device {}: {}",
+                volume_id, device_path, error
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Unstage a filesystem volume
 pub async fn unstage_fs_volume(msg: &NodeUnstageVolumeRequest) -> Result<(), Status> {
     let volume_id = &msg.volume_id;
     let fs_staging_path = &msg.staging_target_path;
 
+    fsck::clear_outcome(volume_id);
+
     if let Some(mount) = mount::find_mount(None, Some(fs_staging_path)) {
         debug!(
             "Unstaging filesystem volume {}, unmounting device {:?} from {}",
@@ -328,3 +464,91 @@ pub fn unpublish_fs_volume(msg: &NodeUnpublishVolumeRequest) -> Result<(), Statu
     info!("Volume {} unpublished from {}", volume_id, target_path);
     Ok(())
 }
+
+/// Get the byte and inode usage of the filesystem volume at the volume path.
+/// The condition is only set, as abnormal, if the filesystem was mounted read-write but has
+/// since become read-only, as happens when the filesystem encounters errors
+/// (eg: ext4's errors=remount-ro).
+pub fn fs_volume_stats(
+    msg: &NodeGetVolumeStatsRequest,
+) -> Result<NodeGetVolumeStatsResponse, Status> {
+    let volume_path = &msg.volume_path;
+    let volume_id = &msg.volume_id;
+
+    let mount = mount::find_mount(None, Some(volume_path)).ok_or_else(|| {
+        failure!(
+            Code::NotFound,
+            "Failed to get stats for volume {}: no mount for volume path {}",
+            volume_id,
+            volume_path
+        )
+    })?;
+
+    let stats = statvfs(volume_path.as_str()).map_err(|error| {
+        failure!(
+            Code::Internal,
+            "Failed to get stats for volume {}: statvfs on {} failed: {}",
+            volume_id,
+            volume_path,
+            error
+        )
+    })?;
+
+    let fragment_size = stats.fragment_size() as i64;
+    let usage = vec![
+        VolumeUsage {
+            available: stats.blocks_available() as i64 * fragment_size,
+            total: stats.blocks() as i64 * fragment_size,
+            used: stats.blocks().saturating_sub(stats.blocks_free()) as i64 * fragment_size,
+            unit: volume_usage::Unit::Bytes as i32,
+        },
+        VolumeUsage {
+            available: stats.files_available() as i64,
+            total: stats.files() as i64,
+            used: stats.files().saturating_sub(stats.files_free()) as i64,
+            unit: volume_usage::Unit::Inodes as i32,
+        },
+    ];
+
+    let volume_condition =
+        if stats.flags().contains(FsFlags::ST_RDONLY) && !mount.options.readonly() {
+            warn!(
+                "Volume {}: filesystem on {:?} mounted at {} has become read-only",
+                volume_id, mount.source, volume_path
+            );
+            Some(VolumeCondition {
+                abnormal: true,
+                message: format!(
+                    "filesystem on {} has become read-only, possibly due to errors",
+                    mount.source.to_string_lossy()
+                ),
+            })
+        } else {
+            let source = mount.source.to_string_lossy();
+            fsck::outcome(volume_id).map(|outcome| match outcome {
+                FsCheckOutcome::Unrecoverable(errors) => VolumeCondition {
+                    abnormal: true,
+                    message: format!(
+                        "filesystem on {} has unrecoverable errors: {}",
+                        source, errors
+                    ),
+                },
+                FsCheckOutcome::Repaired(errors) => VolumeCondition {
+                    abnormal: false,
+                    message: format!(
+                        "filesystem on {} had errors which were repaired when staged: {}",
+                        source, errors
+                    ),
+                },
+                FsCheckOutcome::Clean => VolumeCondition {
+                    abnormal: false,
+                    message: String::from("volume is healthy"),
+                },
+            })
+        };
+
+    Ok(NodeGetVolumeStatsResponse {
+        usage,
+        volume_condition,
+    })
+}
@@ -4,7 +4,13 @@ use std::process::Command;
 
 use devinfo::blkid::probe::Probe;
 
-pub(crate) async fn prepare_device(device: &str, fstype: &str) -> Result<(), String> {
+/// Create a filesystem of type `fstype` on the `device`, unless it already has one,
+/// passing the additional `mkfs_options` to mkfs.
+pub(crate) async fn prepare_device(
+    device: &str,
+    fstype: &str,
+    mkfs_options: &[String],
+) -> Result<(), String> {
     debug!("Probing device {}", device);
 
     let probe = Probe::new_from_filename(device)
@@ -19,10 +25,14 @@ pub(crate) async fn prepare_device(device: &str, fstype: &str) -> Result<(), Str
         return Ok(());
     }
 
-    debug!("Creating new filesystem ({}) on device {}", fstype, device);
+    debug!(
+        "Creating new filesystem ({}) on device {} (options: {:?})",
+        fstype, device, mkfs_options
+    );
 
     let binary = format!("mkfs.{}", fstype);
     let output = Command::new(&binary)
+        .args(mkfs_options)
         .arg(device)
         .output()
         .map_err(|error| format!("failed to execute {}: {}", binary, error))?;
@@ -0,0 +1,198 @@
+//! Utility functions for checking the filesystem of a device before it is mounted
+
+use std::{
+    collections::HashMap,
+    process::{Command, Output},
+    str::FromStr,
+    sync::Mutex,
+};
+
+use devinfo::blkid::probe::Probe;
+
+/// Volume context key of the filesystem check policy requested by the storage class.
+pub(crate) const FS_CHECK: &str = "fsCheck";
+
+/// Policy for checking the filesystem of a volume when it is staged.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum FsCheckPolicy {
+    /// The filesystem is not checked.
+    None,
+    /// The filesystem is checked and repaired where possible, but it is mounted even if
+    /// unrecoverable errors are found, which are then reported as an abnormal volume condition.
+    Warn,
+    /// The filesystem is checked and repaired where possible, and it is not mounted if
+    /// unrecoverable errors are found.
+    Refuse,
+}
+
+impl FromStr for FsCheckPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "none" => Ok(Self::None),
+            "warn" => Ok(Self::Warn),
+            "refuse" => Ok(Self::Refuse),
+            _ => Err(format!("invalid filesystem check policy: {}", value)),
+        }
+    }
+}
+
+impl FsCheckPolicy {
+    /// Get the filesystem check policy from the volume context, defaulting to `None`.
+    pub(crate) fn from_volume_context(
+        volume_context: &HashMap<String, String>,
+    ) -> Result<Self, String> {
+        match volume_context.get(FS_CHECK) {
+            Some(policy) => policy.parse(),
+            None => Ok(Self::None),
+        }
+    }
+}
+
+/// Outcome of a filesystem check.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum FsCheckOutcome {
+    /// No errors were found, or there was no filesystem to check.
+    Clean,
+    /// Errors were found and repaired.
+    Repaired(String),
+    /// Errors were found which could not be repaired.
+    Unrecoverable(String),
+}
+
+lazy_static! {
+    /// Outcome of the filesystem check of the staged volumes which had errors, by volume id.
+    static ref OUTCOMES: Mutex<HashMap<String, FsCheckOutcome>> = Mutex::new(HashMap::new());
+}
+
+/// Record the `outcome` of the filesystem check of the volume, so that any errors which were
+/// found can be reported as its volume condition.
+pub(crate) fn record_outcome(volume_id: &str, outcome: FsCheckOutcome) {
+    if let Ok(mut outcomes) = OUTCOMES.lock() {
+        match outcome {
+            FsCheckOutcome::Clean => outcomes.remove(volume_id),
+            outcome => outcomes.insert(volume_id.to_string(), outcome),
+        };
+    }
+}
+
+/// Forget the outcome of the filesystem check of the volume.
+pub(crate) fn clear_outcome(volume_id: &str) {
+    if let Ok(mut outcomes) = OUTCOMES.lock() {
+        outcomes.remove(volume_id);
+    }
+}
+
+/// Get the outcome of the filesystem check of the volume, if it had errors.
+pub(crate) fn outcome(volume_id: &str) -> Option<FsCheckOutcome> {
+    OUTCOMES
+        .lock()
+        .ok()
+        .and_then(|outcomes| outcomes.get(volume_id).cloned())
+}
+
+/// Check the existing filesystem of the `device`, which must not be mounted.
+/// ext filesystems are checked and safely repaired with `e2fsck -p`, whereas xfs and btrfs
+/// filesystems are only checked, with `xfs_repair -n` and `btrfs check --readonly`.
+pub(crate) async fn check_device(device: &str) -> Result<FsCheckOutcome, String> {
+    debug!("Probing device {}", device);
+
+    let probe = Probe::new_from_filename(device)
+        .map_err(|error| format!("probe setup failed: {}", error))?;
+
+    if let Err(error) = probe.do_probe() {
+        return Err(format!("probe failed: {}", error));
+    }
+
+    let fstype = match probe.lookup_value("TYPE") {
+        Ok(fstype) => fstype,
+        Err(_) => {
+            debug!("No filesystem to check on device {}", device);
+            return Ok(FsCheckOutcome::Clean);
+        }
+    };
+
+    debug!("Checking filesystem ({}) on device {}", fstype, device);
+
+    match fstype.as_str() {
+        "ext2" | "ext3" | "ext4" => {
+            let output = run("e2fsck", &["-p", device])?;
+            // the exit code of e2fsck is a bit mask
+            match output.status.code() {
+                Some(0) => Ok(FsCheckOutcome::Clean),
+                Some(code) if code & !3 == 0 => Ok(FsCheckOutcome::Repaired(summary(&output))),
+                Some(code) if code & !7 == 0 => Ok(FsCheckOutcome::Unrecoverable(summary(&output))),
+                _ => Err(failed("e2fsck", &output)),
+            }
+        }
+        "xfs" => {
+            let output = run("xfs_repair", &["-n", device])?;
+            match output.status.code() {
+                Some(0) => Ok(FsCheckOutcome::Clean),
+                Some(1) => Ok(FsCheckOutcome::Unrecoverable(summary(&output))),
+                // the log is dirty, which is replayed when the filesystem is mounted
+                Some(2) => {
+                    debug!("Filesystem on device {} has a dirty log", device);
+                    Ok(FsCheckOutcome::Clean)
+                }
+                _ => Err(failed("xfs_repair", &output)),
+            }
+        }
+        "btrfs" => {
+            let output = run("btrfs", &["check", "--readonly", device])?;
+            match output.status.code() {
+                Some(0) => Ok(FsCheckOutcome::Clean),
+                Some(_) => Ok(FsCheckOutcome::Unrecoverable(summary(&output))),
+                None => Err(failed("btrfs check", &output)),
+            }
+        }
+        _ => {
+            debug!(
+                "Not checking unsupported filesystem ({}) on device {}",
+                fstype, device
+            );
+            Ok(FsCheckOutcome::Clean)
+        }
+    }
+}
+
+/// Run the `binary` with the `args`.
+fn run(binary: &str, args: &[&str]) -> Result<Output, String> {
+    let output = Command::new(binary)
+        .args(args)
+        .output()
+        .map_err(|error| format!("failed to execute {}: {}", binary, error))?;
+
+    trace!(
+        "Output from {} command: {}",
+        binary,
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    Ok(output)
+}
+
+/// Summarise the output of a check, ie its last few lines, as it can be lengthy.
+fn summary(output: &Output) -> String {
+    const SUMMARY_LINES: usize = 3;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines = stdout
+        .lines()
+        .chain(stderr.lines())
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    lines[lines.len().saturating_sub(SUMMARY_LINES) ..].join("; ")
+}
+
+/// Describe the failure of the `binary` from its `output`.
+fn failed(binary: &str, output: &Output) -> String {
+    format!(
+        "{} command failed ({}): {}",
+        binary,
+        output.status,
+        String::from_utf8_lossy(&output.stderr).trim()
+    )
+}
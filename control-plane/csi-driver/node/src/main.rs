@@ -19,6 +19,7 @@ use k8s_openapi::api::core::v1::Node as K8sNode;
 use kube::{Api, Client, Resource};
 use nodeplugin_grpc::IoEngineNodePluginGrpcServer;
 use std::{
+    collections::HashMap,
     env,
     pin::Pin,
     task::{Context, Poll},
@@ -41,6 +42,7 @@ pub mod csi {
 }
 
 mod block_vol;
+mod cleanup;
 /// Configuration Parameters
 pub(crate) mod config;
 mod dev;
@@ -48,6 +50,7 @@ mod error;
 mod filesystem_vol;
 mod findmnt;
 mod format;
+mod fsck;
 mod identity;
 mod match_dev;
 mod mount;
@@ -109,6 +112,52 @@ impl AsyncWrite for UnixStream {
 
 const GRPC_PORT: u16 = 10199;
 
+// Get the topology segments of the node, other than its hostname, from the values of the given
+// labels of the Kubernetes node. Labels which the node doesn't have are not reported.
+pub async fn get_topology_segments(
+    node_name: &str,
+    keys: Vec<&str>,
+) -> Result<HashMap<String, String>, String> {
+    if keys.is_empty() {
+        return Ok(HashMap::new());
+    }
+    if env::var("KUBERNETES_SERVICE_HOST").is_err() {
+        info!(
+            "No Kubernetes API server available, not reporting topology keys: {:?}",
+            keys
+        );
+        return Ok(HashMap::new());
+    }
+
+    let k8s = Client::try_default()
+        .await
+        .map_err(|error| format!("Failed to initialize k8s API client: {}", error))?;
+    let nodes: Api<K8sNode> = Api::all(k8s);
+
+    let node = nodes.get(node_name).await.map_err(|error| {
+        format!(
+            "Node '{}' not found in Kubernetes cluster: {}",
+            node_name, error
+        )
+    })?;
+
+    let segments = keys
+        .into_iter()
+        .filter_map(|key| {
+            node.meta()
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get(key))
+                .map(|value| (key.to_string(), value.to_string()))
+        })
+        .collect::<HashMap<_, _>>();
+    info!(
+        "Retrieved topology segments of node '{}': {:?}",
+        node_name, segments
+    );
+    Ok(segments)
+}
+
 // Get node name from Kubernetes API server. In case no Kubernetes API server is available,
 // keep the hostname as it is.
 pub async fn get_nodename(hostname: &str) -> String {
@@ -209,6 +258,52 @@ async fn main() -> Result<(), String> {
                 .required(false)
                 .help("Sets the nvme-nr-io-queues parameter when connecting to a volume target"),
         )
+        .arg(
+            Arg::with_name("rest-endpoint")
+                .long("rest-endpoint")
+                .value_name("URL")
+                .takes_value(true)
+                .required(false)
+                .help("REST API endpoint of the control plane, used to cleanup orphaned devices and mounts"),
+        )
+        .arg(
+            Arg::with_name("cleanup-orphans")
+                .long("cleanup-orphans")
+                .requires("rest-endpoint")
+                .help("Cleanup the devices and mounts of volumes which are deleted or no longer staged on this node, on startup"),
+        )
+        .arg(
+            Arg::with_name("cleanup-dry-run")
+                .long("cleanup-dry-run")
+                .requires("cleanup-orphans")
+                .help("Only report the orphaned devices and mounts, without cleaning them up"),
+        )
+        .arg(
+            Arg::with_name("kubelet-dir")
+                .long("kubelet-dir")
+                .value_name("PATH")
+                .takes_value(true)
+                .required(false)
+                .help("Kubelet root directory, under which the volumes are mounted (default /var/lib/kubelet)"),
+        )
+        .arg(
+            Arg::with_name("topology-key")
+                .long("topology-key")
+                .value_name("LABEL")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .required(false)
+                .help("Node label reported as a topology segment in addition to the hostname, eg topology.kubernetes.io/zone"),
+        )
+        .arg(
+            Arg::with_name("rest-timeout")
+                .long("rest-timeout")
+                .value_name("TIMEOUT")
+                .takes_value(true)
+                .required(false)
+                .help("Timeout of the REST API requests (default 5s)"),
+        )
         .get_matches();
 
     utils::print_package_info!();
@@ -260,9 +355,36 @@ async fn main() -> Result<(), String> {
 
     *config::config().nvme_as_mut() = TryFrom::try_from(&matches)?;
     let node_name = get_nodename(matches.value_of("node-name").unwrap()).await;
+    let topology_segments = get_topology_segments(
+        matches.value_of("node-name").unwrap(),
+        matches
+            .values_of("topology-key")
+            .map(|keys| keys.collect())
+            .unwrap_or_default(),
+    )
+    .await?;
+
+    // Cleanup before serving any requests, so that the cleanup doesn't race with the staging of
+    // newly published volumes.
+    if matches.is_present("cleanup-orphans") {
+        let rest_timeout = matches.value_of("rest-timeout").unwrap_or("5s");
+        let rest_timeout = humantime::parse_duration(rest_timeout)
+            .map_err(|error| format!("Invalid rest-timeout {}: {}", rest_timeout, error))?;
+        let cleanup = cleanup::OrphanCleanup::new(
+            matches.value_of("rest-endpoint").unwrap(),
+            rest_timeout,
+            matches
+                .value_of("kubelet-dir")
+                .unwrap_or("/var/lib/kubelet"),
+            matches.is_present("cleanup-dry-run"),
+        );
+        if let Err(error) = cleanup.run().await {
+            error!("Failed to cleanup orphaned devices and mounts: {}", error);
+        }
+    }
 
     let _ = tokio::join!(
-        CsiServer::run(csi_socket, &node_name),
+        CsiServer::run(csi_socket, &node_name, topology_segments),
         IoEngineNodePluginGrpcServer::run(sock_addr.parse().expect("Invalid gRPC endpoint")),
     );
 
@@ -272,7 +394,11 @@ async fn main() -> Result<(), String> {
 struct CsiServer {}
 
 impl CsiServer {
-    pub async fn run(csi_socket: &str, node_name: &str) -> Result<(), ()> {
+    pub async fn run(
+        csi_socket: &str,
+        node_name: &str,
+        topology_segments: HashMap<String, String>,
+    ) -> Result<(), ()> {
         let incoming = {
             let uds = UnixListener::bind(csi_socket).unwrap();
             info!("CSI plugin bound to {}", csi_socket);
@@ -288,6 +414,7 @@ impl CsiServer {
         if let Err(e) = Server::builder()
             .add_service(NodeServer::new(Node {
                 node_name: node_name.into(),
+                topology_segments,
                 filesystems: probe_filesystems(),
             }))
             .add_service(IdentityServer::new(Identity {}))
@@ -71,7 +71,11 @@ pub(super) fn subset(first: &[String], second: &[String]) -> bool {
 
 /// Return supported filesystems.
 pub fn probe_filesystems() -> Vec<String> {
-    vec![String::from("xfs"), String::from("ext4")]
+    vec![
+        String::from("xfs"),
+        String::from("ext4"),
+        String::from("btrfs"),
+    ]
 }
 
 // Utility function to transform a vector of options
@@ -10,18 +10,22 @@ macro_rules! failure {
 use uuid::Uuid;
 
 use crate::{
-    block_vol::{publish_block_volume, unpublish_block_volume},
+    block_vol::{block_volume_stats, publish_block_volume, unpublish_block_volume},
     csi::{
         volume_capability::{access_mode::Mode, AccessType},
         *,
     },
     dev::Device,
-    filesystem_vol::{publish_fs_volume, stage_fs_volume, unpublish_fs_volume, unstage_fs_volume},
+    filesystem_vol::{
+        fs_volume_stats, publish_fs_volume, stage_fs_volume, unpublish_fs_volume, unstage_fs_volume,
+    },
 };
 
 #[derive(Clone, Debug)]
 pub struct Node {
     pub node_name: String,
+    /// Topology segments of the node other than its hostname, eg its zone.
+    pub topology_segments: HashMap<String, String>,
     pub filesystems: Vec<String>,
 }
 
@@ -118,7 +122,7 @@ impl node_server::Node for Node {
         _request: Request<NodeGetInfoRequest>,
     ) -> Result<Response<NodeGetInfoResponse>, Status> {
         let node_id = format!("csi-node://{}", &self.node_name);
-        let mut segments = HashMap::new();
+        let mut segments = self.topology_segments.clone();
         segments.insert("kubernetes.io/hostname".to_owned(), self.node_name.clone());
 
         debug!("NodeGetInfo request: ID={}", node_id);
@@ -134,7 +138,11 @@ impl node_server::Node for Node {
         &self,
         _request: Request<NodeGetCapabilitiesRequest>,
     ) -> Result<Response<NodeGetCapabilitiesResponse>, Status> {
-        let caps = vec![node_service_capability::rpc::Type::StageUnstageVolume];
+        let caps = vec![
+            node_service_capability::rpc::Type::StageUnstageVolume,
+            node_service_capability::rpc::Type::GetVolumeStats,
+            node_service_capability::rpc::Type::VolumeCondition,
+        ];
 
         debug!("NodeGetCapabilities request: {:?}", caps);
 
@@ -300,30 +308,85 @@ impl node_server::Node for Node {
         Ok(Response::new(NodeUnpublishVolumeResponse {}))
     }
 
-    /// Get volume stats method is currently not implemented,
-    /// although it's simple to do.
-    ///
-    /// TODO: Just read the data about capacity/used space
-    /// inodes/bytes from the system using the mountpoint.
+    /// Get the byte and inode usage of a filesystem volume, or the size of a
+    /// raw block volume, along with the condition of the volume, which is
+    /// abnormal if its device is missing or if its filesystem has become
+    /// read-only as a result of errors.
     async fn node_get_volume_stats(
         &self,
         request: Request<NodeGetVolumeStatsRequest>,
     ) -> Result<Response<NodeGetVolumeStatsResponse>, Status> {
         let msg = request.into_inner();
+
         trace!("node_get_volume_stats {:?}", msg);
 
-        /*
-        Ok(Response::new(NodeGetVolumeStatsResponse {
-            usage: vec![VolumeUsage {
-                total: 0 as i64,
-                unit: volume_usage::Unit::Bytes as i32,
-                available: 0,
-                used: 0,
-            }],
-        }))
-        */
-        error!("Unimplemented {:?}", msg);
-        Err(Status::new(Code::Unimplemented, "Method not implemented"))
+        if msg.volume_id.is_empty() {
+            return Err(failure!(
+                Code::InvalidArgument,
+                "Failed to get volume stats: missing volume id"
+            ));
+        }
+
+        if msg.volume_path.is_empty() {
+            return Err(failure!(
+                Code::InvalidArgument,
+                "Failed to get stats for volume {}: missing volume path",
+                &msg.volume_id
+            ));
+        }
+
+        let uuid = Uuid::parse_str(&msg.volume_id).map_err(|error| {
+            failure!(
+                Code::NotFound,
+                "Failed to get stats for volume {}: not a valid UUID: {}",
+                &msg.volume_id,
+                error
+            )
+        })?;
+
+        let volume_path = Path::new(&msg.volume_path);
+        if !volume_path.exists() {
+            return Err(failure!(
+                Code::NotFound,
+                "Failed to get stats for volume {}: volume path {} does not exist",
+                &msg.volume_id,
+                &msg.volume_path
+            ));
+        }
+
+        // don't attempt to read the usage of a volume whose device is gone,
+        // as that may well hang
+        let device = Device::lookup(&uuid).await.map_err(|error| {
+            failure!(
+                Code::Internal,
+                "Failed to get stats for volume {}: error locating device: {}",
+                &msg.volume_id,
+                error
+            )
+        })?;
+        if device.is_none() {
+            warn!("Volume {}: device is missing", &msg.volume_id);
+            return Ok(Response::new(NodeGetVolumeStatsResponse {
+                usage: vec![],
+                volume_condition: Some(VolumeCondition {
+                    abnormal: true,
+                    message: String::from("the device of the volume is missing"),
+                }),
+            }));
+        }
+
+        let mut response = if volume_path.is_dir() {
+            fs_volume_stats(&msg)?
+        } else {
+            block_volume_stats(&msg)?
+        };
+        if response.volume_condition.is_none() {
+            response.volume_condition = Some(VolumeCondition {
+                abnormal: false,
+                message: String::from("volume is healthy"),
+            });
+        }
+        Ok(Response::new(response))
     }
 
     async fn node_expand_volume(
@@ -0,0 +1,27 @@
+//! The storage class parameters which are validated by the CSI controller and which are then
+//! passed on to the CSI node plugin through the volume and publish contexts.
+
+/// Number of nvme IO queues.
+pub const NVME_NR_IO_QUEUES: &str = "nvmeNrIoQueues";
+/// Nvme controller loss timeout, in seconds.
+pub const NVME_CTRL_LOSS_TMO: &str = "nvmeCtrlLossTmo";
+/// Delay between the nvme reconnect attempts, in seconds.
+pub const NVME_RECONNECT_DELAY: &str = "nvmeReconnectDelay";
+
+/// Nvme connection parameters, which are passed on to the node plugin when publishing.
+pub const NVME_OPTIONS: [&str; 3] = [NVME_NR_IO_QUEUES, NVME_CTRL_LOSS_TMO, NVME_RECONNECT_DELAY];
+
+/// Iscsi session replacement timeout, in seconds, ie: how long to wait for a failed session to be
+/// re-established before failing the IO.
+pub const ISCSI_REPLACEMENT_TIMEOUT: &str = "iscsiReplacementTimeout";
+/// Interval between the iscsi NOP-Out pings, in seconds.
+pub const ISCSI_NOOP_OUT_INTERVAL: &str = "iscsiNoopOutInterval";
+/// Iscsi NOP-Out ping timeout, in seconds.
+pub const ISCSI_NOOP_OUT_TIMEOUT: &str = "iscsiNoopOutTimeout";
+
+/// Iscsi session parameters, which are passed on to the node plugin when publishing.
+pub const ISCSI_OPTIONS: [&str; 3] = [
+    ISCSI_REPLACEMENT_TIMEOUT,
+    ISCSI_NOOP_OUT_INTERVAL,
+    ISCSI_NOOP_OUT_TIMEOUT,
+];
@@ -0,0 +1,4 @@
+//! Definitions which are shared between the CSI controller and the CSI node plugin.
+
+/// The volume parameters which are passed from the storage class to the node plugin.
+pub mod context;
@@ -315,6 +315,7 @@ impl TryFrom<nexus::NexusSpec> for NexusSpec {
             operation: value.operation.map(|op| NexusOperationState {
                 operation: NexusOperation::Create,
                 result: op.result,
+                started: op.started,
             }),
         })
     }
@@ -340,6 +341,7 @@ impl From<NexusSpec> for nexus::NexusSpec {
             owner: value.owner.map(|volumeid| volumeid.to_string()),
             operation: value.operation.map(|operation| common::SpecOperation {
                 result: operation.result,
+                started: operation.started,
             }),
         }
     }
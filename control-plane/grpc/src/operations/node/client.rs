@@ -3,17 +3,17 @@ use crate::{
     common::NodeFilter,
     context::{Client, Context, TracedChannel},
     node::{
-        get_nodes_reply, get_nodes_request, node_grpc_client::NodeGrpcClient, GetNodesRequest,
-        ProbeRequest,
+        cordon_node_reply, get_nodes_reply, get_nodes_request, node_grpc_client::NodeGrpcClient,
+        uncordon_node_reply, GetNodesRequest, ProbeRequest,
     },
-    operations::node::traits::{GetBlockDeviceInfo, NodeOperations},
+    operations::node::traits::{CordonNodeInfo, GetBlockDeviceInfo, NodeOperations},
 };
 use common_lib::{
     mbus_api::{
         v0::{BlockDevices, Nodes},
         ReplyError, ResourceKind, TimeoutOptions,
     },
-    types::v0::message_bus::{Filter, MessageIdVs},
+    types::v0::message_bus::{Filter, MessageIdVs, Node},
 };
 use std::{convert::TryFrom, ops::Deref};
 use tonic::transport::Uri;
@@ -84,4 +84,37 @@ impl NodeOperations for NodeClient {
             None => Err(ReplyError::invalid_response(ResourceKind::Block)),
         }
     }
+
+    #[tracing::instrument(name = "NodeClient::cordon", level = "debug", skip(self), err)]
+    async fn cordon(
+        &self,
+        request: &dyn CordonNodeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Node, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::CordonNode);
+        let response = self.client().cordon_node(req).await?.into_inner();
+        match response.reply {
+            Some(cordon_node_reply) => match cordon_node_reply {
+                cordon_node_reply::Reply::Node(node) => Ok(Node::try_from(node)?),
+                cordon_node_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Node)),
+        }
+    }
+    #[tracing::instrument(name = "NodeClient::uncordon", level = "debug", skip(self), err)]
+    async fn uncordon(
+        &self,
+        request: &dyn CordonNodeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Node, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::UncordonNode);
+        let response = self.client().uncordon_node(req).await?.into_inner();
+        match response.reply {
+            Some(uncordon_node_reply) => match uncordon_node_reply {
+                uncordon_node_reply::Reply::Node(node) => Ok(Node::try_from(node)?),
+                uncordon_node_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Node)),
+        }
+    }
 }
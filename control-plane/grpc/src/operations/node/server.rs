@@ -2,9 +2,10 @@ use crate::{
     blockdevice::{get_block_devices_reply, GetBlockDevicesReply, GetBlockDevicesRequest},
     node,
     node::{
-        get_nodes_reply,
+        cordon_node_reply, get_nodes_reply,
         node_grpc_server::{NodeGrpc, NodeGrpcServer},
-        GetNodesReply, GetNodesRequest, ProbeRequest, ProbeResponse,
+        uncordon_node_reply, CordonNodeReply, CordonNodeRequest, GetNodesReply, GetNodesRequest,
+        ProbeRequest, ProbeResponse, UncordonNodeReply, UncordonNodeRequest,
     },
     operations::node::traits::NodeOperations,
 };
@@ -71,4 +72,32 @@ impl NodeGrpc for NodeServer {
             })),
         }
     }
+    async fn cordon_node(
+        &self,
+        request: Request<CordonNodeRequest>,
+    ) -> Result<tonic::Response<CordonNodeReply>, tonic::Status> {
+        let req: CordonNodeRequest = request.into_inner();
+        match self.service.cordon(&req, None).await {
+            Ok(node) => Ok(Response::new(CordonNodeReply {
+                reply: Some(cordon_node_reply::Reply::Node(node.into())),
+            })),
+            Err(err) => Ok(Response::new(CordonNodeReply {
+                reply: Some(cordon_node_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn uncordon_node(
+        &self,
+        request: Request<UncordonNodeRequest>,
+    ) -> Result<tonic::Response<UncordonNodeReply>, tonic::Status> {
+        let req: UncordonNodeRequest = request.into_inner();
+        match self.service.uncordon(&req, None).await {
+            Ok(node) => Ok(Response::new(UncordonNodeReply {
+                reply: Some(uncordon_node_reply::Reply::Node(node.into())),
+            })),
+            Err(err) => Ok(Response::new(UncordonNodeReply {
+                reply: Some(uncordon_node_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
@@ -1,6 +1,9 @@
 use crate::{
-    blockdevice, blockdevice::GetBlockDevicesRequest, context::Context, node,
-    node::get_nodes_request,
+    blockdevice,
+    blockdevice::GetBlockDevicesRequest,
+    context::Context,
+    node,
+    node::{get_nodes_request, CordonNodeRequest, UncordonNodeRequest},
 };
 use common_lib::{
     mbus_api::{
@@ -9,8 +12,8 @@ use common_lib::{
     },
     types::v0::{
         message_bus::{
-            BlockDevice, Filesystem, Filter, GetBlockDevices, Node, NodeId, NodeState, NodeStatus,
-            Partition,
+            BlockDevice, CordonNode, Filesystem, Filter, GetBlockDevices, Node, NodeId, NodeState,
+            NodeStatus, Partition, UncordonNode,
         },
         store::node::NodeSpec,
     },
@@ -30,6 +33,18 @@ pub trait NodeOperations: Send + Sync {
         get_blockdevice: &dyn GetBlockDeviceInfo,
         ctx: Option<Context>,
     ) -> Result<BlockDevices, ReplyError>;
+    /// Cordon a node, so it is not used for new replicas and volume targets
+    async fn cordon(
+        &self,
+        node: &dyn CordonNodeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Node, ReplyError>;
+    /// Uncordon a node, so it can be used for new replicas and volume targets again
+    async fn uncordon(
+        &self,
+        node: &dyn CordonNodeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Node, ReplyError>;
 }
 
 impl TryFrom<node::Node> for Node {
@@ -41,6 +56,7 @@ impl TryFrom<node::Node> for Node {
                 spec.endpoint,
                 spec.labels.unwrap_or_default().value,
             )
+            .with_cordoned(spec.cordoned)
         });
         let node_state = match node_grpc_type.state {
             Some(state) => {
@@ -74,6 +90,7 @@ impl From<Node> for node::Node {
             labels: Some(crate::common::StringMapValue {
                 value: spec.labels().clone(),
             }),
+            cordoned: spec.cordoned(),
         });
         let node_state = match node.state() {
             None => None,
@@ -147,6 +164,65 @@ impl From<NodeStatus> for node::NodeStatus {
     }
 }
 
+/// CordonNodeInfo trait for the node cordon and uncordon operations to be implemented by
+/// entities which want to avail these operations
+pub trait CordonNodeInfo: Send + Sync + std::fmt::Debug {
+    /// id of the IoEngine instance
+    fn node_id(&self) -> NodeId;
+}
+
+impl CordonNodeInfo for CordonNode {
+    fn node_id(&self) -> NodeId {
+        self.id.clone()
+    }
+}
+
+impl CordonNodeInfo for UncordonNode {
+    fn node_id(&self) -> NodeId {
+        self.id.clone()
+    }
+}
+
+impl CordonNodeInfo for CordonNodeRequest {
+    fn node_id(&self) -> NodeId {
+        self.node_id.clone().into()
+    }
+}
+
+impl CordonNodeInfo for UncordonNodeRequest {
+    fn node_id(&self) -> NodeId {
+        self.node_id.clone().into()
+    }
+}
+
+impl From<&dyn CordonNodeInfo> for CordonNodeRequest {
+    fn from(data: &dyn CordonNodeInfo) -> Self {
+        Self {
+            node_id: data.node_id().to_string(),
+        }
+    }
+}
+
+impl From<&dyn CordonNodeInfo> for UncordonNodeRequest {
+    fn from(data: &dyn CordonNodeInfo) -> Self {
+        Self {
+            node_id: data.node_id().to_string(),
+        }
+    }
+}
+
+impl From<&dyn CordonNodeInfo> for CordonNode {
+    fn from(data: &dyn CordonNodeInfo) -> Self {
+        Self { id: data.node_id() }
+    }
+}
+
+impl From<&dyn CordonNodeInfo> for UncordonNode {
+    fn from(data: &dyn CordonNodeInfo) -> Self {
+        Self { id: data.node_id() }
+    }
+}
+
 /// GetBlockDeviceInfo trait for the getblockdevices
 /// operation
 pub trait GetBlockDeviceInfo: Send + Sync {
@@ -1,10 +1,10 @@
 use crate::{
     common::{NodeFilter, NodePoolFilter, PoolFilter},
     context::{Client, Context, TracedChannel},
-    operations::pool::traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
+    operations::pool::traits::{CreatePoolInfo, DestroyPoolInfo, LabelPoolInfo, PoolOperations},
     pool::{
-        create_pool_reply, get_pools_reply, get_pools_request, pool_grpc_client::PoolGrpcClient,
-        GetPoolsRequest,
+        create_pool_reply, get_pools_reply, get_pools_request, label_pool_reply,
+        pool_grpc_client::PoolGrpcClient, GetPoolsRequest,
     },
 };
 use common_lib::{
@@ -100,4 +100,21 @@ impl PoolOperations for PoolClient {
             None => Err(ReplyError::invalid_response(ResourceKind::Pool)),
         }
     }
+
+    #[tracing::instrument(name = "PoolClient::label", level = "debug", skip(self), err)]
+    async fn label(
+        &self,
+        request: &dyn LabelPoolInfo,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::LabelPool);
+        let response = self.client().label_pool(req).await?.into_inner();
+        match response.reply {
+            Some(label_pool_reply) => match label_pool_reply {
+                label_pool_reply::Reply::Pool(pool) => Ok(Pool::try_from(pool)?),
+                label_pool_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Pool)),
+        }
+    }
 }
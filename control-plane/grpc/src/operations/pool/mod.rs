@@ -100,7 +100,7 @@ mod test {
             context::Context,
             operations::pool::{
                 test::TimeoutTester,
-                traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
+                traits::{CreatePoolInfo, DestroyPoolInfo, LabelPoolInfo, PoolOperations},
             },
         };
         use common_lib::{
@@ -136,6 +136,13 @@ mod test {
                 tester.complete();
                 Ok(Pools(vec![]))
             }
+            async fn label(
+                &self,
+                _pool: &dyn LabelPoolInfo,
+                _ctx: Option<Context>,
+            ) -> Result<Pool, ReplyError> {
+                todo!()
+            }
         }
     }
 }
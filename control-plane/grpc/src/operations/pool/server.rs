@@ -2,10 +2,10 @@ use crate::{
     operations::pool::traits::PoolOperations,
     pool,
     pool::{
-        create_pool_reply, get_pools_reply,
+        create_pool_reply, get_pools_reply, label_pool_reply,
         pool_grpc_server::{PoolGrpc, PoolGrpcServer},
         CreatePoolReply, CreatePoolRequest, DestroyPoolReply, DestroyPoolRequest, GetPoolsReply,
-        GetPoolsRequest,
+        GetPoolsRequest, LabelPoolReply, LabelPoolRequest,
     },
 };
 use std::sync::Arc;
@@ -74,4 +74,19 @@ impl PoolGrpc for PoolServer {
             })),
         }
     }
+
+    async fn label_pool(
+        &self,
+        request: Request<LabelPoolRequest>,
+    ) -> Result<tonic::Response<LabelPoolReply>, tonic::Status> {
+        let req: LabelPoolRequest = request.into_inner();
+        match self.service.label(&req, None).await {
+            Ok(pool) => Ok(Response::new(LabelPoolReply {
+                reply: Some(label_pool_reply::Reply::Pool(pool.into())),
+            })),
+            Err(err) => Ok(Response::new(LabelPoolReply {
+                reply: Some(label_pool_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
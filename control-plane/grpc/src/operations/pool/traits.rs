@@ -2,14 +2,15 @@ use crate::{
     common,
     context::Context,
     pool,
-    pool::{get_pools_request, CreatePoolRequest, DestroyPoolRequest},
+    pool::{get_pools_request, CreatePoolRequest, DestroyPoolRequest, LabelPoolRequest},
 };
 use common_lib::{
     mbus_api::{v0::Pools, ReplyError, ResourceKind},
     types::v0::{
         message_bus,
         message_bus::{
-            CreatePool, DestroyPool, Filter, NodeId, Pool, PoolDeviceUri, PoolId, PoolState,
+            CreatePool, DestroyPool, Filter, LabelPool, NodeId, Pool, PoolDeviceUri, PoolId,
+            PoolState,
         },
         store::pool::{PoolLabel, PoolSpec, PoolSpecStatus},
     },
@@ -33,6 +34,12 @@ pub trait PoolOperations: Send + Sync {
     ) -> Result<(), ReplyError>;
     /// Get pools based on the filters
     async fn get(&self, filter: Filter, ctx: Option<Context>) -> Result<Pools, ReplyError>;
+    /// Set or remove the labels of a pool
+    async fn label(
+        &self,
+        pool: &dyn LabelPoolInfo,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError>;
 }
 
 impl TryFrom<pool::PoolDefinition> for PoolSpec {
@@ -323,6 +330,82 @@ impl From<&dyn DestroyPoolInfo> for DestroyPool {
     }
 }
 
+/// LabelPoolInfo trait for the pool labelling to be implemented by entities which want to avail
+/// this operation
+pub trait LabelPoolInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the pool
+    fn pool_id(&self) -> PoolId;
+    /// Labels to be set on the pool
+    fn labels(&self) -> PoolLabel;
+    /// Keys of the labels to be removed from the pool
+    fn remove_labels(&self) -> Vec<String>;
+    /// Overwrite the value of the labels which are already set on the pool
+    fn overwrite(&self) -> bool;
+}
+
+impl LabelPoolInfo for LabelPool {
+    fn pool_id(&self) -> PoolId {
+        self.id.clone()
+    }
+
+    fn labels(&self) -> PoolLabel {
+        self.labels.clone()
+    }
+
+    fn remove_labels(&self) -> Vec<String> {
+        self.remove_labels.clone()
+    }
+
+    fn overwrite(&self) -> bool {
+        self.overwrite
+    }
+}
+
+impl LabelPoolInfo for LabelPoolRequest {
+    fn pool_id(&self) -> PoolId {
+        self.pool_id.clone().into()
+    }
+
+    fn labels(&self) -> PoolLabel {
+        match self.labels.clone() {
+            None => PoolLabel::new(),
+            Some(labels) => labels.value,
+        }
+    }
+
+    fn remove_labels(&self) -> Vec<String> {
+        self.remove_labels.clone()
+    }
+
+    fn overwrite(&self) -> bool {
+        self.overwrite
+    }
+}
+
+impl From<&dyn LabelPoolInfo> for LabelPoolRequest {
+    fn from(data: &dyn LabelPoolInfo) -> Self {
+        Self {
+            pool_id: data.pool_id().to_string(),
+            labels: Some(crate::common::StringMapValue {
+                value: data.labels(),
+            }),
+            remove_labels: data.remove_labels(),
+            overwrite: data.overwrite(),
+        }
+    }
+}
+
+impl From<&dyn LabelPoolInfo> for LabelPool {
+    fn from(data: &dyn LabelPoolInfo) -> Self {
+        Self {
+            id: data.pool_id(),
+            labels: data.labels(),
+            remove_labels: data.remove_labels(),
+            overwrite: data.overwrite(),
+        }
+    }
+}
+
 impl From<pool::PoolStatus> for message_bus::PoolStatus {
     fn from(src: pool::PoolStatus) -> Self {
         match src {
@@ -1,11 +1,15 @@
 use crate::{
     context::{Client, Context, TracedChannel},
-    operations::registry::traits::{GetSpecsInfo, RegistryOperations},
-    registry::{get_specs_reply, registry_grpc_client::RegistryGrpcClient},
+    operations::registry::traits::{GetSpecsInfo, RegistryOperations, SetReconcilerPausedInfo},
+    registry::{
+        get_core_health_reply, get_reconcilers_reply, get_specs_reply,
+        registry_grpc_client::RegistryGrpcClient, set_reconciler_paused_reply,
+        GetCoreHealthRequest, GetReconcilersRequest, PollReconcilersRequest,
+    },
 };
 use common_lib::{
     mbus_api::{ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::message_bus::{MessageIdVs, Specs},
+    types::v0::message_bus::{CoreHealth, MessageIdVs, Reconciler, Reconcilers, Specs},
 };
 use std::{convert::TryFrom, ops::Deref};
 use tonic::transport::Uri;
@@ -48,4 +52,57 @@ impl RegistryOperations for RegistryClient {
             None => Err(ReplyError::invalid_response(ResourceKind::Spec)),
         }
     }
+
+    async fn get_core_health(&self, ctx: Option<Context>) -> Result<CoreHealth, ReplyError> {
+        let req = self.request(GetCoreHealthRequest {}, ctx, MessageIdVs::GetCoreHealth);
+        let response = self.client().get_core_health(req).await?.into_inner();
+        match response.reply {
+            Some(get_core_health_reply) => match get_core_health_reply {
+                get_core_health_reply::Reply::Health(health) => Ok(CoreHealth::try_from(health)?),
+                get_core_health_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Unknown)),
+        }
+    }
+
+    async fn get_reconcilers(&self, ctx: Option<Context>) -> Result<Reconcilers, ReplyError> {
+        let req = self.request(GetReconcilersRequest {}, ctx, MessageIdVs::GetReconcilers);
+        let response = self.client().get_reconcilers(req).await?.into_inner();
+        match response.reply {
+            Some(get_reconcilers_reply) => match get_reconcilers_reply {
+                get_reconcilers_reply::Reply::Reconcilers(reconcilers) => {
+                    Ok(Reconcilers::try_from(reconcilers)?)
+                }
+                get_reconcilers_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Unknown)),
+        }
+    }
+
+    async fn set_reconciler_paused(
+        &self,
+        request: &dyn SetReconcilerPausedInfo,
+        ctx: Option<Context>,
+    ) -> Result<Reconciler, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::SetReconcilerPaused);
+        let response = self.client().set_reconciler_paused(req).await?.into_inner();
+        match response.reply {
+            Some(set_reconciler_paused_reply) => match set_reconciler_paused_reply {
+                set_reconciler_paused_reply::Reply::Reconciler(reconciler) => {
+                    Ok(Reconciler::try_from(reconciler)?)
+                }
+                set_reconciler_paused_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Unknown)),
+        }
+    }
+
+    async fn poll_reconcilers(&self, ctx: Option<Context>) -> Result<(), ReplyError> {
+        let req = self.request(PollReconcilersRequest {}, ctx, MessageIdVs::PollReconcilers);
+        let response = self.client().poll_reconcilers(req).await?.into_inner();
+        match response.error {
+            None => Ok(()),
+            Some(err) => Err(err.into()),
+        }
+    }
 }
@@ -1,9 +1,11 @@
 use crate::{
     operations::registry::traits::RegistryOperations,
     registry::{
-        get_specs_reply,
+        get_core_health_reply, get_reconcilers_reply, get_specs_reply,
         registry_grpc_server::{RegistryGrpc, RegistryGrpcServer},
-        GetSpecsReply, GetSpecsRequest,
+        set_reconciler_paused_reply, GetCoreHealthReply, GetCoreHealthRequest, GetReconcilersReply,
+        GetReconcilersRequest, GetSpecsReply, GetSpecsRequest, PollReconcilersReply,
+        PollReconcilersRequest, SetReconcilerPausedReply, SetReconcilerPausedRequest,
     },
 };
 use std::sync::Arc;
@@ -43,4 +45,63 @@ impl RegistryGrpc for RegistryServer {
             })),
         }
     }
+
+    async fn get_core_health(
+        &self,
+        _request: tonic::Request<GetCoreHealthRequest>,
+    ) -> Result<tonic::Response<GetCoreHealthReply>, tonic::Status> {
+        match self.service.get_core_health(None).await {
+            Ok(health) => Ok(Response::new(GetCoreHealthReply {
+                reply: Some(get_core_health_reply::Reply::Health(health.into())),
+            })),
+            Err(err) => Ok(Response::new(GetCoreHealthReply {
+                reply: Some(get_core_health_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn get_reconcilers(
+        &self,
+        _request: tonic::Request<GetReconcilersRequest>,
+    ) -> Result<tonic::Response<GetReconcilersReply>, tonic::Status> {
+        match self.service.get_reconcilers(None).await {
+            Ok(reconcilers) => Ok(Response::new(GetReconcilersReply {
+                reply: Some(get_reconcilers_reply::Reply::Reconcilers(
+                    reconcilers.into(),
+                )),
+            })),
+            Err(err) => Ok(Response::new(GetReconcilersReply {
+                reply: Some(get_reconcilers_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn set_reconciler_paused(
+        &self,
+        request: tonic::Request<SetReconcilerPausedRequest>,
+    ) -> Result<tonic::Response<SetReconcilerPausedReply>, tonic::Status> {
+        let req: SetReconcilerPausedRequest = request.into_inner();
+        match self.service.set_reconciler_paused(&req, None).await {
+            Ok(reconciler) => Ok(Response::new(SetReconcilerPausedReply {
+                reply: Some(set_reconciler_paused_reply::Reply::Reconciler(
+                    reconciler.into(),
+                )),
+            })),
+            Err(err) => Ok(Response::new(SetReconcilerPausedReply {
+                reply: Some(set_reconciler_paused_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn poll_reconcilers(
+        &self,
+        _request: tonic::Request<PollReconcilersRequest>,
+    ) -> Result<tonic::Response<PollReconcilersReply>, tonic::Status> {
+        match self.service.poll_reconcilers(None).await {
+            Ok(()) => Ok(Response::new(PollReconcilersReply { error: None })),
+            Err(err) => Ok(Response::new(PollReconcilersReply {
+                error: Some(err.into()),
+            })),
+        }
+    }
 }
@@ -1,9 +1,16 @@
-use crate::{context::Context, registry, registry::GetSpecsRequest};
+use crate::{
+    context::Context,
+    registry,
+    registry::{GetSpecsRequest, SetReconcilerPausedRequest},
+};
 use common_lib::{
-    mbus_api::ReplyError,
+    mbus_api::{ReplyError, ResourceKind},
     types::v0::{
         message_bus,
-        message_bus::{GetSpecs, Specs},
+        message_bus::{
+            CoreAgentMode, CoreHealth, CoreLeader, GetSpecs, Reconciler, ReconcilerPollResult,
+            Reconcilers, SetReconcilerPaused, Specs,
+        },
         store::{nexus::NexusSpec, pool::PoolSpec, replica::ReplicaSpec, volume::VolumeSpec},
     },
 };
@@ -18,6 +25,18 @@ pub trait RegistryOperations: Send + Sync {
         get_spec: &dyn GetSpecsInfo,
         ctx: Option<Context>,
     ) -> Result<message_bus::Specs, ReplyError>;
+    /// Get the health of the core agent
+    async fn get_core_health(&self, ctx: Option<Context>) -> Result<CoreHealth, ReplyError>;
+    /// Get the status of the core agent reconcilers
+    async fn get_reconcilers(&self, ctx: Option<Context>) -> Result<Reconcilers, ReplyError>;
+    /// Pause or resume a core agent reconciler
+    async fn set_reconciler_paused(
+        &self,
+        request: &dyn SetReconcilerPausedInfo,
+        ctx: Option<Context>,
+    ) -> Result<Reconciler, ReplyError>;
+    /// Trigger an immediate poll of the core agent reconcilers
+    async fn poll_reconcilers(&self, ctx: Option<Context>) -> Result<(), ReplyError>;
 }
 
 /// GetSpecsInfo trait for the get_specs operation
@@ -39,6 +58,52 @@ impl From<&dyn GetSpecsInfo> for GetSpecs {
     }
 }
 
+/// SetReconcilerPausedInfo trait for the set_reconciler_paused operation
+pub trait SetReconcilerPausedInfo: Send + Sync {
+    /// name of the reconciler
+    fn name(&self) -> String;
+    /// pause the reconciler if true, otherwise resume it
+    fn paused(&self) -> bool;
+}
+
+impl SetReconcilerPausedInfo for SetReconcilerPaused {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn paused(&self) -> bool {
+        self.paused
+    }
+}
+
+impl SetReconcilerPausedInfo for SetReconcilerPausedRequest {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn paused(&self) -> bool {
+        self.paused
+    }
+}
+
+impl From<&dyn SetReconcilerPausedInfo> for SetReconcilerPausedRequest {
+    fn from(data: &dyn SetReconcilerPausedInfo) -> Self {
+        Self {
+            name: data.name(),
+            paused: data.paused(),
+        }
+    }
+}
+
+impl From<&dyn SetReconcilerPausedInfo> for SetReconcilerPaused {
+    fn from(data: &dyn SetReconcilerPausedInfo) -> Self {
+        Self {
+            name: data.name(),
+            paused: data.paused(),
+        }
+    }
+}
+
 impl TryFrom<registry::Specs> for message_bus::Specs {
     type Error = ReplyError;
 
@@ -102,3 +167,167 @@ impl From<message_bus::Specs> for registry::Specs {
         }
     }
 }
+
+impl TryFrom<registry::CoreHealth> for CoreHealth {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::CoreHealth) -> Result<Self, Self::Error> {
+        let mode = match registry::CoreAgentMode::from_i32(value.mode) {
+            Some(mode) => mode.into(),
+            None => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::Unknown,
+                    "core_health.mode",
+                    "".to_string(),
+                ))
+            }
+        };
+        let leader = match value.leader {
+            Some(leader) => leader.into(),
+            None => {
+                return Err(ReplyError::missing_argument(
+                    ResourceKind::Unknown,
+                    "core_health.leader",
+                ))
+            }
+        };
+        Ok(Self {
+            mode,
+            store_lease: value.store_lease,
+            store_online: value.store_online,
+            leader,
+        })
+    }
+}
+
+impl From<CoreHealth> for registry::CoreHealth {
+    fn from(value: CoreHealth) -> Self {
+        let mode: registry::CoreAgentMode = value.mode.into();
+        Self {
+            mode: mode as i32,
+            store_lease: value.store_lease,
+            store_online: value.store_online,
+            leader: Some(value.leader.into()),
+        }
+    }
+}
+
+impl From<registry::CoreLeader> for CoreLeader {
+    fn from(value: registry::CoreLeader) -> Self {
+        Self {
+            instance_name: value.instance_name,
+            lease_id: value.lease_id,
+            takeover: value.takeover,
+        }
+    }
+}
+
+impl From<CoreLeader> for registry::CoreLeader {
+    fn from(value: CoreLeader) -> Self {
+        Self {
+            instance_name: value.instance_name,
+            lease_id: value.lease_id,
+            takeover: value.takeover,
+        }
+    }
+}
+
+impl From<registry::CoreAgentMode> for CoreAgentMode {
+    fn from(src: registry::CoreAgentMode) -> Self {
+        match src {
+            registry::CoreAgentMode::ReadWrite => Self::ReadWrite,
+            registry::CoreAgentMode::ReadOnly => Self::ReadOnly,
+        }
+    }
+}
+
+impl From<CoreAgentMode> for registry::CoreAgentMode {
+    fn from(src: CoreAgentMode) -> Self {
+        match src {
+            CoreAgentMode::ReadWrite => Self::ReadWrite,
+            CoreAgentMode::ReadOnly => Self::ReadOnly,
+        }
+    }
+}
+
+impl TryFrom<registry::Reconciler> for Reconciler {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::Reconciler) -> Result<Self, Self::Error> {
+        let last_result = match value.last_result {
+            Some(result) => match registry::ReconcilerPollResult::from_i32(result) {
+                Some(result) => Some(result.into()),
+                None => {
+                    return Err(ReplyError::invalid_argument(
+                        ResourceKind::Unknown,
+                        "reconciler.last_result",
+                        "".to_string(),
+                    ))
+                }
+            },
+            None => None,
+        };
+        Ok(Self {
+            name: value.name,
+            paused: value.paused,
+            last_run: value.last_run,
+            last_result,
+            errors: value.errors,
+        })
+    }
+}
+
+impl From<Reconciler> for registry::Reconciler {
+    fn from(value: Reconciler) -> Self {
+        Self {
+            name: value.name,
+            paused: value.paused,
+            last_run: value.last_run,
+            last_result: value.last_result.map(|result| {
+                let result: registry::ReconcilerPollResult = result.into();
+                result as i32
+            }),
+            errors: value.errors,
+        }
+    }
+}
+
+impl TryFrom<registry::Reconcilers> for Reconcilers {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::Reconcilers) -> Result<Self, Self::Error> {
+        let mut reconcilers = vec![];
+        for reconciler in value.reconcilers {
+            reconcilers.push(Reconciler::try_from(reconciler)?);
+        }
+        Ok(Self(reconcilers))
+    }
+}
+
+impl From<Reconcilers> for registry::Reconcilers {
+    fn from(value: Reconcilers) -> Self {
+        Self {
+            reconcilers: value.0.into_iter().map(|r| r.into()).collect(),
+        }
+    }
+}
+
+impl From<registry::ReconcilerPollResult> for ReconcilerPollResult {
+    fn from(src: registry::ReconcilerPollResult) -> Self {
+        match src {
+            registry::ReconcilerPollResult::Idle => Self::Idle,
+            registry::ReconcilerPollResult::Busy => Self::Busy,
+            registry::ReconcilerPollResult::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<ReconcilerPollResult> for registry::ReconcilerPollResult {
+    fn from(src: ReconcilerPollResult) -> Self {
+        match src {
+            ReconcilerPollResult::Idle => Self::Idle,
+            ReconcilerPollResult::Busy => Self::Busy,
+            ReconcilerPollResult::Failed => Self::Failed,
+        }
+    }
+}
@@ -853,6 +853,7 @@ impl TryFrom<replica::ReplicaSpec> for ReplicaSpec {
             operation: value.operation.map(|op| ReplicaOperationState {
                 operation: ReplicaOperation::Create,
                 result: op.result,
+                started: op.started,
             }),
         })
     }
@@ -874,6 +875,7 @@ impl From<ReplicaSpec> for replica::ReplicaSpec {
             owners: Some(value.owners.into()),
             operation: value.operation.map(|operation| common::SpecOperation {
                 result: operation.result,
+                started: operation.started,
             }),
         }
     }
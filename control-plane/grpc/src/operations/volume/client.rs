@@ -3,20 +3,22 @@ use crate::{
     context::{Client, Context, TracedChannel},
     operations::{
         volume::traits::{
-            CreateVolumeInfo, DestroyVolumeInfo, PublishVolumeInfo, SetVolumeReplicaInfo,
-            ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo, VolumeOperations,
+            CreateVolumeInfo, CreateVolumeReplicaInfo, DestroyVolumeInfo, GetVolumeCapacityInfo,
+            PublishVolumeInfo, RemoveVolumeReplicaInfo, SetVolumeReplicaInfo, ShareVolumeInfo,
+            UnpublishVolumeInfo, UnshareVolumeInfo, VolumeOperations,
         },
         Pagination,
     },
     volume::{
-        create_volume_reply, get_volumes_reply, get_volumes_request, publish_volume_reply,
+        create_volume_replica_reply, create_volume_reply, get_volume_capacity_reply,
+        get_volumes_reply, get_volumes_request, publish_volume_reply, remove_volume_replica_reply,
         set_volume_replica_reply, share_volume_reply, unpublish_volume_reply,
         volume_grpc_client::VolumeGrpcClient, GetVolumesRequest, ProbeRequest,
     },
 };
 use common_lib::{
     mbus_api::{v0::Volumes, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::message_bus::{Filter, MessageIdVs, Volume},
+    types::v0::message_bus::{Filter, MessageIdVs, Volume, VolumeCapacity},
 };
 use std::{convert::TryFrom, ops::Deref};
 use tonic::transport::Uri;
@@ -189,6 +191,67 @@ impl VolumeOperations for VolumeClient {
         }
     }
 
+    #[tracing::instrument(
+        name = "VolumeClient::create_replica",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn create_replica(
+        &self,
+        request: &dyn CreateVolumeReplicaInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::CreateVolumeReplica);
+        let response = self.client().create_volume_replica(req).await?.into_inner();
+        match response.reply {
+            Some(create_volume_replica_reply) => match create_volume_replica_reply {
+                create_volume_replica_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                create_volume_replica_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::remove_replica",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn remove_replica(
+        &self,
+        request: &dyn RemoveVolumeReplicaInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::RemoveVolumeReplica);
+        let response = self.client().remove_volume_replica(req).await?.into_inner();
+        match response.reply {
+            Some(remove_volume_replica_reply) => match remove_volume_replica_reply {
+                remove_volume_replica_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                remove_volume_replica_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(name = "VolumeClient::get_capacity", level = "debug", skip(self), err)]
+    async fn get_capacity(
+        &self,
+        request: &dyn GetVolumeCapacityInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeCapacity, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetVolumeCapacity);
+        let response = self.client().get_volume_capacity(req).await?.into_inner();
+        match response.reply {
+            Some(get_volume_capacity_reply) => match get_volume_capacity_reply {
+                get_volume_capacity_reply::Reply::Capacity(capacity) => Ok(capacity.into()),
+                get_volume_capacity_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
     #[tracing::instrument(name = "VolumeClient::probe", level = "debug", skip(self))]
     async fn probe(&self, _ctx: Option<Context>) -> Result<bool, ReplyError> {
         match self.client().probe(ProbeRequest {}).await {
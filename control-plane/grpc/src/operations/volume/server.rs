@@ -2,14 +2,17 @@ use crate::{
     misc::traits::ValidateRequestTypes,
     operations::{volume::traits::VolumeOperations, Pagination},
     volume::{
-        create_volume_reply, get_volumes_reply, publish_volume_reply, set_volume_replica_reply,
-        share_volume_reply, unpublish_volume_reply,
+        create_volume_replica_reply, create_volume_reply, get_volume_capacity_reply,
+        get_volumes_reply, publish_volume_reply, remove_volume_replica_reply,
+        set_volume_replica_reply, share_volume_reply, unpublish_volume_reply,
         volume_grpc_server::{VolumeGrpc, VolumeGrpcServer},
-        CreateVolumeReply, CreateVolumeRequest, DestroyVolumeReply, DestroyVolumeRequest,
-        GetVolumesReply, GetVolumesRequest, ProbeRequest, ProbeResponse, PublishVolumeReply,
-        PublishVolumeRequest, SetVolumeReplicaReply, SetVolumeReplicaRequest, ShareVolumeReply,
-        ShareVolumeRequest, UnpublishVolumeReply, UnpublishVolumeRequest, UnshareVolumeReply,
-        UnshareVolumeRequest,
+        CreateVolumeReplicaReply, CreateVolumeReplicaRequest, CreateVolumeReply,
+        CreateVolumeRequest, DestroyVolumeReply, DestroyVolumeRequest, GetVolumeCapacityReply,
+        GetVolumeCapacityRequest, GetVolumesReply, GetVolumesRequest, ProbeRequest, ProbeResponse,
+        PublishVolumeReply, PublishVolumeRequest, RemoveVolumeReplicaReply,
+        RemoveVolumeReplicaRequest, SetVolumeReplicaReply, SetVolumeReplicaRequest,
+        ShareVolumeReply, ShareVolumeRequest, UnpublishVolumeReply, UnpublishVolumeRequest,
+        UnshareVolumeReply, UnshareVolumeRequest,
     },
 };
 use common_lib::types::v0::message_bus::Filter;
@@ -158,6 +161,48 @@ impl VolumeGrpc for VolumeServer {
             })),
         }
     }
+    async fn create_volume_replica(
+        &self,
+        request: tonic::Request<CreateVolumeReplicaRequest>,
+    ) -> Result<tonic::Response<CreateVolumeReplicaReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.create_replica(&req, None).await {
+            Ok(volume) => Ok(Response::new(CreateVolumeReplicaReply {
+                reply: Some(create_volume_replica_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(CreateVolumeReplicaReply {
+                reply: Some(create_volume_replica_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn remove_volume_replica(
+        &self,
+        request: tonic::Request<RemoveVolumeReplicaRequest>,
+    ) -> Result<tonic::Response<RemoveVolumeReplicaReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.remove_replica(&req, None).await {
+            Ok(volume) => Ok(Response::new(RemoveVolumeReplicaReply {
+                reply: Some(remove_volume_replica_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(RemoveVolumeReplicaReply {
+                reply: Some(remove_volume_replica_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_volume_capacity(
+        &self,
+        request: tonic::Request<GetVolumeCapacityRequest>,
+    ) -> Result<tonic::Response<GetVolumeCapacityReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.get_capacity(&req, None).await {
+            Ok(capacity) => Ok(Response::new(GetVolumeCapacityReply {
+                reply: Some(get_volume_capacity_reply::Reply::Capacity(capacity.into())),
+            })),
+            Err(err) => Ok(Response::new(GetVolumeCapacityReply {
+                reply: Some(get_volume_capacity_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
     async fn probe(
         &self,
         _request: tonic::Request<ProbeRequest>,
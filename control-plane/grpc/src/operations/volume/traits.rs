@@ -6,7 +6,8 @@ use crate::{
     operations::Pagination,
     replica, volume,
     volume::{
-        get_volumes_request, CreateVolumeRequest, DestroyVolumeRequest, PublishVolumeRequest,
+        get_volumes_request, CreateVolumeReplicaRequest, CreateVolumeRequest, DestroyVolumeRequest,
+        GetVolumeCapacityRequest, PublishVolumeRequest, RemoveVolumeReplicaRequest,
         SetVolumeReplicaRequest, ShareVolumeRequest, UnpublishVolumeRequest, UnshareVolumeRequest,
     },
 };
@@ -14,13 +15,14 @@ use common_lib::{
     mbus_api::{v0::Volumes, ReplyError, ResourceKind},
     types::v0::{
         message_bus::{
-            CreateVolume, DestroyVolume, ExplicitNodeTopology, Filter, LabelledTopology, Nexus,
-            NexusId, NodeId, NodeTopology, PoolTopology, PublishVolume, ReplicaId, ReplicaStatus,
+            CreateVolume, CreateVolumeReplica, DestroyVolume, ExplicitNodeTopology, Filter,
+            GetVolumeCapacity, LabelledTopology, Nexus, NexusId, NodeId, NodeTopology, PoolId,
+            PoolTopology, PublishVolume, RemoveVolumeReplica, ReplicaId, ReplicaStatus,
             ReplicaTopology, SetVolumeReplica, ShareVolume, Topology, UnpublishVolume,
-            UnshareVolume, Volume, VolumeId, VolumeLabels, VolumePolicy, VolumeShareProtocol,
-            VolumeState,
+            UnshareVolume, Volume, VolumeCapacity, VolumeId, VolumeLabels, VolumePolicy,
+            VolumeShareProtocol, VolumeState,
         },
-        store::volume::{VolumeSpec, VolumeTarget},
+        store::volume::{VolumeOperation, VolumeOperationState, VolumeSpec, VolumeTarget},
     },
 };
 use std::{collections::HashMap, convert::TryFrom};
@@ -77,6 +79,24 @@ pub trait VolumeOperations: Send + Sync {
         req: &dyn SetVolumeReplicaInfo,
         ctx: Option<Context>,
     ) -> Result<Volume, ReplyError>;
+    /// Add a replica to a volume on a given pool
+    async fn create_replica(
+        &self,
+        req: &dyn CreateVolumeReplicaInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
+    /// Remove a given replica from a volume
+    async fn remove_replica(
+        &self,
+        req: &dyn RemoveVolumeReplicaInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
+    /// Get the capacity available for the creation of new volumes
+    async fn get_capacity(
+        &self,
+        req: &dyn GetVolumeCapacityInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeCapacity, ReplyError>;
     /// Liveness probe for volume service
     async fn probe(&self, ctx: Option<Context>) -> Result<bool, ReplyError>;
 }
@@ -96,6 +116,7 @@ impl From<VolumeSpec> for volume::VolumeDefinition {
                 policy: Some(volume_spec.policy.into()),
                 topology: volume_spec.topology.map(|topology| topology.into()),
                 last_nexus_id: volume_spec.last_nexus_id.map(|id| id.to_string()),
+                operation: volume_spec.operation.map(|operation| operation.into()),
             }),
             metadata: Some(volume::Metadata {
                 spec_status: spec_status as i32,
@@ -212,7 +233,10 @@ impl TryFrom<volume::VolumeDefinition> for VolumeSpec {
                 },
                 None => None,
             },
-            operation: None,
+            operation: match volume_spec.operation {
+                Some(operation) => Some(VolumeOperationState::try_from(operation)?),
+                None => None,
+            },
         };
         Ok(volume_spec)
     }
@@ -568,6 +592,95 @@ impl From<VolumeTarget> for volume::VolumeTarget {
     }
 }
 
+impl From<VolumeOperationState> for volume::VolumeSpecOperation {
+    fn from(state: VolumeOperationState) -> Self {
+        let mut operation = volume::VolumeSpecOperation {
+            result: state.result,
+            started: state.started,
+            ..Default::default()
+        };
+        let kind = match state.operation {
+            VolumeOperation::Create => volume::volume_spec_operation::Operation::Create,
+            VolumeOperation::Destroy => volume::volume_spec_operation::Operation::Destroy,
+            VolumeOperation::Share(protocol) => {
+                let protocol: volume::VolumeShareProtocol = protocol.into();
+                operation.protocol = Some(protocol as i32);
+                volume::volume_spec_operation::Operation::Share
+            }
+            VolumeOperation::Unshare => volume::volume_spec_operation::Operation::Unshare,
+            VolumeOperation::SetReplica(replicas) => {
+                operation.replicas = Some(replicas.into());
+                volume::volume_spec_operation::Operation::SetReplica
+            }
+            VolumeOperation::Publish((node, nexus, protocol)) => {
+                operation.target = Some(VolumeTarget::new(node, nexus, protocol).into());
+                volume::volume_spec_operation::Operation::Publish
+            }
+            VolumeOperation::Unpublish => volume::volume_spec_operation::Operation::Unpublish,
+            VolumeOperation::RemoveUnusedReplica(replica) => {
+                operation.replica_id = Some(replica.to_string());
+                volume::volume_spec_operation::Operation::RemoveUnusedReplica
+            }
+        };
+        operation.operation = kind as i32;
+        operation
+    }
+}
+
+impl TryFrom<volume::VolumeSpecOperation> for VolumeOperationState {
+    type Error = ReplyError;
+    fn try_from(state: volume::VolumeSpecOperation) -> Result<Self, Self::Error> {
+        let missing = |argument: &str| ReplyError::missing_argument(ResourceKind::Volume, argument);
+        let operation = match volume::volume_spec_operation::Operation::from_i32(state.operation) {
+            Some(volume::volume_spec_operation::Operation::Create) => VolumeOperation::Create,
+            Some(volume::volume_spec_operation::Operation::Destroy) => VolumeOperation::Destroy,
+            Some(volume::volume_spec_operation::Operation::Share) => {
+                match state
+                    .protocol
+                    .and_then(volume::VolumeShareProtocol::from_i32)
+                {
+                    Some(protocol) => VolumeOperation::Share(protocol.into()),
+                    None => return Err(missing("volume.spec.operation.protocol")),
+                }
+            }
+            Some(volume::volume_spec_operation::Operation::Unshare) => VolumeOperation::Unshare,
+            Some(volume::volume_spec_operation::Operation::SetReplica) => match state.replicas {
+                Some(replicas) => VolumeOperation::SetReplica(replicas as u8),
+                None => return Err(missing("volume.spec.operation.replicas")),
+            },
+            Some(volume::volume_spec_operation::Operation::Publish) => match state.target {
+                Some(target) => {
+                    let target = VolumeTarget::try_from(target)?;
+                    VolumeOperation::Publish((
+                        target.node().clone(),
+                        target.nexus().clone(),
+                        target.protocol().cloned(),
+                    ))
+                }
+                None => return Err(missing("volume.spec.operation.target")),
+            },
+            Some(volume::volume_spec_operation::Operation::Unpublish) => VolumeOperation::Unpublish,
+            Some(volume::volume_spec_operation::Operation::RemoveUnusedReplica) => {
+                VolumeOperation::RemoveUnusedReplica(ReplicaId::try_from(StringValue(
+                    state.replica_id,
+                ))?)
+            }
+            None => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::Volume,
+                    "volume.spec.operation.operation",
+                    "".to_string(),
+                ))
+            }
+        };
+        Ok(Self {
+            operation,
+            result: state.result,
+            started: state.started,
+        })
+    }
+}
+
 impl From<volume::VolumeShareProtocol> for VolumeShareProtocol {
     fn from(src: volume::VolumeShareProtocol) -> Self {
         match src {
@@ -1117,6 +1230,222 @@ impl From<&dyn SetVolumeReplicaInfo> for SetVolumeReplicaRequest {
     }
 }
 
+/// Trait to be implemented for CreateVolumeReplica operation
+pub trait CreateVolumeReplicaInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume
+    fn uuid(&self) -> VolumeId;
+    /// Id of the pool where the replica is created
+    fn pool(&self) -> PoolId;
+}
+
+impl CreateVolumeReplicaInfo for CreateVolumeReplica {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn pool(&self) -> PoolId {
+        self.pool.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to CreateVolumeReplicaRequest type
+#[derive(Debug)]
+pub struct ValidatedCreateVolumeReplicaRequest {
+    inner: CreateVolumeReplicaRequest,
+    uuid: VolumeId,
+}
+
+impl CreateVolumeReplicaInfo for ValidatedCreateVolumeReplicaRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+    fn pool(&self) -> PoolId {
+        self.inner.pool.clone().into()
+    }
+}
+
+impl ValidateRequestTypes for CreateVolumeReplicaRequest {
+    type Validated = ValidatedCreateVolumeReplicaRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedCreateVolumeReplicaRequest {
+            uuid: VolumeId::try_from(StringValue(self.uuid.clone()))?,
+            inner: self,
+        })
+    }
+}
+
+impl From<&dyn CreateVolumeReplicaInfo> for CreateVolumeReplica {
+    fn from(data: &dyn CreateVolumeReplicaInfo) -> Self {
+        Self {
+            uuid: data.uuid(),
+            pool: data.pool(),
+        }
+    }
+}
+
+impl From<&dyn CreateVolumeReplicaInfo> for CreateVolumeReplicaRequest {
+    fn from(data: &dyn CreateVolumeReplicaInfo) -> Self {
+        Self {
+            uuid: Some(data.uuid().to_string()),
+            pool: data.pool().to_string(),
+        }
+    }
+}
+
+/// Trait to be implemented for RemoveVolumeReplica operation
+pub trait RemoveVolumeReplicaInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume
+    fn uuid(&self) -> VolumeId;
+    /// Uuid of the replica to remove
+    fn replica(&self) -> ReplicaId;
+}
+
+impl RemoveVolumeReplicaInfo for RemoveVolumeReplica {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn replica(&self) -> ReplicaId {
+        self.replica.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to RemoveVolumeReplicaRequest type
+#[derive(Debug)]
+pub struct ValidatedRemoveVolumeReplicaRequest {
+    uuid: VolumeId,
+    replica: ReplicaId,
+}
+
+impl RemoveVolumeReplicaInfo for ValidatedRemoveVolumeReplicaRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+    fn replica(&self) -> ReplicaId {
+        self.replica.clone()
+    }
+}
+
+impl ValidateRequestTypes for RemoveVolumeReplicaRequest {
+    type Validated = ValidatedRemoveVolumeReplicaRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedRemoveVolumeReplicaRequest {
+            uuid: VolumeId::try_from(StringValue(self.uuid))?,
+            replica: ReplicaId::try_from(StringValue(self.replica))?,
+        })
+    }
+}
+
+impl From<&dyn RemoveVolumeReplicaInfo> for RemoveVolumeReplica {
+    fn from(data: &dyn RemoveVolumeReplicaInfo) -> Self {
+        Self {
+            uuid: data.uuid(),
+            replica: data.replica(),
+        }
+    }
+}
+
+impl From<&dyn RemoveVolumeReplicaInfo> for RemoveVolumeReplicaRequest {
+    fn from(data: &dyn RemoveVolumeReplicaInfo) -> Self {
+        Self {
+            uuid: Some(data.uuid().to_string()),
+            replica: Some(data.replica().to_string()),
+        }
+    }
+}
+
+/// Trait to be implemented for GetVolumeCapacity operation
+pub trait GetVolumeCapacityInfo: Send + Sync + std::fmt::Debug {
+    /// No of replicas of the new volumes
+    fn replicas(&self) -> u64;
+    /// Topology configuration of the new volumes
+    fn topology(&self) -> Option<Topology>;
+}
+
+impl GetVolumeCapacityInfo for GetVolumeCapacity {
+    fn replicas(&self) -> u64 {
+        self.replicas
+    }
+
+    fn topology(&self) -> Option<Topology> {
+        self.topology.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to GetVolumeCapacityRequest type
+#[derive(Debug)]
+pub struct ValidatedGetVolumeCapacityRequest {
+    inner: GetVolumeCapacityRequest,
+    topology: Option<Topology>,
+}
+
+impl GetVolumeCapacityInfo for ValidatedGetVolumeCapacityRequest {
+    fn replicas(&self) -> u64 {
+        self.inner.replicas
+    }
+    fn topology(&self) -> Option<Topology> {
+        self.topology.clone()
+    }
+}
+
+impl ValidateRequestTypes for GetVolumeCapacityRequest {
+    type Validated = ValidatedGetVolumeCapacityRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedGetVolumeCapacityRequest {
+            topology: match self.topology.clone() {
+                Some(topology) => match Topology::try_from(topology) {
+                    Ok(topology) => Some(topology),
+                    Err(err) => {
+                        return Err(ReplyError::invalid_argument(
+                            ResourceKind::Volume,
+                            "get_volume_capacity_request.topology",
+                            err.to_string(),
+                        ))
+                    }
+                },
+                None => None,
+            },
+            inner: self,
+        })
+    }
+}
+
+impl From<&dyn GetVolumeCapacityInfo> for GetVolumeCapacity {
+    fn from(data: &dyn GetVolumeCapacityInfo) -> Self {
+        Self {
+            replicas: data.replicas(),
+            topology: data.topology(),
+        }
+    }
+}
+
+impl From<&dyn GetVolumeCapacityInfo> for GetVolumeCapacityRequest {
+    fn from(data: &dyn GetVolumeCapacityInfo) -> Self {
+        Self {
+            replicas: data.replicas(),
+            topology: data.topology().map(|topo| topo.into()),
+        }
+    }
+}
+
+impl From<volume::VolumeCapacity> for VolumeCapacity {
+    fn from(data: volume::VolumeCapacity) -> Self {
+        Self {
+            available_capacity: data.available_capacity,
+            maximum_volume_size: data.maximum_volume_size,
+        }
+    }
+}
+
+impl From<VolumeCapacity> for volume::VolumeCapacity {
+    fn from(data: VolumeCapacity) -> Self {
+        Self {
+            available_capacity: data.available_capacity,
+            maximum_volume_size: data.maximum_volume_size,
+        }
+    }
+}
+
 /// A helper to convert the replica topology map form grpc type to corresponding control plane type
 fn to_replica_topology_map(
     map: HashMap<String, volume::ReplicaTopology>,
@@ -2,8 +2,15 @@ use clap::Parser;
 use openapi::tower::client::Url;
 use opentelemetry::global;
 use plugin::{
-    operations::{Get, List, Operations, ReplicaTopology, Scale},
-    resources::{node, pool, volume, GetResources, ScaleResources},
+    operations::{
+        Cordon, Create, Delete, Describe, Drain, Get, Label, List, MoveReplica, Operations,
+        RebuildProgress, RemoveReplica, ReplicaTopology, Scale,
+    },
+    resources::{
+        blockdevice, check, nexus, node, pool, replica, specs, usage, volume, CordonResources,
+        CreateResources, DeleteResources, DescribeResources, DrainResources, GetResources,
+        LabelResources, MoveResources, RemoveResources, ScaleResources, UncordonResources,
+    },
     rest_wrapper::RestClient,
 };
 use std::env;
@@ -19,7 +26,7 @@ struct CliArgs {
     #[clap(subcommand)]
     operations: Operations,
 
-    /// The Output, viz yaml, json.
+    /// The Output, viz wide, yaml, json, custom-columns=<NAME:.path,..>, jsonpath=<template>.
     #[clap(global = true, default_value = plugin::resources::utils::OutputFormat::None.as_ref(), short, long)]
     output: plugin::resources::utils::OutputFormat,
 
@@ -54,21 +61,93 @@ async fn execute(cli_args: CliArgs) {
 
     // Perform the operations based on the subcommand, with proper output format.
     match &cli_args.operations {
-        Operations::Get(resource) => match resource {
-            GetResources::Volumes => volume::Volumes::list(&cli_args.output).await,
-            GetResources::Volume { id } => volume::Volume::get(id, &cli_args.output).await,
-            GetResources::VolumeReplicaTopology { id } => {
-                volume::Volume::topology(id, &cli_args.output).await
+        Operations::Get(args) => {
+            let watch = args.watch();
+            match &args.resource {
+                GetResources::Volumes => volume::Volumes::list(&cli_args.output, watch).await,
+                GetResources::Volume { id } => {
+                    volume::Volume::get(id, &cli_args.output, watch).await
+                }
+                GetResources::VolumeReplicaTopology { id } => {
+                    volume::Volume::topology(id, &cli_args.output, watch).await
+                }
+                GetResources::VolumeRebuildProgress { id } => {
+                    volume::Volume::rebuild_progress(id, &cli_args.output, watch).await
+                }
+                GetResources::Pools => pool::Pools::list(&cli_args.output, watch).await,
+                GetResources::Pool { id } => pool::Pool::get(id, &cli_args.output, watch).await,
+                GetResources::Nodes => node::Nodes::list(&cli_args.output, watch).await,
+                GetResources::Node { id } => node::Node::get(id, &cli_args.output, watch).await,
+                GetResources::Replicas => replica::Replicas::list(&cli_args.output, watch).await,
+                GetResources::Replica { id } => {
+                    replica::Replica::get(id, &cli_args.output, watch).await
+                }
+                GetResources::Nexuses => nexus::Nexuses::list(&cli_args.output, watch).await,
+                GetResources::Nexus { id } => nexus::Nexus::get(id, &cli_args.output, watch).await,
+                GetResources::BlockDevices(args) => {
+                    blockdevice::BlockDevice::get(args, &cli_args.output, watch).await
+                }
+                GetResources::Specs => specs::Specs::list(&cli_args.output, watch).await,
             }
-            GetResources::Pools => pool::Pools::list(&cli_args.output).await,
-            GetResources::Pool { id } => pool::Pool::get(id, &cli_args.output).await,
-            GetResources::Nodes => node::Nodes::list(&cli_args.output).await,
-            GetResources::Node { id } => node::Node::get(id, &cli_args.output).await,
-        },
+        }
         Operations::Scale(resource) => match resource {
             ScaleResources::Volume { id, replica_count } => {
                 volume::Volume::scale(id, *replica_count, &cli_args.output).await
             }
         },
+        Operations::Create(resource) => match resource {
+            CreateResources::Pool(args) => pool::Pool::create(args, &cli_args.output).await,
+        },
+        Operations::Delete(resource) => match resource {
+            DeleteResources::Pool { id, force } => pool::Pool::delete(id, *force).await,
+        },
+        Operations::Label(resource) => match resource {
+            LabelResources::Pool {
+                id,
+                labels,
+                overwrite,
+            } => pool::Pool::label(id, labels, *overwrite, &cli_args.output).await,
+        },
+        Operations::Describe(resource) => match resource {
+            DescribeResources::Volume { id } => {
+                volume::Volume::describe(id, &cli_args.output).await
+            }
+            DescribeResources::Pool { id } => pool::Pool::describe(id, &cli_args.output).await,
+            DescribeResources::Node { id } => node::Node::describe(id, &cli_args.output).await,
+        },
+        Operations::Cordon(resource) => match resource {
+            CordonResources::Node { id } => node::Node::cordon(id, &cli_args.output).await,
+        },
+        Operations::Uncordon(resource) => match resource {
+            UncordonResources::Node { id } => node::Node::uncordon(id, &cli_args.output).await,
+        },
+        Operations::Drain(resource) => match resource {
+            DrainResources::Node(args) => {
+                let exit_code = node::Node::drain(args, &cli_args.output).await;
+                if exit_code != 0 {
+                    std::process::exit(exit_code);
+                }
+            }
+        },
+        Operations::Remove(resource) => match resource {
+            RemoveResources::VolumeReplica { id, replica } => {
+                volume::Volume::remove_replica(id, replica, &cli_args.output).await
+            }
+        },
+        Operations::Move(resource) => match resource {
+            MoveResources::VolumeReplica(args) => {
+                let exit_code = volume::Volume::move_replica(args, &cli_args.output).await;
+                if exit_code != 0 {
+                    std::process::exit(exit_code);
+                }
+            }
+        },
+        Operations::Usage(args) => usage::Usage::report(args, &cli_args.output).await,
+        Operations::Check(args) => {
+            let exit_code = check::Check::run(args, &cli_args.output).await;
+            if exit_code != 0 {
+                std::process::exit(exit_code);
+            }
+        }
     };
 }
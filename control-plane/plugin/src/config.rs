@@ -0,0 +1,250 @@
+use crate::{resources::utils::OutputFormat, rest_wrapper::RestAuth};
+use anyhow::Result;
+use openapi::tower::client::Url;
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable which overrides the location of the config file.
+pub const CONFIG_PATH_ENV: &str = "KUBECTL_MAYASTOR_CONFIG";
+
+/// The plugin config file, holding the named contexts of each cluster, eg:
+/// ```yaml
+/// current-context: prod
+/// contexts:
+///   - name: prod
+///     rest: https://rest.prod.example.com:30011
+///     token: <bearer token>
+///     ca-cert: /etc/mayastor/ca.pem
+///     namespace: mayastor
+///     output: wide
+/// ```
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct PluginConfig {
+    /// Name of the context used when none is specified on the command line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_context: Option<String>,
+    /// The named contexts.
+    #[serde(default)]
+    pub contexts: Vec<Context>,
+}
+
+/// A named context, with the REST endpoint of a cluster and the defaults used with it.
+/// Flags given on the command line take precedence over the context.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Context {
+    /// Name of the context.
+    pub name: String,
+    /// The rest endpoint to connect to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rest: Option<String>,
+    /// Bearer token used to authenticate with the REST server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Path to the PEM encoded certificate of the CA which signed the REST server certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<PathBuf>,
+    /// Kubernetes namespace of the mayastor services.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// The Output, viz wide, yaml, json, custom-columns=<NAME:.path,..>, jsonpath=<template>.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// Timeout for the REST operations, eg: `30s`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    /// Trace rest requests to the Jaeger endpoint agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jaeger: Option<String>,
+}
+
+impl PluginConfig {
+    /// Get the path of the config file, which is `$KUBECTL_MAYASTOR_CONFIG` if set and
+    /// `~/.kube/mayastor-plugin.yaml` otherwise.
+    pub fn default_path() -> Result<PathBuf> {
+        if let Ok(path) = env::var(CONFIG_PATH_ENV) {
+            return Ok(PathBuf::from(path));
+        }
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        let home = env::var("HOME")?;
+        #[cfg(target_os = "windows")]
+        let home = env::var("USERPROFILE")?;
+        Ok(Path::new(&home).join(".kube").join("mayastor-plugin.yaml"))
+    }
+
+    /// Load the config from the file, or an empty config if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            anyhow::anyhow!("Failed to read config file '{}': {}", path.display(), e)
+        })?;
+        Self::from_yaml(&contents)
+            .map_err(|e| anyhow::anyhow!("Invalid config file '{}': {}", path.display(), e))
+    }
+
+    /// Parse the config from its yaml representation.
+    pub fn from_yaml(contents: &str) -> Result<Self> {
+        Ok(serde_yaml::from_str(contents)?)
+    }
+
+    /// Save the config to the file.
+    /// The file may hold bearer tokens, so on unix it's only readable by the owner.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let file = options.open(path).map_err(|e| {
+            anyhow::anyhow!("Failed to write config file '{}': {}", path.display(), e)
+        })?;
+        serde_yaml::to_writer(file, self)?;
+        Ok(())
+    }
+
+    /// Get the context with the given name.
+    pub fn context(&self, name: &str) -> Option<&Context> {
+        self.contexts.iter().find(|context| context.name == name)
+    }
+
+    /// Get the context with the given name, or the current context if no name is given.
+    /// Returns `None` if no name is given and there's no current context.
+    pub fn select(&self, name: Option<&str>) -> Result<Option<&Context>> {
+        match name.or_else(|| self.current_context.as_deref()) {
+            Some(name) => self
+                .context(name)
+                .map(Some)
+                .ok_or_else(|| anyhow::anyhow!("Context '{}' not found", name)),
+            None => Ok(None),
+        }
+    }
+
+    /// Make the context with the given name the current context.
+    pub fn use_context(&mut self, name: &str) -> Result<()> {
+        if self.context(name).is_none() {
+            return Err(anyhow::anyhow!("Context '{}' not found", name));
+        }
+        self.current_context = Some(name.to_string());
+        Ok(())
+    }
+}
+
+impl Context {
+    /// Get the REST URL of the context.
+    pub fn rest_url(&self) -> Result<Option<Url>> {
+        self.rest
+            .as_deref()
+            .map(|url| {
+                Url::parse(url).map_err(|e| {
+                    anyhow::anyhow!(
+                        "Invalid REST URL '{}' in context '{}': {}",
+                        url,
+                        self.name,
+                        e
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    /// Get the authentication of the context, reading the CA certificate from its file.
+    pub fn rest_auth(&self) -> Result<RestAuth> {
+        let ca_certificate = match &self.ca_cert {
+            Some(path) => Some(std::fs::read(path).map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to read the CA certificate '{}' of context '{}': {}",
+                    path.display(),
+                    self.name,
+                    e
+                )
+            })?),
+            None => None,
+        };
+        Ok(RestAuth {
+            bearer_token: self.token.clone(),
+            ca_certificate,
+        })
+    }
+
+    /// Get the default output format of the context.
+    pub fn output_format(&self) -> Result<Option<OutputFormat>> {
+        self.output
+            .as_deref()
+            .map(|output| {
+                output.parse::<OutputFormat>().map_err(|e| {
+                    anyhow::anyhow!("Invalid output in context '{}': {}", self.name, e)
+                })
+            })
+            .transpose()
+    }
+
+    /// Get the default timeout of the context.
+    pub fn timeout(&self) -> Result<Option<humantime::Duration>> {
+        self.timeout
+            .as_deref()
+            .map(|timeout| {
+                timeout.parse::<humantime::Duration>().map_err(|e| {
+                    anyhow::anyhow!(
+                        "Invalid timeout '{}' in context '{}': {}",
+                        timeout,
+                        self.name,
+                        e
+                    )
+                })
+            })
+            .transpose()
+    }
+}
+
+/// The operations on the plugin config file.
+#[derive(clap::Subcommand, Debug)]
+pub enum ConfigOperations {
+    /// Set the context used when none is specified with `--context`.
+    UseContext {
+        /// Name of the context.
+        name: String,
+    },
+    /// Show the name of the current context.
+    CurrentContext,
+    /// List the contexts of the config file.
+    GetContexts,
+}
+
+impl ConfigOperations {
+    /// Execute the operation on the config file at the given path.
+    pub fn execute(&self, path: &Path) -> Result<()> {
+        let mut config = PluginConfig::load(path)?;
+        match self {
+            Self::UseContext { name } => {
+                config.use_context(name)?;
+                config.save(path)?;
+                println!("Switched to context \"{}\"", name);
+            }
+            Self::CurrentContext => match &config.current_context {
+                Some(name) => println!("{}", name),
+                None => println!("No current context is set in '{}'", path.display()),
+            },
+            Self::GetContexts => {
+                println!("{:<8}{:<24}REST", "CURRENT", "NAME");
+                for context in &config.contexts {
+                    let current = config.current_context.as_deref() == Some(&context.name);
+                    println!(
+                        "{:<8}{:<24}{}",
+                        if current { "*" } else { "" },
+                        context.name,
+                        context.rest.as_deref().unwrap_or("<kubeconfig>")
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
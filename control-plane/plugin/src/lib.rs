@@ -6,6 +6,7 @@ extern crate lazy_static;
 use opentelemetry::{global, sdk::propagation::TraceContextPropagator};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Registry};
 
+pub mod config;
 pub mod operations;
 pub mod resources;
 pub mod rest_wrapper;
@@ -1,22 +1,78 @@
-use crate::resources::{utils, GetResources, ScaleResources};
+use crate::resources::{
+    check::CheckArgs,
+    usage::UsageArgs,
+    utils,
+    utils::{CreateRows, GetHeaderRow},
+    CordonResources, CreateResources, DeleteResources, DescribeResources, DrainResources, GetArgs,
+    LabelResources, MoveResources, RemoveResources, ScaleResources, UncordonResources,
+};
 use async_trait::async_trait;
+use serde::Serialize;
+use std::time::Duration;
 
 /// The types of operations that are supported.
 #[derive(clap::Subcommand, Debug)]
 pub enum Operations {
     /// 'Get' resources.
-    #[clap(subcommand)]
-    Get(GetResources),
+    Get(GetArgs),
     /// 'Scale' resources.
     #[clap(subcommand)]
     Scale(ScaleResources),
+    /// 'Create' resources.
+    #[clap(subcommand)]
+    Create(CreateResources),
+    /// 'Delete' resources.
+    #[clap(subcommand)]
+    Delete(DeleteResources),
+    /// 'Label' resources.
+    #[clap(subcommand)]
+    Label(LabelResources),
+    /// 'Describe' resources.
+    #[clap(subcommand)]
+    Describe(DescribeResources),
+    /// 'Cordon' resources, so they are not used for new replicas and volume targets.
+    #[clap(subcommand)]
+    Cordon(CordonResources),
+    /// 'Uncordon' resources.
+    #[clap(subcommand)]
+    Uncordon(UncordonResources),
+    /// 'Drain' resources, moving their replicas elsewhere.
+    #[clap(subcommand)]
+    Drain(DrainResources),
+    /// 'Remove' resources, eg: a replica from a volume.
+    #[clap(subcommand)]
+    Remove(RemoveResources),
+    /// 'Move' resources, eg: a replica of a volume to another pool.
+    #[clap(subcommand)]
+    Move(MoveResources),
+    /// Report the storage capacity and usage of the cluster.
+    #[clap(alias = "top")]
+    Usage(UsageArgs),
+    /// Check the consistency of the specs and the states of the resources.
+    /// Exits with 1 if warnings are found, 2 if errors are found and 3 if the check fails.
+    #[clap(alias = "doctor")]
+    Check(CheckArgs),
 }
 
 /// List trait.
 /// To be implemented by resources which support the 'list' operation.
 #[async_trait(?Send)]
 pub trait List {
-    async fn list(output: &utils::OutputFormat);
+    type Resources: Serialize + CreateRows + GetHeaderRow;
+    /// Fetch the resources, printing the error and returning `None` on failure.
+    async fn fetch() -> Option<Self::Resources>;
+    /// List the resources, watching them for changes if a polling interval is given.
+    async fn list(output: &utils::OutputFormat, watch: Option<Duration>) {
+        match watch {
+            None => {
+                if let Some(resources) = Self::fetch().await {
+                    // Print table, json or yaml based on output format.
+                    utils::print_table(output, resources);
+                }
+            }
+            Some(interval) => utils::watch(output, interval, || Self::fetch()).await,
+        }
+    }
 }
 
 /// Get trait.
@@ -24,7 +80,21 @@ pub trait List {
 #[async_trait(?Send)]
 pub trait Get {
     type ID;
-    async fn get(id: &Self::ID, output: &utils::OutputFormat);
+    type Resource: Serialize + CreateRows + GetHeaderRow;
+    /// Fetch the resource, printing the error and returning `None` on failure.
+    async fn fetch(id: &Self::ID) -> Option<Self::Resource>;
+    /// Get the resource, watching it for changes if a polling interval is given.
+    async fn get(id: &Self::ID, output: &utils::OutputFormat, watch: Option<Duration>) {
+        match watch {
+            None => {
+                if let Some(resource) = Self::fetch(id).await {
+                    // Print table, json or yaml based on output format.
+                    utils::print_table(output, resource);
+                }
+            }
+            Some(interval) => utils::watch(output, interval, || Self::fetch(id)).await,
+        }
+    }
 }
 
 /// Scale trait.
@@ -40,5 +110,115 @@ pub trait Scale {
 #[async_trait(?Send)]
 pub trait ReplicaTopology {
     type ID;
-    async fn topology(id: &Self::ID, output: &utils::OutputFormat);
+    type Topology: Serialize + CreateRows + GetHeaderRow;
+    /// Fetch the replica topology, printing the error and returning `None` on failure.
+    async fn fetch_topology(id: &Self::ID) -> Option<Self::Topology>;
+    /// Get the replica topology, watching it for changes if a polling interval is given.
+    async fn topology(id: &Self::ID, output: &utils::OutputFormat, watch: Option<Duration>) {
+        match watch {
+            None => {
+                if let Some(topology) = Self::fetch_topology(id).await {
+                    // Print table, json or yaml based on output format.
+                    utils::print_table(output, topology);
+                }
+            }
+            Some(interval) => utils::watch(output, interval, || Self::fetch_topology(id)).await,
+        }
+    }
+}
+
+/// Rebuild progress trait.
+/// To be implemented by resources which support the 'rebuild-progress' operation.
+#[async_trait(?Send)]
+pub trait RebuildProgress {
+    type ID;
+    type Progress: Serialize + CreateRows + GetHeaderRow;
+    /// Fetch the rebuild progress, printing the error and returning `None` on failure.
+    async fn fetch_rebuild_progress(id: &Self::ID) -> Option<Self::Progress>;
+    /// Get the rebuild progress, watching it for changes if a polling interval is given.
+    async fn rebuild_progress(
+        id: &Self::ID,
+        output: &utils::OutputFormat,
+        watch: Option<Duration>,
+    ) {
+        match watch {
+            None => {
+                if let Some(progress) = Self::fetch_rebuild_progress(id).await {
+                    // Print table, json or yaml based on output format.
+                    utils::print_table(output, progress);
+                }
+            }
+            Some(interval) => {
+                utils::watch(output, interval, || Self::fetch_rebuild_progress(id)).await
+            }
+        }
+    }
+}
+
+/// Create trait.
+/// To be implemented by resources which support the 'create' operation.
+#[async_trait(?Send)]
+pub trait Create {
+    type Args;
+    async fn create(args: &Self::Args, output: &utils::OutputFormat);
+}
+
+/// Delete trait.
+/// To be implemented by resources which support the 'delete' operation.
+#[async_trait(?Send)]
+pub trait Delete {
+    type ID;
+    async fn delete(id: &Self::ID, force: bool);
+}
+
+/// Label trait.
+/// To be implemented by resources which support the 'label' operation.
+#[async_trait(?Send)]
+pub trait Label {
+    type ID;
+    async fn label(id: &Self::ID, labels: &[String], overwrite: bool, output: &utils::OutputFormat);
+}
+
+/// Describe trait.
+/// To be implemented by resources which support the 'describe' operation.
+#[async_trait(?Send)]
+pub trait Describe {
+    type ID;
+    async fn describe(id: &Self::ID, output: &utils::OutputFormat);
+}
+
+/// Cordon trait.
+/// To be implemented by resources which support the 'cordon' and 'uncordon' operations.
+#[async_trait(?Send)]
+pub trait Cordon {
+    type ID;
+    async fn cordon(id: &Self::ID, output: &utils::OutputFormat);
+    async fn uncordon(id: &Self::ID, output: &utils::OutputFormat);
+}
+
+/// Drain trait.
+/// To be implemented by resources which support the 'drain' operation.
+#[async_trait(?Send)]
+pub trait Drain {
+    type Args;
+    /// Drain the resource and return the process exit code, which is 0 on success.
+    async fn drain(args: &Self::Args, output: &utils::OutputFormat) -> i32;
+}
+
+/// Remove replica trait.
+/// To be implemented by resources which support the 'remove' replica operation.
+#[async_trait(?Send)]
+pub trait RemoveReplica {
+    type ID;
+    type ReplicaID;
+    async fn remove_replica(id: &Self::ID, replica: &Self::ReplicaID, output: &utils::OutputFormat);
+}
+
+/// Move replica trait.
+/// To be implemented by resources which support the 'move' replica operation.
+#[async_trait(?Send)]
+pub trait MoveReplica {
+    type Args;
+    /// Move the replica and return the process exit code, which is 0 on success.
+    async fn move_replica(args: &Self::Args, output: &utils::OutputFormat) -> i32;
 }
@@ -0,0 +1,77 @@
+use crate::{
+    operations::Get,
+    resources::{
+        utils,
+        utils::{optional_cell, CreateRows, GetHeaderRow},
+        NodeId,
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use prettytable::Row;
+
+/// Arguments used when getting the block devices of a node.
+#[derive(clap::Args, Debug)]
+pub struct BlockDeviceArgs {
+    /// Id of the node.
+    node_id: NodeId,
+    /// Show all block devices, including the ones which are in use.
+    #[clap(long)]
+    all: bool,
+}
+
+/// Empty fields of a block device are shown as having no content.
+fn non_empty_cell(field: &str) -> String {
+    optional_cell(Some(field).filter(|field| !field.is_empty()))
+}
+
+// CreateRows being trait for BlockDevice would create the rows from the list of
+// BlockDevices returned from REST call.
+impl CreateRows for openapi::models::BlockDevice {
+    fn create_rows(&self) -> Vec<Row> {
+        let rows = vec![row![
+            self.devname,
+            self.devtype,
+            self.size,
+            self.available,
+            non_empty_cell(&self.model),
+            non_empty_cell(&self.filesystem.fstype),
+            non_empty_cell(&self.filesystem.mountpoint)
+        ]];
+        rows
+    }
+}
+
+// GetHeaderRow being trait for BlockDevice would return the Header Row for
+// BlockDevice.
+impl GetHeaderRow for openapi::models::BlockDevice {
+    fn get_header_row(&self) -> Row {
+        (&*utils::BLOCKDEVICE_HEADERS).clone()
+    }
+}
+
+/// BlockDevice resource.
+#[derive(clap::Args, Debug)]
+pub struct BlockDevice {}
+
+#[async_trait(?Send)]
+impl Get for BlockDevice {
+    type ID = BlockDeviceArgs;
+    type Resource = Vec<openapi::models::BlockDevice>;
+    async fn fetch(args: &Self::ID) -> Option<Self::Resource> {
+        match RestClient::client()
+            .block_devices_api()
+            .get_node_block_devices(&args.node_id, Some(args.all))
+            .await
+        {
+            Ok(block_devices) => Some(block_devices.into_body()),
+            Err(e) => {
+                println!(
+                    "Failed to get block devices of node {}. Error {}",
+                    args.node_id, e
+                );
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{
+    operations::List,
+    resources::{
+        nexus, node, replica, specs,
+        specs::spec_operation,
+        utils::{self, CreateRows, GetHeaderRow, OutputFormat},
+        volume,
+    },
+};
+use openapi::{apis::Uuid, models};
+use prettytable::Row;
+use serde_derive::Serialize;
+use std::{
+    collections::HashSet,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Arguments used when checking the consistency of the cluster.
+#[derive(clap::Args, Debug)]
+pub struct CheckArgs {
+    /// Operations which have been pending for longer than this period are reported as stuck.
+    #[clap(long, default_value = "1m")]
+    pending_for: humantime::Duration,
+}
+
+/// Severity of a finding.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, strum_macros::Display)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// The process exit code which reports the findings with this severity.
+    fn exit_code(&self) -> i32 {
+        match self {
+            Severity::Warning => 1,
+            Severity::Error => 2,
+        }
+    }
+}
+
+/// The exit code used when the check could not be run, eg: when the REST server is unreachable.
+const CHECK_FAILED_EXIT_CODE: i32 = 3;
+
+/// A mismatch found by the consistency check.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Finding {
+    severity: Severity,
+    /// Kind of the resource, eg: volume.
+    kind: &'static str,
+    id: String,
+    message: String,
+}
+
+impl Finding {
+    fn new(severity: Severity, kind: &'static str, id: impl ToString, message: String) -> Self {
+        Self {
+            severity,
+            kind,
+            id: id.to_string(),
+            message,
+        }
+    }
+}
+
+impl CreateRows for Finding {
+    fn create_rows(&self) -> Vec<Row> {
+        vec![row![self.severity, self.kind, self.id, self.message]]
+    }
+}
+
+impl GetHeaderRow for Finding {
+    fn get_header_row(&self) -> Row {
+        (&*utils::CHECK_HEADERS).clone()
+    }
+}
+
+/// Consistency check of the specs and the states of the cluster.
+pub struct Check {}
+
+impl Check {
+    /// Run the consistency check, print the findings and return the process exit code:
+    /// 0 if there are no findings, 1 if there are warnings, 2 if there are errors and 3 if the
+    /// check could not be run.
+    pub async fn run(args: &CheckArgs, output: &OutputFormat) -> i32 {
+        let (specs, volumes, nexuses, replicas, nodes) = match (
+            specs::Specs::fetch().await,
+            volume::Volumes::fetch().await,
+            nexus::Nexuses::fetch().await,
+            replica::Replicas::fetch().await,
+            node::Nodes::fetch().await,
+        ) {
+            (Some(specs), Some(volumes), Some(nexuses), Some(replicas), Some(nodes)) => {
+                (specs, volumes, nexuses, replicas, nodes)
+            }
+            _ => return CHECK_FAILED_EXIT_CODE,
+        };
+        let mut findings = check_invariants(&specs, &volumes, &nexuses, &replicas, &nodes);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        findings.extend(stuck_operations(&specs, now, *args.pending_for));
+
+        findings.sort_by(|a, b| b.severity.cmp(&a.severity));
+        let exit_code = findings
+            .iter()
+            .map(|finding| finding.severity.exit_code())
+            .max()
+            .unwrap_or_default();
+        match output {
+            OutputFormat::None | OutputFormat::Wide if findings.is_empty() => {
+                println!("No issues found ✔")
+            }
+            _ => utils::print_table(output, findings),
+        }
+        exit_code
+    }
+}
+
+/// A pending operation of a spec, as its kind, id, operation and start time in seconds since the
+/// UNIX epoch.
+type PendingOperation = (&'static str, String, String, Option<u64>);
+
+/// Get the pending operations of the specs.
+fn pending_operations(specs: &models::Specs) -> Vec<PendingOperation> {
+    let volumes = specs.volumes.iter().filter_map(|spec| {
+        let started = spec.operation.as_ref().and_then(|op| op.started);
+        spec_operation(&spec.operation).map(|op| ("volume", spec.uuid.to_string(), op, started))
+    });
+    let nexuses = specs.nexuses.iter().filter_map(|spec| {
+        let started = spec.operation.as_ref().and_then(|op| op.started);
+        spec_operation(&spec.operation).map(|op| ("nexus", spec.uuid.to_string(), op, started))
+    });
+    let replicas = specs.replicas.iter().filter_map(|spec| {
+        let started = spec.operation.as_ref().and_then(|op| op.started);
+        spec_operation(&spec.operation).map(|op| ("replica", spec.uuid.to_string(), op, started))
+    });
+    volumes.chain(nexuses).chain(replicas).collect()
+}
+
+/// Get the findings of the operations which, at the given time since the UNIX epoch, have been
+/// pending for longer than the given period.
+/// An operation without a start time was started by a version which did not record it, hence it
+/// is reported as well.
+pub(crate) fn stuck_operations(
+    specs: &models::Specs,
+    now: Duration,
+    pending_for: Duration,
+) -> Vec<Finding> {
+    pending_operations(specs)
+        .into_iter()
+        .filter(|(.., started)| match started {
+            Some(started) => now.as_secs().saturating_sub(*started) > pending_for.as_secs(),
+            None => true,
+        })
+        .map(|(kind, id, operation, _)| {
+            Finding::new(
+                Severity::Warning,
+                kind,
+                id,
+                format!(
+                    "operation {} is pending for more than {}",
+                    operation,
+                    humantime::format_duration(pending_for)
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Get the uuid of the replica a nexus child points at, from the uuid parameter of its URI.
+fn child_replica_uuid(uri: &str) -> Option<Uuid> {
+    let (_, query) = uri.split_once('?')?;
+    query
+        .split('&')
+        .find_map(|param| param.strip_prefix("uuid="))
+        .and_then(|uuid| Uuid::parse_str(uuid).ok())
+}
+
+/// Check the invariants which must hold between the specs and the states of the resources.
+pub(crate) fn check_invariants(
+    specs: &models::Specs,
+    volumes: &[models::Volume],
+    nexuses: &[models::Nexus],
+    replicas: &[models::Replica],
+    nodes: &[models::Node],
+) -> Vec<Finding> {
+    let mut findings = vec![];
+    let volume_ids = specs
+        .volumes
+        .iter()
+        .map(|spec| spec.uuid)
+        .collect::<HashSet<_>>();
+    let replica_ids = specs
+        .replicas
+        .iter()
+        .map(|spec| spec.uuid)
+        .chain(replicas.iter().map(|replica| replica.uuid))
+        .collect::<HashSet<_>>();
+
+    // ownership of the replicas and of the nexuses
+    for replica in &specs.replicas {
+        match &replica.owners.volume {
+            Some(volume) if !volume_ids.contains(volume) => findings.push(Finding::new(
+                Severity::Error,
+                "replica",
+                replica.uuid,
+                format!("owner volume {} does not exist", volume),
+            )),
+            None if replica.managed && replica.owners.nexuses.is_empty() => {
+                findings.push(Finding::new(
+                    Severity::Warning,
+                    "replica",
+                    replica.uuid,
+                    "replica has no owning volume or nexus".to_string(),
+                ))
+            }
+            _ => {}
+        }
+    }
+    for nexus in &specs.nexuses {
+        if let Some(volume) = nexus.owner.filter(|volume| !volume_ids.contains(volume)) {
+            findings.push(Finding::new(
+                Severity::Error,
+                "nexus",
+                nexus.uuid,
+                format!("owner volume {} does not exist", volume),
+            ));
+        }
+        if !nexus.managed {
+            continue;
+        }
+        for child in &nexus.children {
+            if let Some(replica) = child_replica_uuid(child).filter(|r| !replica_ids.contains(r)) {
+                findings.push(Finding::new(
+                    Severity::Error,
+                    "nexus",
+                    nexus.uuid,
+                    format!("child {} points at missing replica {}", child, replica),
+                ));
+            }
+        }
+    }
+    for nexus in nexuses {
+        for child in &nexus.children {
+            if child.state == models::ChildState::Faulted {
+                findings.push(Finding::new(
+                    Severity::Warning,
+                    "nexus",
+                    nexus.uuid,
+                    format!("child {} is faulted", child.uri),
+                ));
+            }
+        }
+    }
+
+    // replica counts of the volumes
+    for spec in &specs.volumes {
+        if spec.status != models::SpecStatus::Created {
+            continue;
+        }
+        let replica_specs = specs
+            .replicas
+            .iter()
+            .filter(|replica| replica.owners.volume.as_ref() == Some(&spec.uuid))
+            .count();
+        if replica_specs != spec.num_replicas as usize {
+            findings.push(Finding::new(
+                Severity::Warning,
+                "volume",
+                spec.uuid,
+                format!(
+                    "volume owns {} replicas but {} are requested",
+                    replica_specs, spec.num_replicas
+                ),
+            ));
+        }
+        let volume = volumes.iter().find(|volume| volume.spec.uuid == spec.uuid);
+        if let Some(volume) = volume {
+            let healthy = volume
+                .state
+                .replica_topology
+                .values()
+                .filter(|replica| replica.state == models::ReplicaState::Online)
+                .count();
+            if healthy < spec.num_replicas as usize {
+                findings.push(Finding::new(
+                    Severity::Warning,
+                    "volume",
+                    spec.uuid,
+                    format!(
+                        "volume has {} online replicas but {} are requested",
+                        healthy, spec.num_replicas
+                    ),
+                ));
+            }
+        }
+    }
+
+    // pools on offline nodes
+    for pool in &specs.pools {
+        let status = nodes
+            .iter()
+            .find(|node| node.id == pool.node)
+            .map(node::node_status)
+            .unwrap_or(models::NodeStatus::Unknown);
+        if status != models::NodeStatus::Online {
+            findings.push(Finding::new(
+                Severity::Error,
+                "pool",
+                &pool.id,
+                format!("pool is on node {} which is {}", pool.node, status),
+            ));
+        }
+    }
+
+    findings
+}
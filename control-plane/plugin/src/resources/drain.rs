@@ -0,0 +1,330 @@
+use crate::{
+    operations::{Drain, Get, List},
+    resources::{
+        nexus, node,
+        utils::{self, CreateRows, GetHeaderRow, OutputFormat},
+        volume, NodeId, VolumeId,
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use openapi::models;
+use prettytable::Row;
+use serde_derive::Serialize;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Arguments used when draining a node.
+#[derive(clap::Args, Debug)]
+pub struct DrainNodeArgs {
+    /// ID of the node.
+    id: NodeId,
+    /// Give up if the replicas are not moved off the node within this period.
+    #[clap(long, default_value = "10m")]
+    timeout: humantime::Duration,
+    /// Only list the volumes which would be affected, without cordoning or draining the node.
+    #[clap(long)]
+    dry_run: bool,
+}
+
+/// Interval at which the volumes are polled while their replicas are moved.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The exit code used when the node could not be drained, eg: on timeout.
+const DRAIN_FAILED_EXIT_CODE: i32 = 1;
+
+/// Effect on a volume of losing the replicas it has on the node.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, strum_macros::Display)]
+pub enum DrainImpact {
+    /// The volume has fewer online replicas than requested.
+    Degraded,
+    /// The volume has no online replica left.
+    Faulted,
+}
+
+/// A volume with replicas or its target on the node which is drained.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct DrainVolume {
+    id: VolumeId,
+    /// Number of replicas requested for the volume.
+    replicas: u8,
+    /// Replicas of the volume on the node.
+    node_replicas: Vec<String>,
+    /// Whether the target of the volume is on the node.
+    target: bool,
+    /// Effect on the volume of losing the replicas on the node, if any.
+    impact: Option<DrainImpact>,
+}
+
+impl DrainVolume {
+    /// The replica counts through which the volume is scaled to move its replicas off the node.
+    /// The replica count may only change by one at a time, so for each replica on the node a
+    /// replica is added and then a replica is removed.
+    pub(crate) fn scale_steps(&self) -> Vec<u8> {
+        self.node_replicas
+            .iter()
+            .flat_map(|_| [self.replicas + 1, self.replicas])
+            .collect()
+    }
+}
+
+impl CreateRows for DrainVolume {
+    fn create_rows(&self) -> Vec<Row> {
+        vec![row![
+            self.id,
+            self.replicas,
+            self.node_replicas.len(),
+            self.target,
+            utils::optional_cell(self.impact)
+        ]]
+    }
+}
+
+impl GetHeaderRow for DrainVolume {
+    fn get_header_row(&self) -> Row {
+        (&*utils::DRAIN_HEADERS).clone()
+    }
+}
+
+/// Get the volumes with replicas or their target on the node, along with the effect on each of
+/// them of losing the replicas on the node.
+pub(crate) fn drain_impact(node: &NodeId, volumes: &[models::Volume]) -> Vec<DrainVolume> {
+    volumes
+        .iter()
+        .filter_map(|volume| {
+            let node_replicas = volume
+                .state
+                .replica_topology
+                .iter()
+                .filter(|(_, topology)| topology.node.as_ref() == Some(node))
+                .map(|(id, _)| id.clone())
+                .collect::<Vec<_>>();
+            let target = volume.state.target.as_ref().map(|target| &target.node) == Some(node);
+            if node_replicas.is_empty() && !target {
+                return None;
+            }
+            let online_elsewhere = volume
+                .state
+                .replica_topology
+                .values()
+                .filter(|topology| topology.node.as_ref() != Some(node))
+                .filter(|topology| topology.state == models::ReplicaState::Online)
+                .count();
+            let impact = match online_elsewhere {
+                _ if node_replicas.is_empty() => None,
+                0 => Some(DrainImpact::Faulted),
+                online if online < volume.spec.num_replicas as usize => Some(DrainImpact::Degraded),
+                _ => None,
+            };
+            Some(DrainVolume {
+                id: volume.spec.uuid,
+                replicas: volume.spec.num_replicas,
+                node_replicas,
+                target,
+                impact,
+            })
+        })
+        .collect()
+}
+
+/// Print the progress of the drain, unless the output is meant to be parsed.
+pub(crate) fn progress(output: &OutputFormat, message: String) {
+    if let OutputFormat::None | OutputFormat::Wide = output {
+        println!("{}", message);
+    }
+}
+
+#[async_trait(?Send)]
+impl Drain for node::Node {
+    type Args = DrainNodeArgs;
+    async fn drain(args: &Self::Args, output: &OutputFormat) -> i32 {
+        if Self::fetch(&args.id).await.is_none() {
+            return DRAIN_FAILED_EXIT_CODE;
+        }
+        let volumes = match volume::Volumes::fetch().await {
+            Some(volumes) => volumes,
+            None => return DRAIN_FAILED_EXIT_CODE,
+        };
+
+        // pre-flight listing of the volumes which are affected by the drain
+        let affected = drain_impact(&args.id, &volumes);
+        match output {
+            OutputFormat::None | OutputFormat::Wide if affected.is_empty() => {
+                println!("No volumes have replicas or targets on node {}", args.id)
+            }
+            _ => utils::print_table(output, affected.clone()),
+        }
+        if args.dry_run {
+            return 0;
+        }
+
+        if let Err(e) = RestClient::client()
+            .nodes_api()
+            .put_node_cordon(&args.id)
+            .await
+        {
+            println!("Failed to cordon node {}. Error {}", args.id, e);
+            return DRAIN_FAILED_EXIT_CODE;
+        }
+        progress(output, format!("Node {} cordoned", args.id));
+
+        let deadline = Instant::now() + *args.timeout;
+        let mut failed = 0;
+        for volume in affected.iter().filter(|v| !v.node_replicas.is_empty()) {
+            match move_replicas(&args.id, volume, deadline, output).await {
+                Ok(()) => progress(
+                    output,
+                    format!("Volume {}: replicas moved off node {}", volume.id, args.id),
+                ),
+                Err(error) => {
+                    println!(
+                        "Failed to move the replicas of volume {}. {}",
+                        volume.id, error
+                    );
+                    failed += 1;
+                }
+            }
+        }
+        for volume in affected.iter().filter(|v| v.target) {
+            progress(
+                output,
+                format!(
+                    "Volume {}: the target remains on node {} until the volume is republished",
+                    volume.id, args.id
+                ),
+            );
+        }
+
+        match failed {
+            0 => {
+                progress(output, format!("Node {} Drained Successfully 🚀", args.id));
+                0
+            }
+            failed => {
+                println!(
+                    "Failed to drain node {}: the replicas of {} volumes are still on the node",
+                    args.id, failed
+                );
+                DRAIN_FAILED_EXIT_CODE
+            }
+        }
+    }
+}
+
+/// Move the replicas of the volume off the node, one at a time, by adding a replica on another
+/// node and removing a replica on the node once the new replica is rebuilt.
+/// The replicas on the node are removed first since the node is cordoned.
+async fn move_replicas(
+    node: &NodeId,
+    volume: &DrainVolume,
+    deadline: Instant,
+    output: &OutputFormat,
+) -> Result<(), String> {
+    let current = volume::Volume::fetch(&volume.id)
+        .await
+        .ok_or_else(|| "Error failed to get the volume".to_string())?;
+    if current.state.target.is_none() {
+        // without a target the new replicas can't be rebuilt from the existing ones
+        return Err("Error the volume is not published".to_string());
+    }
+
+    let mut current = volume.replicas;
+    let mut node_replicas = volume.node_replicas.len();
+    let mut rebuilt = Ok(());
+    for replicas in volume.scale_steps() {
+        progress(
+            output,
+            format!(
+                "Volume {}: scaling from {} to {} replicas",
+                volume.id, current, replicas
+            ),
+        );
+        scale(&volume.id, replicas).await?;
+        if replicas > current {
+            rebuilt = wait_volume(&volume.id, deadline, output, "the new replica", |volume| {
+                let children = volume
+                    .state
+                    .target
+                    .as_ref()
+                    .map(|target| target.children.as_slice())
+                    .unwrap_or_default();
+                children.len() >= replicas as usize
+                    && children
+                        .iter()
+                        .all(|child| child.state == models::ChildState::Online)
+            })
+            .await;
+        } else {
+            // the volume is scaled back even if the rebuild did not complete, in which case the
+            // replica which is not rebuilt is removed first
+            rebuilt.clone()?;
+            node_replicas -= 1;
+            wait_volume(
+                &volume.id,
+                deadline,
+                output,
+                "the removal of the replica",
+                |volume| {
+                    volume
+                        .state
+                        .replica_topology
+                        .values()
+                        .filter(|topology| topology.node.as_ref() == Some(node))
+                        .count()
+                        <= node_replicas
+                },
+            )
+            .await?;
+        }
+        current = replicas;
+    }
+    Ok(())
+}
+
+/// Set the replica count of the volume.
+async fn scale(id: &VolumeId, replicas: u8) -> Result<(), String> {
+    RestClient::client()
+        .volumes_api()
+        .put_volume_replica_count(id, replicas)
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Error {}", e))
+}
+
+/// Poll the volume until `done` holds or the deadline is reached, printing the rebuild progress
+/// of its children whenever it changes.
+pub(crate) async fn wait_volume(
+    id: &VolumeId,
+    deadline: Instant,
+    output: &OutputFormat,
+    waiting_for: &str,
+    done: impl Fn(&models::Volume) -> bool,
+) -> Result<(), String> {
+    let mut last_progress = None;
+    loop {
+        let volume = volume::Volume::fetch(id)
+            .await
+            .ok_or_else(|| "Error failed to get the volume".to_string())?;
+        if done(&volume) {
+            return Ok(());
+        }
+        let rebuild_progress = volume
+            .state
+            .target
+            .as_ref()
+            .and_then(|target| nexus::rebuild_progress(&target.children));
+        if rebuild_progress != last_progress {
+            if let Some(rebuild_progress) = &rebuild_progress {
+                progress(
+                    output,
+                    format!("Volume {}: rebuilding {}", id, rebuild_progress),
+                );
+            }
+            last_progress = rebuild_progress;
+        }
+        if Instant::now() >= deadline {
+            return Err(format!("Error timed out waiting for {}", waiting_for));
+        }
+        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
+}
@@ -0,0 +1,296 @@
+//! A subset of the kubectl JSONPath support, applied to the JSON form of the openapi models.
+//! Lists are exposed as the `items` of the root object, matching the kubectl conventions, so
+//! that eg `{.items[*].spec.uuid}` extracts the uuid of every listed volume.
+
+use serde_json::Value;
+use std::{convert::TryFrom, str::FromStr};
+
+/// A single step of a JSONPath expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    /// The field with the given name of an object.
+    Field(String),
+    /// The element with the given index of an array, counting from the end if negative.
+    Index(i64),
+    /// Every element of an array or every field value of an object.
+    Wildcard,
+}
+
+/// A JSONPath expression, eg: `.spec.uuid`, `.items[0].state` or `.items[*].id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    expression: String,
+    steps: Vec<Step>,
+}
+
+impl FromStr for JsonPath {
+    type Err = String;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| format!("Invalid JSONPath '{}': {}", path, reason);
+        let mut steps = vec![];
+        let mut chars = path.trim().trim_start_matches('$').chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '.' => {
+                    let mut name = String::new();
+                    while let Some(c) = chars.next_if(|c| *c != '.' && *c != '[') {
+                        name.push(c);
+                    }
+                    match name.as_str() {
+                        // the root itself, eg: `{.}`
+                        "" if steps.is_empty() && chars.peek().is_none() => {}
+                        // allows `.[*]` as well as `[*]`
+                        "" if chars.peek() == Some(&'[') => {}
+                        "" => return Err(invalid("empty field name")),
+                        "*" => steps.push(Step::Wildcard),
+                        _ => steps.push(Step::Field(name)),
+                    }
+                }
+                '[' => {
+                    let mut index = String::new();
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some(c) => index.push(c),
+                            None => return Err(invalid("missing ']'")),
+                        }
+                    }
+                    let index = index.trim();
+                    let quoted = index
+                        .strip_prefix('\'')
+                        .and_then(|i| i.strip_suffix('\''))
+                        .or_else(|| index.strip_prefix('"').and_then(|i| i.strip_suffix('"')));
+                    match (index, quoted) {
+                        (_, Some(field)) => steps.push(Step::Field(field.to_string())),
+                        ("*", None) => steps.push(Step::Wildcard),
+                        (index, None) => steps.push(Step::Index(
+                            index
+                                .parse()
+                                .map_err(|_| invalid("the index is not a number"))?,
+                        )),
+                    }
+                }
+                _ => return Err(invalid("expected '.' or '['")),
+            }
+        }
+        Ok(Self {
+            expression: path.to_string(),
+            steps,
+        })
+    }
+}
+
+impl JsonPath {
+    /// Get all the values matching the path.
+    pub fn find<'a>(&self, root: &'a Value) -> Vec<&'a Value> {
+        self.steps.iter().fold(vec![root], |values, step| {
+            values
+                .into_iter()
+                .flat_map(|value| match (step, value) {
+                    (Step::Field(name), Value::Object(map)) => map.get(name).into_iter().collect(),
+                    (Step::Index(index), Value::Array(array)) => {
+                        let index = if *index < 0 {
+                            array.len() as i64 + index
+                        } else {
+                            *index
+                        };
+                        usize::try_from(index)
+                            .ok()
+                            .and_then(|index| array.get(index))
+                            .into_iter()
+                            .collect()
+                    }
+                    (Step::Wildcard, Value::Array(array)) => array.iter().collect(),
+                    (Step::Wildcard, Value::Object(map)) => map.values().collect(),
+                    _ => vec![],
+                })
+                .collect()
+        })
+    }
+}
+
+/// A token of a JSONPath template.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Text which is printed as is.
+    Text(String),
+    /// The values matching the path, separated by spaces.
+    Path(JsonPath),
+    /// The tokens are printed for each value matching the path, eg: `{range .items[*]}..{end}`.
+    Range(JsonPath, Vec<Token>),
+}
+
+/// A JSONPath template, eg: `{range .items[*]}{.spec.uuid}{"\t"}{.state.status}{"\n"}{end}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPathTemplate {
+    tokens: Vec<Token>,
+}
+
+impl FromStr for JsonPathTemplate {
+    type Err = String;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut blocks = vec![];
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                blocks.push(Block::Text(rest[.. start].to_string()));
+            }
+            let end = rest[start ..]
+                .find('}')
+                .ok_or_else(|| format!("Invalid JSONPath template '{}': missing '}}'", template))?;
+            blocks.push(Block::Expression(
+                rest[start + 1 .. start + end].trim().to_string(),
+            ));
+            rest = &rest[start + end + 1 ..];
+        }
+        if !rest.is_empty() {
+            blocks.push(Block::Text(rest.to_string()));
+        }
+
+        let mut blocks = blocks.into_iter();
+        let tokens = parse_tokens(&mut blocks, false)?;
+        Ok(Self { tokens })
+    }
+}
+
+/// The text and `{..}` expression blocks of a template.
+enum Block {
+    Text(String),
+    Expression(String),
+}
+
+/// Parse the blocks into tokens, up to the `{end}` of a range if `in_range` is set.
+fn parse_tokens(
+    blocks: &mut impl Iterator<Item = Block>,
+    in_range: bool,
+) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    while let Some(block) = blocks.next() {
+        match block {
+            Block::Text(text) => tokens.push(Token::Text(text)),
+            Block::Expression(expression) if expression == "end" => {
+                return match in_range {
+                    true => Ok(tokens),
+                    false => Err("Invalid JSONPath template: '{end}' without '{range}'".into()),
+                };
+            }
+            Block::Expression(expression) => {
+                if let Some(path) = expression.strip_prefix("range ") {
+                    let path = path.parse()?;
+                    tokens.push(Token::Range(path, parse_tokens(blocks, true)?));
+                } else if let Some(literal) = string_literal(&expression) {
+                    tokens.push(Token::Text(literal));
+                } else {
+                    tokens.push(Token::Path(expression.parse()?));
+                }
+            }
+        }
+    }
+    match in_range {
+        true => Err("Invalid JSONPath template: '{range}' without '{end}'".into()),
+        false => Ok(tokens),
+    }
+}
+
+/// Get the text of a quoted literal, eg: `"\n"`, with its escape sequences replaced.
+fn string_literal(expression: &str) -> Option<String> {
+    let literal = expression.strip_prefix('"')?.strip_suffix('"')?;
+    Some(
+        literal
+            .replace("\\n", "\n")
+            .replace("\\t", "\t")
+            .replace("\\r", "\r")
+            .replace("\\\"", "\""),
+    )
+}
+
+impl JsonPathTemplate {
+    /// Render the template against the given root value.
+    pub fn render(&self, root: &Value) -> Result<String, String> {
+        let mut output = String::new();
+        render_tokens(&self.tokens, root, &mut output)?;
+        Ok(output)
+    }
+}
+
+fn render_tokens(tokens: &[Token], value: &Value, output: &mut String) -> Result<(), String> {
+    for token in tokens {
+        match token {
+            Token::Text(text) => output.push_str(text),
+            Token::Path(path) => {
+                let values = path.find(value);
+                if values.is_empty() {
+                    return Err(format!("JSONPath '{}' is not found", path.expression));
+                }
+                let values = values.into_iter().map(value_text).collect::<Vec<_>>();
+                output.push_str(&values.join(" "));
+            }
+            Token::Range(path, tokens) => {
+                for value in path.find(value) {
+                    render_tokens(tokens, value, output)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Get the text of a value, without the quotes of strings.
+pub fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Columns selected with `custom-columns=NAME:.path,..`, eg: `ID:.spec.uuid,SIZE:.spec.size`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomColumns {
+    columns: Vec<(String, JsonPath)>,
+}
+
+impl FromStr for CustomColumns {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let columns = spec
+            .split(',')
+            .map(|column| match column.split_once(':') {
+                Some((name, path)) if !name.is_empty() => Ok((name.to_string(), path.parse()?)),
+                _ => Err(format!(
+                    "Invalid custom column '{}', expected NAME:.path",
+                    column
+                )),
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self { columns })
+    }
+}
+
+impl CustomColumns {
+    /// Get the names of the columns.
+    pub fn names(&self) -> Vec<&str> {
+        self.columns.iter().map(|(name, _)| name.as_str()).collect()
+    }
+    /// Get the cells of the columns for the given item.
+    pub fn cells(&self, item: &Value) -> Vec<Option<String>> {
+        self.columns
+            .iter()
+            .map(|(_, path)| {
+                let values = path.find(item);
+                match values.is_empty() {
+                    true => None,
+                    false => Some(
+                        values
+                            .into_iter()
+                            .map(value_text)
+                            .collect::<Vec<_>>()
+                            .join(","),
+                    ),
+                }
+            })
+            .collect()
+    }
+}
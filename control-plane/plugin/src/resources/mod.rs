@@ -1,12 +1,44 @@
+pub mod blockdevice;
+pub mod check;
+pub mod drain;
+pub mod jsonpath;
+pub mod nexus;
 pub mod node;
 pub mod pool;
+pub mod replica;
+pub mod specs;
+pub mod usage;
 pub mod utils;
 pub mod volume;
+pub mod volume_replica;
 
 pub type VolumeId = openapi::apis::Uuid;
 pub type ReplicaCount = u8;
 pub type PoolId = String;
 pub type NodeId = String;
+pub type ReplicaId = openapi::apis::Uuid;
+pub type NexusId = openapi::apis::Uuid;
+
+/// Arguments used when getting resources.
+#[derive(clap::Args, Debug)]
+pub struct GetArgs {
+    /// The resources to get.
+    #[clap(subcommand)]
+    pub resource: GetResources,
+    /// Watch the resources, printing the rows which changed along with the time of the change.
+    #[clap(global = true, long, short)]
+    pub watch: bool,
+    /// Interval at which the resources are polled when watching.
+    #[clap(global = true, long, default_value = "2s")]
+    pub watch_interval: humantime::Duration,
+}
+
+impl GetArgs {
+    /// Get the polling interval if the resources are to be watched.
+    pub fn watch(&self) -> Option<std::time::Duration> {
+        self.watch.then(|| *self.watch_interval)
+    }
+}
 
 /// The types of resources that support the 'get' operation.
 #[derive(clap::Subcommand, Debug)]
@@ -17,6 +49,9 @@ pub enum GetResources {
     Volume { id: VolumeId },
     /// Get the replica toplogy for the volume with the given ID
     VolumeReplicaTopology { id: VolumeId },
+    /// Get the children of the target of the volume with the given ID, along with their
+    /// rebuild progress.
+    VolumeRebuildProgress { id: VolumeId },
     /// Get all pools.
     Pools,
     /// Get pool with the given ID.
@@ -25,6 +60,18 @@ pub enum GetResources {
     Nodes,
     /// Get node with the given ID.
     Node { id: NodeId },
+    /// Get all replicas.
+    Replicas,
+    /// Get replica with the given ID.
+    Replica { id: ReplicaId },
+    /// Get all nexuses.
+    Nexuses,
+    /// Get nexus with the given ID.
+    Nexus { id: NexusId },
+    /// Get the block devices of the node with the given ID.
+    BlockDevices(blockdevice::BlockDeviceArgs),
+    /// Get all resource specs.
+    Specs,
 }
 
 /// The types of resources that support the 'scale' operation.
@@ -39,6 +86,97 @@ pub enum ScaleResources {
     },
 }
 
+/// The types of resources that support the 'create' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum CreateResources {
+    /// Create pool.
+    Pool(pool::CreatePoolArgs),
+}
+
+/// The types of resources that support the 'delete' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum DeleteResources {
+    /// Delete pool.
+    Pool {
+        /// ID of the pool.
+        id: PoolId,
+        /// Delete the pool even if it still holds replicas.
+        /// Replicas which are managed by the control plane still prevent the deletion.
+        #[clap(long)]
+        force: bool,
+    },
+}
+
+/// The types of resources that support the 'label' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum LabelResources {
+    /// Label pool.
+    Pool {
+        /// ID of the pool.
+        id: PoolId,
+        /// Labels to be set, as key=value, or removed, as key-.
+        #[clap(required = true)]
+        labels: Vec<String>,
+        /// Overwrite the value of the labels which are already set.
+        #[clap(long)]
+        overwrite: bool,
+    },
+}
+
+/// The types of resources that support the 'describe' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum DescribeResources {
+    /// Describe volume with the given ID, along with its target, replicas, pools and nodes.
+    Volume { id: VolumeId },
+    /// Describe pool with the given ID, along with its node and replicas.
+    Pool { id: PoolId },
+    /// Describe node with the given ID, along with its pools, nexuses and replicas.
+    Node { id: NodeId },
+}
+
+/// The types of resources that support the 'cordon' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum CordonResources {
+    /// Cordon node, so it is not used for new replicas and volume targets.
+    Node { id: NodeId },
+}
+
+/// The types of resources that support the 'uncordon' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum UncordonResources {
+    /// Uncordon node, so it can be used for new replicas and volume targets again.
+    Node { id: NodeId },
+}
+
+/// The types of resources that support the 'drain' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum DrainResources {
+    /// Cordon node and move the replicas of the volumes off it, after listing the volumes which
+    /// would be degraded without their replicas on the node.
+    Node(drain::DrainNodeArgs),
+}
+
+/// The types of resources that support the 'remove' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum RemoveResources {
+    /// Remove the replica with the given ID from the volume, decreasing its replica count.
+    /// The last healthy replica of the volume can't be removed.
+    VolumeReplica {
+        /// ID of the volume.
+        id: VolumeId,
+        /// ID of the replica.
+        replica: ReplicaId,
+    },
+}
+
+/// The types of resources that support the 'move' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum MoveResources {
+    /// Move the replica of the volume to the pool, by adding a replica on the pool and removing
+    /// the given replica once the new replica is rebuilt.
+    VolumeReplica(volume_replica::MoveVolumeReplicaArgs),
+}
+
 /// Tabular Output Tests
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,106 @@
+use crate::{
+    operations::{Get, List},
+    resources::{
+        utils,
+        utils::{optional_cell, CreateRows, GetHeaderRow},
+        NexusId,
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use prettytable::Row;
+
+/// Nexuses resource.
+#[derive(clap::Args, Debug)]
+pub struct Nexuses {}
+
+// CreateRows being trait for Nexus would create the rows from the list of
+// Nexuses returned from REST call.
+impl CreateRows for openapi::models::Nexus {
+    fn create_rows(&self) -> Vec<Row> {
+        let rows = vec![row![
+            self.uuid,
+            self.node,
+            self.size,
+            self.children.len(),
+            self.rebuilds,
+            self.protocol,
+            self.state
+        ]];
+        rows
+    }
+    fn create_wide_rows(&self) -> Vec<Row> {
+        let rows = vec![row![
+            self.uuid,
+            self.node,
+            self.size,
+            self.children.len(),
+            self.rebuilds,
+            self.protocol,
+            self.state,
+            optional_cell(rebuild_progress(&self.children)),
+            optional_cell(Some(&self.device_uri).filter(|uri| !uri.is_empty()))
+        ]];
+        rows
+    }
+}
+
+/// Retrieve the rebuild progress of the children which are being rebuilt, if any.
+pub(crate) fn rebuild_progress(children: &[openapi::models::Child]) -> Option<String> {
+    let progress = children
+        .iter()
+        .filter_map(|child| {
+            child
+                .rebuild_progress
+                .map(|progress| format!("{}: {}%", child.uri, progress))
+        })
+        .collect::<Vec<_>>();
+    match progress.is_empty() {
+        true => None,
+        false => Some(progress.join(", ")),
+    }
+}
+
+// GetHeaderRow being trait for Nexus would return the Header Row for
+// Nexus.
+impl GetHeaderRow for openapi::models::Nexus {
+    fn get_header_row(&self) -> Row {
+        (&*utils::NEXUS_HEADERS).clone()
+    }
+    fn get_wide_header_row(&self) -> Row {
+        (&*utils::NEXUS_WIDE_HEADERS).clone()
+    }
+}
+
+#[async_trait(?Send)]
+impl List for Nexuses {
+    type Resources = Vec<openapi::models::Nexus>;
+    async fn fetch() -> Option<Self::Resources> {
+        match RestClient::client().nexuses_api().get_nexuses().await {
+            Ok(nexuses) => Some(nexuses.into_body()),
+            Err(e) => {
+                println!("Failed to list nexuses. Error {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Nexus resource.
+#[derive(clap::Args, Debug)]
+pub struct Nexus {}
+
+#[async_trait(?Send)]
+impl Get for Nexus {
+    type ID = NexusId;
+    type Resource = openapi::models::Nexus;
+    async fn fetch(id: &Self::ID) -> Option<Self::Resource> {
+        match RestClient::client().nexuses_api().get_nexus(id).await {
+            Ok(nexus) => Some(nexus.into_body()),
+            Err(e) => {
+                println!("Failed to get nexus {}. Error {}", id, e);
+                None
+            }
+        }
+    }
+}
@@ -1,14 +1,15 @@
 use crate::{
-    operations::{Get, List},
+    operations::{Cordon, Describe, Get, List},
     resources::{
-        utils,
-        utils::{CreateRows, GetHeaderRow},
+        nexus, pool, replica, utils,
+        utils::{optional_cell, CreateRows, Description, GetHeaderRow, OutputFormat},
         NodeId,
     },
     rest_wrapper::RestClient,
 };
 use async_trait::async_trait;
 use prettytable::Row;
+use serde_derive::Serialize;
 
 /// Nodes resource.
 #[derive(clap::Args, Debug)]
@@ -26,11 +27,35 @@ impl CreateRows for openapi::models::Node {
             grpc_endpoint: spec.grpc_endpoint,
             status: openapi::models::NodeStatus::Unknown,
         });
-        let rows = vec![row![self.id, state.grpc_endpoint, state.status,]];
+        let rows = vec![row![self.id, state.grpc_endpoint, node_status_cell(self)]];
         rows
     }
 }
 
+/// Retrieve the status of a node, which is unknown if the node has no state.
+pub(crate) fn node_status(node: &openapi::models::Node) -> openapi::models::NodeStatus {
+    node.state
+        .as_ref()
+        .map(|state| state.status)
+        .unwrap_or(openapi::models::NodeStatus::Unknown)
+}
+
+/// Retrieve the status of a node for display, marking cordoned nodes, eg: `Online,Cordoned`.
+pub(crate) fn node_status_cell(node: &openapi::models::Node) -> String {
+    match node_cordoned(node) {
+        true => format!("{},Cordoned", node_status(node)),
+        false => node_status(node).to_string(),
+    }
+}
+
+/// Check if the node is cordoned.
+pub(crate) fn node_cordoned(node: &openapi::models::Node) -> bool {
+    node.spec
+        .as_ref()
+        .map(|spec| spec.cordoned)
+        .unwrap_or_default()
+}
+
 // GetHeaderRow being trait for Node would return the Header Row for
 // Node.
 impl GetHeaderRow for openapi::models::Node {
@@ -41,14 +66,13 @@ impl GetHeaderRow for openapi::models::Node {
 
 #[async_trait(?Send)]
 impl List for Nodes {
-    async fn list(output: &utils::OutputFormat) {
+    type Resources = Vec<openapi::models::Node>;
+    async fn fetch() -> Option<Self::Resources> {
         match RestClient::client().nodes_api().get_nodes().await {
-            Ok(nodes) => {
-                // Print table, json or yaml based on output format.
-                utils::print_table(output, nodes.into_body());
-            }
+            Ok(nodes) => Some(nodes.into_body()),
             Err(e) => {
-                println!("Failed to list nodes. Error {}", e)
+                println!("Failed to list nodes. Error {}", e);
+                None
             }
         }
     }
@@ -61,15 +85,157 @@ pub struct Node {}
 #[async_trait(?Send)]
 impl Get for Node {
     type ID = NodeId;
-    async fn get(id: &Self::ID, output: &utils::OutputFormat) {
+    type Resource = openapi::models::Node;
+    async fn fetch(id: &Self::ID) -> Option<Self::Resource> {
         match RestClient::client().nodes_api().get_node(id).await {
-            Ok(node) => {
-                // Print table, json or yaml based on output format.
-                utils::print_table(output, node.into_body());
+            Ok(node) => Some(node.into_body()),
+            Err(e) => {
+                println!("Failed to get node {}. Error {}", id, e);
+                None
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Cordon for Node {
+    type ID = NodeId;
+    async fn cordon(id: &Self::ID, output: &OutputFormat) {
+        match RestClient::client().nodes_api().put_node_cordon(id).await {
+            Ok(node) => match output {
+                OutputFormat::None | OutputFormat::Wide => {
+                    // In case the output format is tabular, show a success message.
+                    println!("Node {} Cordoned Successfully 🚀", id)
+                }
+                _ => {
+                    // Print json, yaml or the selected fields based on output format.
+                    utils::print_table(output, node.into_body());
+                }
+            },
+            Err(e) => {
+                println!("Failed to cordon node {}. Error {}", id, e)
             }
+        }
+    }
+    async fn uncordon(id: &Self::ID, output: &OutputFormat) {
+        match RestClient::client()
+            .nodes_api()
+            .delete_node_cordon(id)
+            .await
+        {
+            Ok(node) => match output {
+                OutputFormat::None | OutputFormat::Wide => {
+                    // In case the output format is tabular, show a success message.
+                    println!("Node {} Uncordoned Successfully 🚀", id)
+                }
+                _ => {
+                    // Print json, yaml or the selected fields based on output format.
+                    utils::print_table(output, node.into_body());
+                }
+            },
             Err(e) => {
-                println!("Failed to get node {}. Error {}", id, e)
+                println!("Failed to uncordon node {}. Error {}", id, e)
             }
         }
     }
 }
+
+/// Description of a node, along with its pools, nexuses and replicas.
+#[derive(Serialize)]
+pub struct NodeDescription {
+    node: openapi::models::Node,
+    pools: Vec<openapi::models::Pool>,
+    nexuses: Vec<openapi::models::Nexus>,
+    replicas: Vec<openapi::models::Replica>,
+}
+
+#[async_trait(?Send)]
+impl Describe for Node {
+    type ID = NodeId;
+    async fn describe(id: &Self::ID, output: &utils::OutputFormat) {
+        let node = match Self::fetch(id).await {
+            Some(node) => node,
+            None => return,
+        };
+        let (pools, nexuses, replicas) = match (
+            pool::Pools::fetch().await,
+            nexus::Nexuses::fetch().await,
+            replica::Replicas::fetch().await,
+        ) {
+            (Some(pools), Some(nexuses), Some(replicas)) => (pools, nexuses, replicas),
+            _ => return,
+        };
+        utils::print_description(
+            output,
+            &NodeDescription {
+                node,
+                pools: pools
+                    .into_iter()
+                    .filter(|pool| pool::pool_node(pool) == Some(id))
+                    .collect(),
+                nexuses: nexuses
+                    .into_iter()
+                    .filter(|nexus| &nexus.node == id)
+                    .collect(),
+                replicas: replicas
+                    .into_iter()
+                    .filter(|replica| &replica.node == id)
+                    .collect(),
+            },
+        );
+    }
+}
+
+impl Description for NodeDescription {
+    fn print_text(&self) {
+        let node = &self.node;
+        let grpc_endpoint = node
+            .state
+            .as_ref()
+            .map(|state| &state.grpc_endpoint)
+            .or_else(|| node.spec.as_ref().map(|spec| &spec.grpc_endpoint));
+        utils::print_fields(vec![
+            ("Name", node.id.clone()),
+            ("Status", node_status_cell(node)),
+            ("Grpc Endpoint", optional_cell(grpc_endpoint)),
+        ]);
+
+        let pools = self
+            .pools
+            .iter()
+            .map(|pool| {
+                let state = pool.state.as_ref();
+                row![
+                    pool.id,
+                    optional_cell(state.map(|state| state.status)),
+                    optional_cell(state.map(|state| state.capacity)),
+                    optional_cell(state.map(|state| state.used)),
+                    optional_cell(pool::pool_free_space(pool))
+                ]
+            })
+            .collect();
+        utils::print_section(
+            "Pools",
+            row!["ID", "STATUS", "CAPACITY", "USED", "FREE"],
+            pools,
+        );
+
+        let nexuses = self
+            .nexuses
+            .iter()
+            .map(|n| row![n.uuid, n.size, n.children.len(), n.rebuilds, n.state])
+            .collect();
+        utils::print_section(
+            "Nexuses",
+            row!["ID", "SIZE", "CHILDREN", "REBUILDS", "STATUS"],
+            nexuses,
+        );
+
+        let replicas = self
+            .replicas
+            .iter()
+            .map(|r| row![r.uuid, r.pool, r.size, r.state])
+            .collect();
+        utils::print_section("Replicas", row!["ID", "POOL", "SIZE", "STATUS"], replicas);
+    }
+}
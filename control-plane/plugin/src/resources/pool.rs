@@ -1,14 +1,16 @@
 use crate::{
-    operations::{Get, List},
+    operations::{Create, Delete, Describe, Get, Label, List},
     resources::{
-        utils,
-        utils::{CreateRows, GetHeaderRow},
-        PoolId,
+        node, replica, utils,
+        utils::{optional_cell, CreateRows, Description, GetHeaderRow, OutputFormat},
+        NodeId, PoolId,
     },
     rest_wrapper::RestClient,
 };
 use async_trait::async_trait;
-use prettytable::Row;
+use prettytable::{Cell, Row};
+use serde_derive::Serialize;
+use std::collections::HashMap;
 
 /// Pools resource.
 #[derive(clap::Args, Debug)]
@@ -47,6 +49,47 @@ impl CreateRows for openapi::models::Pool {
         ]];
         rows
     }
+    fn create_wide_rows(&self) -> Vec<Row> {
+        let labels = pool_labels(self);
+        self.create_rows()
+            .into_iter()
+            .map(|mut row| {
+                row.add_cell(Cell::new(&optional_cell(labels.clone())));
+                row
+            })
+            .collect()
+    }
+}
+
+/// Retrieve the labels of a pool as comma separated key=value pairs, if it has any.
+fn pool_labels(pool: &openapi::models::Pool) -> Option<String> {
+    pool.spec
+        .as_ref()
+        .and_then(|spec| spec.labels.as_ref())
+        .filter(|labels| !labels.is_empty())
+        .map(|labels| {
+            let mut labels = labels
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>();
+            labels.sort();
+            labels.join(",")
+        })
+}
+
+/// Retrieve the node of a pool, from its state or otherwise from its spec.
+pub(crate) fn pool_node(pool: &openapi::models::Pool) -> Option<&NodeId> {
+    pool.state
+        .as_ref()
+        .map(|state| &state.node)
+        .or_else(|| pool.spec.as_ref().map(|spec| &spec.node))
+}
+
+/// Retrieve the free space of a pool, if the pool has a state.
+pub(crate) fn pool_free_space(pool: &openapi::models::Pool) -> Option<u64> {
+    pool.state
+        .as_ref()
+        .map(|state| state.capacity.saturating_sub(state.used))
 }
 
 // GetHeaderRow being trait for Pool would return the Header Row for
@@ -55,18 +98,20 @@ impl GetHeaderRow for openapi::models::Pool {
     fn get_header_row(&self) -> Row {
         (&*utils::POOLS_HEADERS).clone()
     }
+    fn get_wide_header_row(&self) -> Row {
+        (&*utils::POOLS_WIDE_HEADERS).clone()
+    }
 }
 
 #[async_trait(?Send)]
 impl List for Pools {
-    async fn list(output: &utils::OutputFormat) {
+    type Resources = Vec<openapi::models::Pool>;
+    async fn fetch() -> Option<Self::Resources> {
         match RestClient::client().pools_api().get_pools().await {
-            Ok(pools) => {
-                // Print table, json or yaml based on output format.
-                utils::print_table(output, pools.into_body());
-            }
+            Ok(pools) => Some(pools.into_body()),
             Err(e) => {
-                println!("Failed to list pools. Error {}", e)
+                println!("Failed to list pools. Error {}", e);
+                None
             }
         }
     }
@@ -79,15 +124,289 @@ pub struct Pool {}
 #[async_trait(?Send)]
 impl Get for Pool {
     type ID = PoolId;
-    async fn get(id: &Self::ID, output: &utils::OutputFormat) {
+    type Resource = openapi::models::Pool;
+    async fn fetch(id: &Self::ID) -> Option<Self::Resource> {
         match RestClient::client().pools_api().get_pool(id).await {
-            Ok(pool) => {
-                // Print table, json or yaml based on output format.
-                utils::print_table(output, pool.into_body());
+            Ok(pool) => Some(pool.into_body()),
+            Err(e) => {
+                println!("Failed to get pool {}. Error {}", id, e);
+                None
             }
+        }
+    }
+}
+
+/// Arguments used when creating a pool.
+#[derive(clap::Args, Debug)]
+pub struct CreatePoolArgs {
+    /// ID of the pool.
+    id: PoolId,
+    /// ID of the node where the pool is created.
+    node_id: NodeId,
+    /// Disk device paths or URIs to be claimed by the pool.
+    /// eg: /dev/sda, aio:///dev/sda, malloc:///disk?size_mb=100
+    #[clap(required = true)]
+    disks: Vec<String>,
+    /// Labels to be set on the pool, as key=value.
+    #[clap(long = "label", short)]
+    labels: Vec<String>,
+}
+
+#[async_trait(?Send)]
+impl Create for Pool {
+    type Args = CreatePoolArgs;
+    async fn create(args: &Self::Args, output: &utils::OutputFormat) {
+        let labels = match args
+            .labels
+            .iter()
+            .map(|label| parse_label(label))
+            .collect::<Result<HashMap<_, _>, _>>()
+        {
+            Ok(labels) => labels,
             Err(e) => {
-                println!("Failed to get pool {}. Error {}", id, e)
+                println!("Failed to create pool {}. Error {}", args.id, e);
+                return;
             }
+        };
+        if let Err(e) = validate_disks(&args.node_id, &args.disks).await {
+            println!("Failed to create pool {}. Error {}", args.id, e);
+            return;
         }
+
+        let body = openapi::models::CreatePoolBody::new_all(args.disks.clone(), labels);
+        match RestClient::client()
+            .pools_api()
+            .put_node_pool(&args.node_id, &args.id, body)
+            .await
+        {
+            Ok(pool) => match output {
+                OutputFormat::None | OutputFormat::Wide => {
+                    // In case the output format is tabular, show a success message.
+                    println!("Pool {} Created Successfully 🚀", args.id)
+                }
+                _ => {
+                    // Print json, yaml or the selected fields based on output format.
+                    utils::print_table(output, pool.into_body());
+                }
+            },
+            Err(e) => {
+                println!("Failed to create pool {}. Error {}", args.id, e)
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Delete for Pool {
+    type ID = PoolId;
+    async fn delete(id: &Self::ID, force: bool) {
+        if !force {
+            // Replicas which are not managed by the control plane don't prevent the deletion of
+            // the pool, so they're checked here.
+            match RestClient::client().replicas_api().get_replicas().await {
+                Ok(replicas) => {
+                    let replicas = replicas
+                        .into_body()
+                        .into_iter()
+                        .filter(|replica| &replica.pool == id)
+                        .count();
+                    if replicas > 0 {
+                        println!(
+                            "Pool {} still holds {} replica(s), use --force to delete it anyway",
+                            id, replicas
+                        );
+                        return;
+                    }
+                }
+                Err(e) => {
+                    println!("Failed to list the replicas of pool {}. Error {}", id, e);
+                    return;
+                }
+            }
+        }
+
+        match RestClient::client().pools_api().del_pool(id).await {
+            Ok(_) => {
+                println!("Pool {} Deleted Successfully 🚀", id)
+            }
+            Err(e) => {
+                println!("Failed to delete pool {}. Error {}", id, e)
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Label for Pool {
+    type ID = PoolId;
+    async fn label(id: &Self::ID, labels: &[String], overwrite: bool, output: &OutputFormat) {
+        let mut set_labels = HashMap::new();
+        let mut remove_labels = vec![];
+        for label in labels {
+            match label.strip_suffix('-') {
+                Some(key) if !key.is_empty() && !key.contains('=') => {
+                    remove_labels.push(key.to_string())
+                }
+                _ => match parse_label(label) {
+                    Ok((key, value)) => {
+                        set_labels.insert(key, value);
+                    }
+                    Err(e) => {
+                        println!("Failed to label pool {}. Error {}", id, e);
+                        return;
+                    }
+                },
+            }
+        }
+
+        let body = openapi::models::LabelPoolBody::new_all(set_labels, remove_labels, overwrite);
+        match RestClient::client()
+            .pools_api()
+            .put_pool_label(id, body)
+            .await
+        {
+            Ok(pool) => match output {
+                OutputFormat::None | OutputFormat::Wide => {
+                    // In case the output format is tabular, show a success message.
+                    println!("Pool {} Labelled Successfully 🚀", id)
+                }
+                _ => {
+                    // Print json, yaml or the selected fields based on output format.
+                    utils::print_table(output, pool.into_body());
+                }
+            },
+            Err(e) => {
+                println!("Failed to label pool {}. Error {}", id, e)
+            }
+        }
+    }
+}
+
+/// Parse a label given as key=value.
+fn parse_label(label: &str) -> Result<(String, String), String> {
+    match label.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("Invalid label '{}', expected key=value", label)),
+    }
+}
+
+/// Check that the disks of a pool are block devices of the node which are not in use.
+/// Disks which are not backed by a block device, eg: malloc:///disk?size_mb=100, are not checked.
+async fn validate_disks(node_id: &NodeId, disks: &[String]) -> Result<(), String> {
+    let block_devices = RestClient::client()
+        .block_devices_api()
+        .get_node_block_devices(node_id, Some(true))
+        .await
+        .map_err(|e| format!("Failed to get the block devices of node {}: {}", node_id, e))?
+        .into_body();
+
+    for disk in disks {
+        let path = match disk_path(disk) {
+            Some(path) => path,
+            None => continue,
+        };
+        let block_device = block_devices.iter().find(|device| {
+            device.devname == path || device.devlinks.iter().any(|link| link == path)
+        });
+        match block_device {
+            None => {
+                return Err(format!(
+                    "Disk {} is not a block device of node {}",
+                    disk, node_id
+                ))
+            }
+            Some(device) if !device.available => {
+                return Err(format!("Disk {} of node {} is in use", disk, node_id))
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Get the device path of a disk, if it's backed by a block device.
+fn disk_path(disk: &str) -> Option<&str> {
+    let path = match disk.split_once("://") {
+        Some(("aio", path)) | Some(("uring", path)) => path,
+        Some(_) => return None,
+        None => disk,
+    };
+    path.split('?').next()
+}
+
+/// Description of a pool, along with its node and replicas.
+#[derive(Serialize)]
+pub struct PoolDescription {
+    pool: openapi::models::Pool,
+    node: Option<openapi::models::Node>,
+    replicas: Vec<openapi::models::Replica>,
+}
+
+#[async_trait(?Send)]
+impl Describe for Pool {
+    type ID = PoolId;
+    async fn describe(id: &Self::ID, output: &OutputFormat) {
+        let pool = match Self::fetch(id).await {
+            Some(pool) => pool,
+            None => return,
+        };
+        let node = match pool_node(&pool) {
+            Some(node_id) => node::Node::fetch(node_id).await,
+            None => None,
+        };
+        let replicas = match replica::Replicas::fetch().await {
+            Some(replicas) => replicas
+                .into_iter()
+                .filter(|replica| &replica.pool == id)
+                .collect(),
+            None => return,
+        };
+        utils::print_description(
+            output,
+            &PoolDescription {
+                pool,
+                node,
+                replicas,
+            },
+        );
+    }
+}
+
+impl Description for PoolDescription {
+    fn print_text(&self) {
+        let pool = &self.pool;
+        let state = pool.state.as_ref();
+        let spec = pool.spec.as_ref();
+        let disks = state
+            .map(|state| &state.disks)
+            .or_else(|| spec.map(|spec| &spec.disks))
+            .map(|disks| disks.join(", "));
+        utils::print_fields(vec![
+            ("Name", pool.id.clone()),
+            ("Node", optional_cell(pool_node(pool))),
+            (
+                "Node Status",
+                optional_cell(self.node.as_ref().map(node::node_status)),
+            ),
+            ("Status", optional_cell(state.map(|state| state.status))),
+            ("Spec Status", optional_cell(spec.map(|spec| spec.status))),
+            ("Managed", spec.is_some().to_string()),
+            ("Disks", optional_cell(disks)),
+            ("Labels", optional_cell(pool_labels(pool))),
+            ("Capacity", optional_cell(state.map(|state| state.capacity))),
+            ("Used", optional_cell(state.map(|state| state.used))),
+            ("Free", optional_cell(pool_free_space(pool))),
+        ]);
+
+        let replicas = self
+            .replicas
+            .iter()
+            .map(|r| row![r.uuid, r.size, r.thin, r.share, r.state])
+            .collect();
+        utils::print_section(
+            "Replicas",
+            row!["ID", "SIZE", "THIN", "SHARE", "STATUS"],
+            replicas,
+        );
     }
 }
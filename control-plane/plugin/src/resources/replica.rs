@@ -0,0 +1,76 @@
+use crate::{
+    operations::{Get, List},
+    resources::{
+        utils,
+        utils::{CreateRows, GetHeaderRow},
+        ReplicaId,
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use prettytable::Row;
+
+/// Replicas resource.
+#[derive(clap::Args, Debug)]
+pub struct Replicas {}
+
+// CreateRows being trait for Replica would create the rows from the list of
+// Replicas returned from REST call.
+impl CreateRows for openapi::models::Replica {
+    fn create_rows(&self) -> Vec<Row> {
+        let rows = vec![row![
+            self.uuid, self.node, self.pool, self.size, self.thin, self.share, self.state
+        ]];
+        rows
+    }
+    fn create_wide_rows(&self) -> Vec<Row> {
+        let rows = vec![row![
+            self.uuid, self.node, self.pool, self.size, self.thin, self.share, self.state, self.uri
+        ]];
+        rows
+    }
+}
+
+// GetHeaderRow being trait for Replica would return the Header Row for
+// Replica.
+impl GetHeaderRow for openapi::models::Replica {
+    fn get_header_row(&self) -> Row {
+        (&*utils::REPLICA_HEADERS).clone()
+    }
+    fn get_wide_header_row(&self) -> Row {
+        (&*utils::REPLICA_WIDE_HEADERS).clone()
+    }
+}
+
+#[async_trait(?Send)]
+impl List for Replicas {
+    type Resources = Vec<openapi::models::Replica>;
+    async fn fetch() -> Option<Self::Resources> {
+        match RestClient::client().replicas_api().get_replicas().await {
+            Ok(replicas) => Some(replicas.into_body()),
+            Err(e) => {
+                println!("Failed to list replicas. Error {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Replica resource.
+#[derive(clap::Args, Debug)]
+pub struct Replica {}
+
+#[async_trait(?Send)]
+impl Get for Replica {
+    type ID = ReplicaId;
+    type Resource = openapi::models::Replica;
+    async fn fetch(id: &Self::ID) -> Option<Self::Resource> {
+        match RestClient::client().replicas_api().get_replica(id).await {
+            Ok(replica) => Some(replica.into_body()),
+            Err(e) => {
+                println!("Failed to get replica {}. Error {}", id, e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    operations::List,
+    resources::{
+        utils,
+        utils::{optional_cell, CreateRows, GetHeaderRow},
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use prettytable::Row;
+use serde::Serialize;
+
+/// Specs resource.
+#[derive(clap::Args, Debug)]
+pub struct Specs {}
+
+/// Retrieve the name of the pending operation of a spec, if any.
+pub(crate) fn spec_operation<T: Serialize>(operation: &Option<T>) -> Option<String> {
+    let operation = serde_json::to_value(operation.as_ref()?).ok()?;
+    operation
+        .get("operation")?
+        .as_str()
+        .map(ToString::to_string)
+}
+
+// CreateRows being trait for Specs would create a row for each of the
+// specs returned from REST call.
+impl CreateRows for openapi::models::Specs {
+    fn create_rows(&self) -> Vec<Row> {
+        let volumes = self.volumes.iter().map(|spec| {
+            row![
+                "volume",
+                spec.uuid,
+                spec.status,
+                optional_cell(spec_operation(&spec.operation))
+            ]
+        });
+        let nexuses = self.nexuses.iter().map(|spec| {
+            row![
+                "nexus",
+                spec.uuid,
+                spec.status,
+                optional_cell(spec_operation(&spec.operation))
+            ]
+        });
+        let replicas = self.replicas.iter().map(|spec| {
+            row![
+                "replica",
+                spec.uuid,
+                spec.status,
+                optional_cell(spec_operation(&spec.operation))
+            ]
+        });
+        let pools = self
+            .pools
+            .iter()
+            .map(|spec| row!["pool", spec.id, spec.status, optional_cell::<String>(None)]);
+        volumes
+            .chain(nexuses)
+            .chain(replicas)
+            .chain(pools)
+            .collect()
+    }
+}
+
+// GetHeaderRow being trait for Specs would return the Header Row for
+// Specs.
+impl GetHeaderRow for openapi::models::Specs {
+    fn get_header_row(&self) -> Row {
+        (&*utils::SPECS_HEADERS).clone()
+    }
+    /// The specs are identified by their kind and id.
+    fn key_columns(&self) -> usize {
+        2
+    }
+}
+
+#[async_trait(?Send)]
+impl List for Specs {
+    type Resources = openapi::models::Specs;
+    async fn fetch() -> Option<Self::Resources> {
+        match RestClient::client().specs_api().get_specs().await {
+            Ok(specs) => Some(specs.into_body()),
+            Err(e) => {
+                println!("Failed to list specs. Error {}", e);
+                None
+            }
+        }
+    }
+}
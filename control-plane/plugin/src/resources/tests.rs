@@ -1,14 +1,32 @@
 #[cfg(test)]
-use crate::resources::utils::{print_table, CreateRows, GetHeaderRow, OutputFormat};
+use crate::{
+    config::PluginConfig,
+    resources::{
+        check::{check_invariants, stuck_operations},
+        drain::drain_impact,
+        jsonpath::{CustomColumns, JsonPathTemplate},
+        nexus::rebuild_progress,
+        usage::Usage,
+        utils::{
+            changed_rows, print_table, CreateRows, Description, GetHeaderRow, OutputFormat,
+            RowChange,
+        },
+        volume::{spec_state_diff, VolumeDescription},
+        volume_replica::volume_children,
+    },
+};
 use deployer_cluster::{Cluster, ClusterBuilder};
 use gag::BufferRedirect;
 use once_cell::sync::OnceCell;
 use openapi::{
     apis::Uuid,
-    models::{CreateVolumeBody, NodeState, PoolState, VolumeSpec, VolumeState},
+    models::{
+        volume_spec_operation, BlockDevice, CreateVolumeBody, Nexus, NodeState, PoolState, Replica,
+        VolumeSpec, VolumeSpecOperation, VolumeState,
+    },
 };
 use serde::ser;
-use std::io::Read;
+use std::{io::Read, time::Duration};
 
 static CLUSTER: OnceCell<std::sync::Mutex<Option<std::sync::Arc<Cluster>>>> = OnceCell::new();
 const VOLUME_UUID: &str = "1e3cf927-80c2-47a8-adf0-95c486bdd7b7";
@@ -207,6 +225,37 @@ async fn get_node() {
     compare(node_output(node_state), node);
 }
 
+#[tokio::test]
+async fn get_replicas() {
+    let replicas = cluster()
+        .await
+        .rest_v00()
+        .replicas_api()
+        .get_replicas()
+        .await
+        .unwrap();
+    compare(replica_output(&replicas[0]), replicas);
+}
+
+#[tokio::test]
+async fn get_replica() {
+    let replicas = cluster()
+        .await
+        .rest_v00()
+        .replicas_api()
+        .get_replicas()
+        .await
+        .unwrap();
+    let replica = cluster()
+        .await
+        .rest_v00()
+        .replicas_api()
+        .get_replica(&replicas[0].uuid)
+        .await
+        .unwrap();
+    compare(replica_output(&replica), replica);
+}
+
 #[tokio::test]
 async fn get_replica_topology() {
     let replica_topo = cluster()
@@ -240,6 +289,544 @@ async fn get_replica_topology() {
     );
 }
 
+#[test]
+fn nexus_output() {
+    let nexus: Nexus = serde_json::from_value(serde_json::json!({
+        "children": [
+            { "state": "Online", "uri": "nvmf://10.1.0.5:8420/nqn.2019-05.io.openebs:replica1" },
+            {
+                "rebuildProgress": 50,
+                "state": "Degraded",
+                "uri": "nvmf://10.1.0.6:8420/nqn.2019-05.io.openebs:replica2"
+            }
+        ],
+        "deviceUri": "",
+        "node": "io-engine-1",
+        "rebuilds": 1,
+        "protocol": "nvmf",
+        "size": 8024024,
+        "state": "Degraded",
+        "uuid": "514ed1c8-7174-49ac-b9cd-ad44ef670a67"
+    }))
+    .unwrap();
+    compare(
+        format!(
+            " {:38}{:13}{:9}CHILDREN  REBUILDS  PROTOCOL  STATUS \n",
+            "ID", "NODE", "SIZE"
+        ) + &*format!(
+            " {}  {}  {}  {:8}  {:8}  {:8}  {} \n",
+            nexus.uuid,
+            nexus.node,
+            nexus.size,
+            "2",
+            "1",
+            nexus.protocol.to_string(),
+            nexus.state.to_string()
+        ),
+        nexus.clone(),
+    );
+    assert_eq!(
+        rebuild_progress(&nexus.children).as_deref(),
+        Some("nvmf://10.1.0.6:8420/nqn.2019-05.io.openebs:replica2: 50%")
+    );
+    assert_eq!(rebuild_progress(&nexus.children[.. 1]), None);
+}
+
+#[test]
+fn block_device_output() {
+    let block_device: BlockDevice = serde_json::from_value(serde_json::json!({
+        "available": true,
+        "devlinks": [],
+        "devmajor": 8,
+        "devminor": 0,
+        "devname": "/dev/sda",
+        "devpath": "/devices/pci0000:00/0000:00:01.1/ata1/host0/target0:0:0/0:0:0:0/block/sda",
+        "devtype": "disk",
+        "filesystem": { "fstype": "", "label": "", "mountpoint": "", "uuid": "" },
+        "model": "QEMU HARDDISK",
+        "partition": {
+            "name": "", "number": 0, "parent": "", "scheme": "", "typeid": "", "uuid": ""
+        },
+        "size": 2097152
+    }))
+    .unwrap();
+    // the empty filesystem fields are shown as having no content
+    compare(
+        format!(
+            " {:10}DEVTYPE  SIZE     AVAILABLE  {:15}FSTYPE  MOUNTPOINT \n",
+            "DEVNAME", "MODEL"
+        ) + &*format!(
+            " {}  {}     {}  {}       {}  {}  {} \n",
+            "/dev/sda", "disk", 2097152, true, "QEMU HARDDISK", "<none>", "<none>"
+        ),
+        vec![block_device],
+    );
+}
+
+#[tokio::test]
+async fn get_specs() {
+    let specs = cluster()
+        .await
+        .rest_v00()
+        .specs_api()
+        .get_specs()
+        .await
+        .unwrap();
+    // the volume is not published, hence it has no nexus
+    assert!(specs.nexuses.is_empty());
+    let volume = &specs.volumes[0];
+    let replica = &specs.replicas[0];
+    let pool = &specs.pools[0];
+    assert_eq!(
+        specs.get_header_row(),
+        row!["KIND", "ID", "STATUS", "OPERATION"]
+    );
+    assert_eq!(
+        specs.create_rows(),
+        vec![
+            row!["volume", volume.uuid, volume.status, "<none>"],
+            row!["replica", replica.uuid, replica.status, "<none>"],
+            row!["pool", pool.id, pool.status, "<none>"],
+        ]
+    );
+}
+
+#[tokio::test]
+async fn describe_volume() {
+    let volume = cluster()
+        .await
+        .rest_v00()
+        .volumes_api()
+        .get_volume(&Uuid::parse_str(VOLUME_UUID).unwrap())
+        .await
+        .unwrap();
+    assert!(spec_state_diff(&volume).is_empty());
+
+    let mut degraded = volume.clone();
+    degraded.spec.num_replicas = 2;
+    assert_eq!(
+        spec_state_diff(&degraded),
+        vec![("replicas", "2".to_string(), "1".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn describe_volume_without_target() {
+    let description = volume_description(None).await;
+    let output = description_output(&description);
+    assert_eq!(description_field(&output, "Name"), Some(VOLUME_UUID));
+    assert_eq!(description_field(&output, "Target Node"), Some("<none>"));
+    assert_eq!(description_field(&output, "Accessibility"), Some("<none>"));
+    assert_eq!(description_field(&output, "Spec vs State"), Some("<none>"));
+    assert_eq!(
+        description_field(&output, "Target Children"),
+        Some("<none>")
+    );
+
+    // the only node holds the replica
+    let node = cluster().await.node(0).to_string();
+    let node_row = output
+        .lines()
+        .find(|line| line.trim().starts_with(&node))
+        .unwrap();
+    assert_eq!(
+        node_row.split_whitespace().collect::<Vec<_>>(),
+        vec![node.as_str(), "replica", "Online"]
+    );
+}
+
+#[tokio::test]
+async fn describe_volume_with_target() {
+    let node = cluster().await.node(0).to_string();
+    let child = "bdev:///3f3a0d3f-f2a4-4d7d-9e5a-2b5d5d0f8e24?uuid=3f3a0d3f";
+    let target: Nexus = serde_json::from_value(serde_json::json!({
+        "children": [{ "rebuildProgress": 50, "state": "Degraded", "uri": child }],
+        "deviceUri": "nvmf://10.1.0.5:8420/nqn.2019-05.io.openebs:1e3cf927",
+        "node": node,
+        "rebuilds": 1,
+        "protocol": "nvmf",
+        "size": 5242880,
+        "state": "Degraded",
+        "uuid": VOLUME_UUID
+    }))
+    .unwrap();
+    let description = volume_description(Some(target)).await;
+    let output = description_output(&description);
+    assert_eq!(
+        description_field(&output, "Target Node"),
+        Some(node.as_str())
+    );
+    assert_eq!(description_field(&output, "Accessibility"), Some("nvmf"));
+    assert_eq!(description_field(&output, "Target Children"), None);
+
+    // the target is not in the spec
+    let diff_rows = output
+        .lines()
+        .filter(|line| {
+            ["target node", "protocol"]
+                .iter()
+                .any(|f| line.trim().starts_with(f))
+        })
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        diff_rows,
+        vec![
+            vec!["target", "node", "<none>", node.as_str()],
+            vec!["protocol", "<none>", "nvmf"]
+        ]
+    );
+    let child_row = output
+        .lines()
+        .find(|line| line.trim().starts_with(child))
+        .unwrap();
+    assert_eq!(
+        child_row.split_whitespace().collect::<Vec<_>>(),
+        vec![child, "Degraded", "50%"]
+    );
+
+    // the only node holds both the target and the replica
+    let node_row = output
+        .lines()
+        .find(|line| line.trim().starts_with(&node))
+        .unwrap();
+    assert_eq!(
+        node_row.split_whitespace().collect::<Vec<_>>(),
+        vec![node.as_str(), "target,", "replica", "Online"]
+    );
+}
+
+#[tokio::test]
+async fn usage() {
+    let cluster = cluster().await;
+    let rest = cluster.rest_v00();
+    let pools = rest.pools_api().get_pools().await.unwrap();
+    let replicas = rest.replicas_api().get_replicas().await.unwrap();
+    let volumes = rest
+        .volumes_api()
+        .get_volumes(0, None)
+        .await
+        .unwrap()
+        .entries;
+
+    let usage = serde_json::to_value(Usage::new(&pools, &replicas, &volumes, 100)).unwrap();
+    let pool_state = pools[0].state.clone().unwrap();
+    assert_eq!(usage["total"]["pools"], 1);
+    assert_eq!(usage["total"]["capacity"], pool_state.capacity);
+    assert_eq!(usage["total"]["used"], pool_state.used);
+    assert_eq!(usage["nodes"][&pool_state.node]["pools"], 1);
+    assert_eq!(usage["volumes_size"], 5242880);
+    assert_eq!(usage["provisioned"], 5242880);
+    assert_eq!(usage["pools"][0]["replicas"], 1);
+    assert_eq!(usage["pools"][0]["above_threshold"], false);
+}
+
+#[tokio::test]
+async fn check() {
+    let cluster = cluster().await;
+    let rest = cluster.rest_v00();
+    let mut specs = rest.specs_api().get_specs().await.unwrap();
+    let volumes = rest
+        .volumes_api()
+        .get_volumes(0, None)
+        .await
+        .unwrap()
+        .entries;
+    let nexuses = rest.nexuses_api().get_nexuses().await.unwrap();
+    let replicas = rest.replicas_api().get_replicas().await.unwrap();
+    let nodes = rest.nodes_api().get_nodes().await.unwrap();
+    assert!(check_invariants(&specs, &volumes, &nexuses, &replicas, &nodes).is_empty());
+
+    // a volume which requests more replicas than it owns
+    specs.volumes[0].num_replicas = 2;
+    let findings = serde_json::to_value(check_invariants(
+        &specs, &volumes, &nexuses, &replicas, &nodes,
+    ))
+    .unwrap();
+    assert_eq!(findings[0]["severity"], "Warning");
+    assert_eq!(findings[0]["kind"], "volume");
+    assert_eq!(findings[0]["id"], VOLUME_UUID);
+}
+
+#[tokio::test]
+async fn check_stuck_operations() {
+    let mut specs = cluster()
+        .await
+        .rest_v00()
+        .specs_api()
+        .get_specs()
+        .await
+        .unwrap();
+    let now = Duration::from_secs(1000);
+    let pending_for = Duration::from_secs(60);
+    assert!(stuck_operations(&specs, now, pending_for).is_empty());
+
+    let publish = |started: Option<u64>| {
+        Some(VolumeSpecOperation::new_all(
+            volume_spec_operation::Operation::Publish,
+            None,
+            started,
+        ))
+    };
+    // a recently started operation is not stuck yet
+    specs.volumes[0].operation = publish(Some(990));
+    assert!(stuck_operations(&specs, now, pending_for).is_empty());
+
+    // an operation started long ago, or at an unknown time, is stuck
+    for started in [Some(900), None] {
+        specs.volumes[0].operation = publish(started);
+        let findings = serde_json::to_value(stuck_operations(&specs, now, pending_for)).unwrap();
+        assert_eq!(findings.as_array().unwrap().len(), 1);
+        assert_eq!(findings[0]["kind"], "volume");
+        assert_eq!(findings[0]["id"], VOLUME_UUID);
+        assert_eq!(
+            findings[0]["message"],
+            "operation Publish is pending for more than 1m"
+        );
+    }
+}
+
+#[tokio::test]
+async fn drain() {
+    let cluster = cluster().await;
+    let volumes = cluster
+        .rest_v00()
+        .volumes_api()
+        .get_volumes(0, None)
+        .await
+        .unwrap()
+        .entries;
+
+    // the only replica of the volume is on the only node
+    let affected =
+        serde_json::to_value(drain_impact(&cluster.node(0).to_string(), &volumes)).unwrap();
+    assert_eq!(affected[0]["id"], VOLUME_UUID);
+    assert_eq!(affected[0]["node_replicas"].as_array().unwrap().len(), 1);
+    assert_eq!(affected[0]["target"], false);
+    assert_eq!(affected[0]["impact"], "Faulted");
+
+    assert!(drain_impact(&"missing".to_string(), &volumes).is_empty());
+}
+
+#[tokio::test]
+async fn drain_replicas_on_several_pools() {
+    let cluster = cluster().await;
+    let mut volume = cluster
+        .rest_v00()
+        .volumes_api()
+        .get_volume(&Uuid::parse_str(VOLUME_UUID).unwrap())
+        .await
+        .unwrap();
+    // the volume has two replicas on the node, on different pools
+    let mut replica = volume
+        .state
+        .replica_topology
+        .values()
+        .next()
+        .unwrap()
+        .clone();
+    replica.pool = Some("pool-2".to_string());
+    volume
+        .state
+        .replica_topology
+        .insert("0b0dd6bc-0ba4-4dc2-a3b1-f52e9b1d5cb8".to_string(), replica);
+    volume.spec.num_replicas = 2;
+
+    let affected = drain_impact(&cluster.node(0).to_string(), &[volume]);
+    assert_eq!(affected.len(), 1);
+    // the replicas are moved one at a time, as the replica count may only change by one
+    let steps = affected[0].scale_steps();
+    assert_eq!(steps, vec![3, 2, 3, 2]);
+    let mut replicas = 2;
+    for step in steps {
+        assert_eq!((step as i16 - replicas as i16).abs(), 1);
+        replicas = step;
+    }
+}
+
+#[tokio::test]
+async fn volume_replicas() {
+    let cluster = cluster().await;
+    let volumes_api = cluster.rest_v00().volumes_api();
+    let volume_id = Uuid::parse_str(VOLUME_UUID).unwrap();
+    let volume = volumes_api.get_volume(&volume_id).await.unwrap();
+    let replicas = cluster
+        .rest_v00()
+        .replicas_api()
+        .get_replicas()
+        .await
+        .unwrap();
+
+    // the volume is not published, so it has no target children to rebuild
+    assert!(volume_children(&volume, &replicas).is_empty());
+
+    // the only replica of the volume can't be removed
+    let replica = volume.state.replica_topology.keys().next().unwrap();
+    volumes_api
+        .del_volume_replica(&volume_id, &Uuid::parse_str(replica).unwrap())
+        .await
+        .expect_err("The last replica can't be removed");
+
+    // the only node already has a replica of the volume
+    volumes_api
+        .put_volume_pool_replica(&volume_id, &cluster.pool(0, 0).to_string())
+        .await
+        .expect_err("The pool is not suitable for a new replica");
+    let volume = volumes_api.get_volume(&volume_id).await.unwrap();
+    assert_eq!(volume.spec.num_replicas, 1);
+}
+
+#[test]
+fn watch_changed_rows() {
+    let previous = vec![
+        row!["vol-1", "Online"],
+        row!["vol-2", "Online"],
+        row!["vol-4", "Online"],
+    ];
+    let current = vec![
+        row!["vol-1", "Online"],
+        row!["vol-2", "Degraded"],
+        row!["vol-3", "Online"],
+    ];
+    assert_eq!(
+        changed_rows(&previous, &current, 1),
+        vec![
+            (RowChange::Modified, row!["vol-2", "Degraded"]),
+            (RowChange::Added, row!["vol-3", "Online"]),
+            (RowChange::Deleted, row!["vol-4", "Online"]),
+        ]
+    );
+    assert!(changed_rows(&current, &current, 1).is_empty());
+
+    // the resources are identified by more than their first column
+    let previous = vec![
+        row!["volume", "vol-1", "Created"],
+        row!["volume", "vol-2", "Created"],
+    ];
+    let current = vec![row!["volume", "vol-1", "Created"]];
+    assert_eq!(
+        changed_rows(&previous, &current, 2),
+        vec![(RowChange::Deleted, row!["volume", "vol-2", "Created"])]
+    );
+}
+
+#[test]
+fn jsonpath_output() {
+    let volumes = serde_json::json!({ "items": [
+        { "spec": { "uuid": "vol-1", "num_replicas": 1 } },
+        { "spec": { "uuid": "vol-2", "num_replicas": 3 } },
+    ]});
+    let render = |template: &str| {
+        template
+            .parse::<JsonPathTemplate>()
+            .unwrap()
+            .render(&volumes)
+    };
+    assert_eq!(render("{.items[*].spec.uuid}").unwrap(), "vol-1 vol-2");
+    assert_eq!(render("{.items[-1].spec.num_replicas}").unwrap(), "3");
+    assert_eq!(
+        render(r#"{range .items[*]}{.spec.uuid}{"\t"}{.spec.num_replicas}{"\n"}{end}"#).unwrap(),
+        "vol-1\t1\nvol-2\t3\n"
+    );
+    assert!(render("{.items[0].spec.size}").is_err());
+    assert!("{range .items[*]}{.spec.uuid}"
+        .parse::<JsonPathTemplate>()
+        .is_err());
+}
+
+#[test]
+fn custom_columns_output() {
+    let columns = "ID:.spec.uuid,SIZE:.spec.size"
+        .parse::<CustomColumns>()
+        .unwrap();
+    assert_eq!(columns.names(), vec!["ID", "SIZE"]);
+    let volume = serde_json::json!({ "spec": { "uuid": "vol-1" } });
+    assert_eq!(
+        columns.cells(&volume),
+        vec![Some("vol-1".to_string()), None]
+    );
+    assert!("ID".parse::<CustomColumns>().is_err());
+}
+
+#[test]
+fn plugin_config() {
+    let mut config = PluginConfig::from_yaml(
+        r#"
+current-context: prod
+contexts:
+  - name: prod
+    rest: https://rest.prod.example.com:30011
+    token: secret
+    namespace: storage
+    output: wide
+    timeout: 30s
+  - name: dev
+"#,
+    )
+    .unwrap();
+
+    let prod = config.select(None).unwrap().unwrap();
+    assert_eq!(prod.token.as_deref(), Some("secret"));
+    assert_eq!(
+        prod.rest_url().unwrap().unwrap().as_str(),
+        "https://rest.prod.example.com:30011/"
+    );
+    assert!(matches!(
+        prod.output_format().unwrap(),
+        Some(OutputFormat::Wide)
+    ));
+    assert_eq!(
+        *prod.timeout().unwrap().unwrap(),
+        std::time::Duration::from_secs(30)
+    );
+
+    let dev = config.select(Some("dev")).unwrap().unwrap();
+    assert_eq!(dev.rest_url().unwrap(), None);
+    assert!(dev.rest_auth().unwrap().bearer_token.is_none());
+    assert!(config.select(Some("staging")).is_err());
+
+    config.use_context("dev").unwrap();
+    assert_eq!(config.current_context.as_deref(), Some("dev"));
+    assert!(config.use_context("staging").is_err());
+    assert_eq!(config.current_context.as_deref(), Some("dev"));
+
+    let saved = serde_yaml::to_string(&config).unwrap();
+    assert_eq!(PluginConfig::from_yaml(&saved).unwrap(), config);
+    assert!(PluginConfig::default().select(None).unwrap().is_none());
+}
+
+// Describes the volume of the cluster, with the given target, along with its pools and nodes
+async fn volume_description(target: Option<Nexus>) -> VolumeDescription {
+    let rest = cluster().await.rest_v00();
+    let mut volume = rest
+        .volumes_api()
+        .get_volume(&Uuid::parse_str(VOLUME_UUID).unwrap())
+        .await
+        .unwrap();
+    volume.state.target = target;
+    let pools = rest.pools_api().get_pools().await.unwrap();
+    let nodes = rest.nodes_api().get_nodes().await.unwrap();
+    VolumeDescription::new(volume, pools, nodes)
+}
+
+// Returns the text description redirected to buffer
+fn description_output<T: Description>(description: &T) -> String {
+    let mut buf = BufferRedirect::stdout().unwrap();
+    description.print_text();
+    let mut output = String::new();
+    buf.read_to_string(&mut output).unwrap();
+    output
+}
+
+// Returns the value of the given field, or section, of the text description
+fn description_field<'a>(output: &'a str, name: &str) -> Option<&'a str> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(&format!("{}:", name)))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
 // Compares the print_table output redirected to buffer with the expected string
 fn compare<T>(expected_output: String, obj: T)
 where
@@ -306,3 +893,28 @@ fn node_output(node_state: NodeState) -> String {
         node_state.status.to_string()
     )
 }
+
+fn replica_output(replica: &Replica) -> String {
+    let size = replica.size.to_string();
+    format!(
+        " {:width_id$}{:width_node$}{:width_pool$}{:width_size$}THIN   SHARE  STATUS \n",
+        "ID",
+        "NODE",
+        "POOL",
+        "SIZE",
+        width_id = 38,
+        width_node = replica.node.len() + 2,
+        width_pool = replica.pool.len() + 2,
+        width_size = size.len().max(4) + 2
+    ) + &*format!(
+        " {}  {}  {}  {:width_size$}{:7}{:7}{} \n",
+        replica.uuid,
+        replica.node,
+        replica.pool,
+        size,
+        replica.thin.to_string(),
+        replica.share.to_string(),
+        replica.state.to_string(),
+        width_size = size.len().max(4) + 2
+    )
+}
@@ -0,0 +1,276 @@
+use crate::{
+    operations::List,
+    resources::{
+        pool, replica,
+        utils::{self, optional_cell, Description, OutputFormat},
+        volume, NodeId, PoolId,
+    },
+};
+use prettytable::{Attr, Row};
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
+
+/// Arguments used when reporting the storage usage.
+#[derive(clap::Args, Debug)]
+pub struct UsageArgs {
+    /// Pools whose used capacity is above this percentage are highlighted.
+    #[clap(long, default_value = "80", validator = valid_threshold)]
+    threshold: u8,
+}
+
+fn valid_threshold(threshold: &str) -> Result<(), String> {
+    match threshold.parse::<u8>() {
+        Ok(threshold) if threshold <= 100 => Ok(()),
+        _ => Err("the threshold must be a percentage between 0 and 100".to_string()),
+    }
+}
+
+/// Capacity and used space of a group of pools.
+#[derive(Serialize, Default)]
+pub struct CapacityUsage {
+    /// Number of pools in the group.
+    pools: usize,
+    /// Total capacity of the pools.
+    capacity: u64,
+    /// Used capacity of the pools.
+    used: u64,
+}
+
+impl CapacityUsage {
+    fn add(&mut self, capacity: u64, used: u64) {
+        self.pools += 1;
+        self.capacity += capacity;
+        self.used += used;
+    }
+    fn free(&self) -> u64 {
+        self.capacity.saturating_sub(self.used)
+    }
+    /// Percentage of the capacity which is used, if there is any capacity.
+    fn used_percentage(&self) -> Option<f64> {
+        match self.capacity {
+            0 => None,
+            capacity => Some(self.used as f64 * 100.0 / capacity as f64),
+        }
+    }
+    fn header(name: &str) -> Row {
+        row![name, "POOLS", "CAPACITY", "USED", "FREE", "USAGE"]
+    }
+    fn row(&self, name: &str) -> Row {
+        row![
+            name,
+            self.pools,
+            self.capacity,
+            self.used,
+            self.free(),
+            optional_cell(self.used_percentage().map(percentage))
+        ]
+    }
+}
+
+/// Usage of a single pool, along with the replicas it holds.
+#[derive(Serialize)]
+pub struct PoolUsage {
+    id: PoolId,
+    node: Option<NodeId>,
+    capacity: u64,
+    used: u64,
+    /// Number of replicas on the pool.
+    replicas: usize,
+    /// Sum of the size of the replicas on the pool.
+    replicas_size: u64,
+    /// Whether the used capacity is above the threshold.
+    above_threshold: bool,
+}
+
+/// Cluster wide storage usage.
+#[derive(Serialize)]
+pub struct Usage {
+    /// Percentage of used capacity above which pools are highlighted.
+    threshold: u8,
+    /// Usage of all the pools.
+    total: CapacityUsage,
+    /// Sum of the size of the volumes.
+    volumes_size: u64,
+    /// Sum of the size of the volumes times their number of replicas.
+    provisioned: u64,
+    /// Ratio of the provisioned size to the physical capacity.
+    over_commit_ratio: Option<f64>,
+    /// Usage of the pools of each node.
+    nodes: BTreeMap<NodeId, CapacityUsage>,
+    /// Usage of the pools with each label, as key=value.
+    labels: BTreeMap<String, CapacityUsage>,
+    pools: Vec<PoolUsage>,
+}
+
+fn percentage(percentage: f64) -> String {
+    format!("{:.1}%", percentage)
+}
+
+impl Usage {
+    /// Aggregate the usage of the given pools, replicas and volumes.
+    pub fn new(
+        pools: &[openapi::models::Pool],
+        replicas: &[openapi::models::Replica],
+        volumes: &[openapi::models::Volume],
+        threshold: u8,
+    ) -> Self {
+        let mut total = CapacityUsage::default();
+        let mut nodes = BTreeMap::<NodeId, CapacityUsage>::new();
+        let mut labels = BTreeMap::<String, CapacityUsage>::new();
+        let mut pool_usages = vec![];
+        for pool in pools {
+            // pools without a state, eg: on a lost node, don't contribute any capacity
+            let (capacity, used) = pool
+                .state
+                .as_ref()
+                .map(|state| (state.capacity, state.used))
+                .unwrap_or_default();
+            total.add(capacity, used);
+            if let Some(node) = pool::pool_node(pool) {
+                nodes.entry(node.clone()).or_default().add(capacity, used);
+            }
+            let pool_labels = pool.spec.as_ref().and_then(|spec| spec.labels.as_ref());
+            for (key, value) in pool_labels.into_iter().flatten() {
+                labels
+                    .entry(format!("{}={}", key, value))
+                    .or_default()
+                    .add(capacity, used);
+            }
+
+            let pool_replicas = replicas.iter().filter(|replica| replica.pool == pool.id);
+            let usage = CapacityUsage {
+                pools: 1,
+                capacity,
+                used,
+            };
+            pool_usages.push(PoolUsage {
+                id: pool.id.clone(),
+                node: pool::pool_node(pool).cloned(),
+                capacity,
+                used,
+                replicas: pool_replicas.clone().count(),
+                replicas_size: pool_replicas.map(|replica| replica.size).sum(),
+                above_threshold: usage
+                    .used_percentage()
+                    .map(|used| used > threshold as f64)
+                    .unwrap_or_default(),
+            });
+        }
+
+        let volumes_size = volumes.iter().map(|volume| volume.spec.size).sum();
+        let provisioned = volumes
+            .iter()
+            .map(|volume| volume.spec.size * volume.spec.num_replicas as u64)
+            .sum::<u64>();
+        let over_commit_ratio = match total.capacity {
+            0 => None,
+            capacity => Some(provisioned as f64 / capacity as f64),
+        };
+        Self {
+            threshold,
+            total,
+            volumes_size,
+            provisioned,
+            over_commit_ratio,
+            nodes,
+            labels,
+            pools: pool_usages,
+        }
+    }
+
+    /// Report the storage usage of the cluster.
+    pub async fn report(args: &UsageArgs, output: &OutputFormat) {
+        let (pools, replicas, volumes) = match (
+            pool::Pools::fetch().await,
+            replica::Replicas::fetch().await,
+            volume::Volumes::fetch().await,
+        ) {
+            (Some(pools), Some(replicas), Some(volumes)) => (pools, replicas, volumes),
+            _ => return,
+        };
+        let usage = Self::new(&pools, &replicas, &volumes, args.threshold);
+        utils::print_description(output, &usage);
+    }
+}
+
+impl Description for Usage {
+    fn print_text(&self) {
+        utils::print_fields(vec![
+            ("Pools", self.total.pools.to_string()),
+            ("Capacity", self.total.capacity.to_string()),
+            ("Used", self.total.used.to_string()),
+            ("Free", self.total.free().to_string()),
+            (
+                "Usage",
+                optional_cell(self.total.used_percentage().map(percentage)),
+            ),
+            ("Volumes Size", self.volumes_size.to_string()),
+            ("Provisioned", self.provisioned.to_string()),
+            (
+                "Over-commit Ratio",
+                optional_cell(self.over_commit_ratio.map(|ratio| format!("{:.2}", ratio))),
+            ),
+        ]);
+
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|(node, usage)| usage.row(node))
+            .collect();
+        utils::print_section("Nodes", CapacityUsage::header("NODE"), nodes);
+
+        let labels = self
+            .labels
+            .iter()
+            .map(|(label, usage)| usage.row(label))
+            .collect();
+        utils::print_section("Labels", CapacityUsage::header("LABEL"), labels);
+
+        let pools = self
+            .pools
+            .iter()
+            .map(|pool| {
+                let usage = CapacityUsage {
+                    pools: 1,
+                    capacity: pool.capacity,
+                    used: pool.used,
+                };
+                let alert = match pool.above_threshold {
+                    true => format!("above {}%", self.threshold),
+                    false => String::new(),
+                };
+                let mut row = row![
+                    pool.id,
+                    optional_cell(pool.node.as_ref()),
+                    pool.capacity,
+                    pool.used,
+                    usage.free(),
+                    optional_cell(usage.used_percentage().map(percentage)),
+                    pool.replicas,
+                    pool.replicas_size,
+                    alert
+                ];
+                if pool.above_threshold {
+                    // highlight the pools above the threshold, on terminals which support it
+                    row.iter_mut().for_each(|cell| cell.style(Attr::Bold));
+                }
+                row
+            })
+            .collect();
+        utils::print_section(
+            "Pools",
+            row![
+                "POOL",
+                "NODE",
+                "CAPACITY",
+                "USED",
+                "FREE",
+                "USAGE",
+                "REPLICAS",
+                "REPLICAS-SIZE",
+                "ALERT"
+            ],
+            pools,
+        );
+    }
+}
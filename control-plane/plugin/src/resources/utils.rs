@@ -1,5 +1,7 @@
-use prettytable::{format, Row, Table};
+use crate::resources::jsonpath::{CustomColumns, JsonPathTemplate};
+use prettytable::{format, Cell, Row, Table};
 use serde::ser;
+use std::{future::Future, str::FromStr, time::Duration};
 
 const CELL_NO_CONTENT: &str = "<none>";
 
@@ -20,6 +22,17 @@ lazy_static! {
         "STATUS",
         "SIZE"
     ];
+    pub static ref VOLUME_WIDE_HEADERS: Row = row![
+        "ID",
+        "REPLICAS",
+        "TARGET-NODE",
+        "ACCESSIBILITY",
+        "STATUS",
+        "SIZE",
+        "REBUILDS",
+        "REBUILD-PROGRESS",
+        "DEVICE-URI"
+    ];
     pub static ref POOLS_HEADERS: Row = row![
         "ID",
         "TOTAL CAPACITY",
@@ -29,8 +42,63 @@ lazy_static! {
         "STATUS",
         "MANAGED"
     ];
+    pub static ref POOLS_WIDE_HEADERS: Row = row![
+        "ID",
+        "TOTAL CAPACITY",
+        "USED CAPACITY",
+        "DISKS",
+        "NODE",
+        "STATUS",
+        "MANAGED",
+        "LABELS"
+    ];
     pub static ref NODE_HEADERS: Row = row!["ID", "GRPC ENDPOINT", "STATUS",];
     pub static ref REPLICA_TOPOLOGY_HEADERS: Row = row!["ID", "NODE", "POOL", "STATUS"];
+    pub static ref REBUILD_PROGRESS_HEADERS: Row =
+        row!["REPLICA", "NODE", "POOL", "STATE", "REBUILD-PROGRESS"];
+    pub static ref REBUILD_PROGRESS_WIDE_HEADERS: Row = row![
+        "REPLICA",
+        "NODE",
+        "POOL",
+        "STATE",
+        "REBUILD-PROGRESS",
+        "URI"
+    ];
+    pub static ref REPLICA_HEADERS: Row =
+        row!["ID", "NODE", "POOL", "SIZE", "THIN", "SHARE", "STATUS"];
+    pub static ref REPLICA_WIDE_HEADERS: Row =
+        row!["ID", "NODE", "POOL", "SIZE", "THIN", "SHARE", "STATUS", "URI"];
+    pub static ref NEXUS_HEADERS: Row =
+        row!["ID", "NODE", "SIZE", "CHILDREN", "REBUILDS", "PROTOCOL", "STATUS"];
+    pub static ref NEXUS_WIDE_HEADERS: Row = row![
+        "ID",
+        "NODE",
+        "SIZE",
+        "CHILDREN",
+        "REBUILDS",
+        "PROTOCOL",
+        "STATUS",
+        "REBUILD-PROGRESS",
+        "DEVICE-URI"
+    ];
+    pub static ref BLOCKDEVICE_HEADERS: Row = row![
+        "DEVNAME",
+        "DEVTYPE",
+        "SIZE",
+        "AVAILABLE",
+        "MODEL",
+        "FSTYPE",
+        "MOUNTPOINT"
+    ];
+    pub static ref SPECS_HEADERS: Row = row!["KIND", "ID", "STATUS", "OPERATION"];
+    pub static ref CHECK_HEADERS: Row = row!["SEVERITY", "KIND", "ID", "FINDING"];
+    pub static ref DRAIN_HEADERS: Row = row![
+        "ID",
+        "REPLICAS",
+        "REPLICAS-ON-NODE",
+        "TARGET-ON-NODE",
+        "IMPACT"
+    ];
 }
 
 // table_printer takes the above defined headers and the rows created at execution,
@@ -49,20 +117,61 @@ pub fn table_printer(titles: Row, rows: Vec<Row>) {
 // CreateRows trait to be implemented by Vec<Volume/Pool> to create the rows.
 pub trait CreateRows {
     fn create_rows(&self) -> Vec<Row>;
+    /// Create the rows with the extra columns of the wide output format.
+    fn create_wide_rows(&self) -> Vec<Row> {
+        self.create_rows()
+    }
 }
 
 // GetHeaderRow trait to be implemented by Volume/Pool to fetch the corresponding headers.
 pub trait GetHeaderRow {
     fn get_header_row(&self) -> Row;
+    /// Get the header row with the extra columns of the wide output format.
+    fn get_wide_header_row(&self) -> Row {
+        self.get_header_row()
+    }
+    /// Get the number of leading columns which identify a resource, eg: its id.
+    fn key_columns(&self) -> usize {
+        1
+    }
 }
 
 // OutputFormat to be used as an enum to match the output from args.
-#[derive(Debug, strum_macros::EnumString, strum_macros::AsRefStr)]
+#[derive(Debug, strum_macros::AsRefStr)]
 #[strum(serialize_all = "lowercase")]
 pub enum OutputFormat {
     None,
+    Wide,
     Yaml,
     Json,
+    /// The given columns, eg: `custom-columns=ID:.spec.uuid,SIZE:.spec.size`.
+    CustomColumns(CustomColumns),
+    /// The given JSONPath template, eg: `jsonpath={.items[*].spec.uuid}`.
+    JsonPath(JsonPathTemplate),
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(columns) = s.strip_prefix("custom-columns=") {
+            return Ok(Self::CustomColumns(columns.parse()?));
+        }
+        if let Some(template) = s.strip_prefix("jsonpath=") {
+            return Ok(Self::JsonPath(template.parse()?));
+        }
+        match s {
+            "none" => Ok(Self::None),
+            "wide" => Ok(Self::Wide),
+            "yaml" => Ok(Self::Yaml),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "Invalid output format '{}', expected one of: wide, yaml, json, \
+                custom-columns=<NAME:.path,..>, jsonpath=<template>",
+                s
+            )),
+        }
+    }
 }
 
 impl<T> CreateRows for Vec<T>
@@ -72,6 +181,9 @@ where
     fn create_rows(&self) -> Vec<Row> {
         self.iter().flat_map(|i| i.create_rows()).collect()
     }
+    fn create_wide_rows(&self) -> Vec<Row> {
+        self.iter().flat_map(|i| i.create_wide_rows()).collect()
+    }
 }
 
 // GetHeaderRow trait to be implemented by Volume/Pool to fetch the corresponding headers.
@@ -84,30 +196,239 @@ where
             .map(GetHeaderRow::get_header_row)
             .unwrap_or_default()
     }
+    fn get_wide_header_row(&self) -> Row {
+        self.get(0)
+            .map(GetHeaderRow::get_wide_header_row)
+            .unwrap_or_default()
+    }
+    fn key_columns(&self) -> usize {
+        self.get(0).map_or(1, GetHeaderRow::key_columns)
+    }
 }
 
-pub fn print_table<T>(output: &OutputFormat, obj: T)
+/// An object rendered in one of the output formats.
+enum Rendered {
+    /// The text form, viz yaml, json or a JSONPath template.
+    Text(String),
+    /// The tabular form, as the header and the rows.
+    Table(Row, Vec<Row>),
+}
+
+/// Render the object in the given output format, if the format only depends on the serialized
+/// form of the object, viz yaml, json, custom columns or a JSONPath template.
+fn render_serialized<T>(output: &OutputFormat, obj: &T) -> Option<Result<Rendered, String>>
 where
     T: ser::Serialize,
-    T: CreateRows,
-    T: GetHeaderRow,
 {
     match output {
-        OutputFormat::Yaml => {
-            // Show the YAML form output if output format is YAML.
-            let s = serde_yaml::to_string(&obj).unwrap();
-            println!("{}", s);
+        OutputFormat::None | OutputFormat::Wide => None,
+        OutputFormat::Yaml => Some(Ok(Rendered::Text(serde_yaml::to_string(obj).unwrap()))),
+        OutputFormat::Json => Some(Ok(Rendered::Text(serde_json::to_string(obj).unwrap()))),
+        OutputFormat::JsonPath(template) => {
+            // Lists are the items of the root object, as with kubectl.
+            let value = match serde_json::to_value(obj).unwrap() {
+                serde_json::Value::Array(items) => serde_json::json!({ "items": items }),
+                value => value,
+            };
+            Some(template.render(&value).map(Rendered::Text))
         }
-        OutputFormat::Json => {
-            // Show the JSON form output if output format is JSON.
-            let s = serde_json::to_string(&obj).unwrap();
-            println!("{}", s);
+        OutputFormat::CustomColumns(columns) => {
+            let items = match serde_json::to_value(obj).unwrap() {
+                serde_json::Value::Array(items) => items,
+                value => vec![value],
+            };
+            let header = Row::new(columns.names().into_iter().map(Cell::new).collect());
+            let rows = items
+                .iter()
+                .map(|item| {
+                    Row::new(
+                        columns
+                            .cells(item)
+                            .into_iter()
+                            .map(|cell| Cell::new(&optional_cell(cell)))
+                            .collect(),
+                    )
+                })
+                .collect();
+            Some(Ok(Rendered::Table(header, rows)))
         }
-        OutputFormat::None => {
-            // Show the tabular form if output format is not specified.
-            let rows: Vec<Row> = obj.create_rows();
-            let header: Row = obj.get_header_row();
-            table_printer(header, rows);
+    }
+}
+
+/// Render the object in the given output format.
+fn render<T>(output: &OutputFormat, obj: &T) -> Result<Rendered, String>
+where
+    T: ser::Serialize,
+    T: CreateRows,
+    T: GetHeaderRow,
+{
+    match output {
+        OutputFormat::Wide => Ok(Rendered::Table(
+            obj.get_wide_header_row(),
+            obj.create_wide_rows(),
+        )),
+        _ => render_serialized(output, obj)
+            .unwrap_or_else(|| Ok(Rendered::Table(obj.get_header_row(), obj.create_rows()))),
+    }
+}
+
+fn print_rendered(rendered: Result<Rendered, String>) {
+    match rendered {
+        // Show the YAML, JSON or JSONPath form output based on the output format.
+        Ok(Rendered::Text(s)) => println!("{}", s),
+        // Show the tabular form if output format is not specified, is wide or custom columns.
+        Ok(Rendered::Table(header, rows)) => table_printer(header, rows),
+        Err(e) => println!("Failed to print the output. Error {}", e),
+    }
+}
+
+pub fn print_table<T>(output: &OutputFormat, obj: T)
+where
+    T: ser::Serialize,
+    T: CreateRows,
+    T: GetHeaderRow,
+{
+    print_rendered(render(output, &obj))
+}
+
+// Description trait to be implemented by the descriptions of resources, which are shown as
+// text unless the output format selects their serialized form.
+pub trait Description: ser::Serialize {
+    fn print_text(&self);
+}
+
+pub fn print_description<T: Description>(output: &OutputFormat, description: &T) {
+    match render_serialized(output, description) {
+        Some(rendered) => print_rendered(rendered),
+        None => description.print_text(),
+    }
+}
+
+/// Print the fields of a description, as aligned name and value pairs.
+pub fn print_fields(fields: Vec<(&str, String)>) {
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_CLEAN);
+    for (name, value) in fields {
+        table.add_row(row![format!("{}:", name), value]);
+    }
+    table.printstd();
+}
+
+/// Print a section of a description, as a table indented under its title.
+pub fn print_section(title: &str, header: Row, rows: Vec<Row>) {
+    if rows.is_empty() {
+        println!(" {}: {}", title, CELL_NO_CONTENT);
+        return;
+    }
+    println!(" {}:", title);
+    let mut table = Table::new();
+    table.set_format(format::FormatBuilder::new().padding(1, 1).indent(2).build());
+    table.set_titles(header);
+    for row in rows {
+        table.add_row(row);
+    }
+    table.printstd();
+}
+
+/// Poll the resources returned by `fetch` at the given interval, printing them whenever they
+/// change. In tabular form only the rows of the resources which were added, modified or deleted
+/// are printed, prefixed by the time at which the change was seen and by the change.
+/// Runs until the process is interrupted.
+pub async fn watch<T, F, Fut>(output: &OutputFormat, interval: Duration, fetch: F)
+where
+    T: ser::Serialize,
+    T: CreateRows,
+    T: GetHeaderRow,
+    F: Fn() -> Fut,
+    Fut: Future<Output = Option<T>>,
+{
+    let mut previous_rows: Vec<Row> = vec![];
+    let mut previous_output: Option<String> = None;
+    let mut header_printed = false;
+    loop {
+        if let Some(obj) = fetch().await {
+            match render(output, &obj) {
+                Ok(Rendered::Text(s)) => {
+                    // Print the whole document again when anything changed.
+                    if previous_output.as_ref() != Some(&s) {
+                        println!("{}", s);
+                        previous_output = Some(s);
+                    }
+                }
+                Ok(Rendered::Table(header, rows)) => {
+                    // Custom columns need not start with the columns which identify a resource.
+                    let key_columns = match output {
+                        OutputFormat::CustomColumns(_) => 1,
+                        _ => obj.key_columns(),
+                    };
+                    let changed = changed_rows(&previous_rows, &rows, key_columns);
+                    let print_header = !header_printed && !header.is_empty();
+                    if print_header || !changed.is_empty() {
+                        let now =
+                            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+                        let mut table = Table::new();
+                        table.set_format(*format::consts::FORMAT_CLEAN);
+                        if print_header {
+                            table.set_titles(timestamped_row("TIME", "EVENT", header));
+                            header_printed = true;
+                        }
+                        for (change, row) in changed {
+                            table.add_row(timestamped_row(&now, change.as_ref(), row));
+                        }
+                        table.printstd();
+                    }
+                    previous_rows = rows;
+                }
+                Err(e) => {
+                    println!("Failed to print the output. Error {}", e);
+                    return;
+                }
+            }
         }
+        tokio::time::sleep(interval).await;
     }
 }
+
+/// The change of a resource which is seen when watching it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::AsRefStr)]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum RowChange {
+    Added,
+    Modified,
+    Deleted,
+}
+
+/// Get the rows of the resources which were added, modified or deleted since the previously
+/// printed rows, where a resource is identified by the first `key_columns` cells of its row.
+/// A deleted resource is shown by its previously printed row.
+pub fn changed_rows(
+    previous: &[Row],
+    current: &[Row],
+    key_columns: usize,
+) -> Vec<(RowChange, Row)> {
+    let key = |row: &Row| {
+        row.iter()
+            .take(key_columns)
+            .map(Cell::get_content)
+            .collect::<Vec<_>>()
+    };
+    let changed = current
+        .iter()
+        .filter(|row| !previous.contains(row))
+        .map(|row| match previous.iter().any(|p| key(p) == key(row)) {
+            true => (RowChange::Modified, row.clone()),
+            false => (RowChange::Added, row.clone()),
+        });
+    let deleted = previous
+        .iter()
+        .filter(|p| !current.iter().any(|row| key(row) == key(p)))
+        .map(|row| (RowChange::Deleted, row.clone()));
+    changed.chain(deleted).collect()
+}
+
+/// Prefix the row with the cells holding the given timestamp and change.
+fn timestamped_row(timestamp: &str, change: &str, mut row: Row) -> Row {
+    row.insert_cell(0, Cell::new(change));
+    row.insert_cell(0, Cell::new(timestamp));
+    row
+}
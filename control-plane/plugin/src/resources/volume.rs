@@ -1,15 +1,20 @@
 use crate::{
-    operations::{Get, List, Scale},
-    resources::{utils, VolumeId},
+    operations::{Describe, Get, List, Scale},
+    resources::{node, pool, utils, VolumeId},
     rest_wrapper::RestClient,
 };
 use async_trait::async_trait;
 
 use crate::{
     operations::ReplicaTopology,
-    resources::utils::{optional_cell, CreateRows, GetHeaderRow, OutputFormat},
+    resources::{
+        nexus::rebuild_progress,
+        specs::spec_operation,
+        utils::{optional_cell, CreateRows, Description, GetHeaderRow, OutputFormat},
+    },
 };
 use prettytable::Row;
+use serde_derive::Serialize;
 use std::collections::HashMap;
 
 /// Volumes resource.
@@ -31,6 +36,26 @@ impl CreateRows for openapi::models::Volume {
         ]];
         rows
     }
+    fn create_wide_rows(&self) -> Vec<Row> {
+        let state = self.state.clone();
+        let target = state.target.as_ref();
+        let rows = vec![row![
+            state.uuid,
+            self.spec.num_replicas,
+            optional_cell(target.map(|t| t.node.clone())),
+            optional_cell(target.and_then(target_protocol)),
+            state.status,
+            state.size,
+            optional_cell(target.map(|t| t.rebuilds)),
+            optional_cell(target.and_then(|t| rebuild_progress(&t.children))),
+            optional_cell(
+                target
+                    .map(|t| t.device_uri.clone())
+                    .filter(|uri| !uri.is_empty())
+            )
+        ]];
+        rows
+    }
 }
 
 /// Retrieve the protocol from a volume target and return it as an option
@@ -47,15 +72,16 @@ impl GetHeaderRow for openapi::models::Volume {
     fn get_header_row(&self) -> Row {
         (&*utils::VOLUME_HEADERS).clone()
     }
+    fn get_wide_header_row(&self) -> Row {
+        (&*utils::VOLUME_WIDE_HEADERS).clone()
+    }
 }
 
 #[async_trait(?Send)]
 impl List for Volumes {
-    async fn list(output: &utils::OutputFormat) {
-        if let Some(volumes) = get_paginated_volumes().await {
-            // Print table, json or yaml based on output format.
-            utils::print_table(output, volumes);
-        }
+    type Resources = Vec<openapi::models::Volume>;
+    async fn fetch() -> Option<Self::Resources> {
+        get_paginated_volumes().await
     }
 }
 
@@ -97,14 +123,13 @@ pub struct Volume {}
 #[async_trait(?Send)]
 impl Get for Volume {
     type ID = VolumeId;
-    async fn get(id: &Self::ID, output: &utils::OutputFormat) {
+    type Resource = openapi::models::Volume;
+    async fn fetch(id: &Self::ID) -> Option<Self::Resource> {
         match RestClient::client().volumes_api().get_volume(id).await {
-            Ok(volume) => {
-                // Print table, json or yaml based on output format.
-                utils::print_table(output, volume.into_body());
-            }
+            Ok(volume) => Some(volume.into_body()),
             Err(e) => {
-                println!("Failed to get volume {}. Error {}", id, e)
+                println!("Failed to get volume {}. Error {}", id, e);
+                None
             }
         }
     }
@@ -120,14 +145,14 @@ impl Scale for Volume {
             .await
         {
             Ok(volume) => match output {
-                OutputFormat::Yaml | OutputFormat::Json => {
-                    // Print json or yaml based on output format.
-                    utils::print_table(output, volume.into_body());
-                }
-                OutputFormat::None => {
-                    // In case the output format is not specified, show a success message.
+                OutputFormat::None | OutputFormat::Wide => {
+                    // In case the output format is tabular, show a success message.
                     println!("Volume {} Scaled Successfully 🚀", id)
                 }
+                _ => {
+                    // Print json, yaml or the selected fields based on output format.
+                    utils::print_table(output, volume.into_body());
+                }
             },
             Err(e) => {
                 println!("Failed to scale volume {}. Error {}", id, e)
@@ -139,16 +164,11 @@ impl Scale for Volume {
 #[async_trait(?Send)]
 impl ReplicaTopology for Volume {
     type ID = VolumeId;
-    async fn topology(id: &Self::ID, output: &OutputFormat) {
-        match RestClient::client().volumes_api().get_volume(id).await {
-            Ok(volume) => {
-                // Print table, json or yaml based on output format.
-                utils::print_table(output, volume.into_body().state.replica_topology);
-            }
-            Err(e) => {
-                println!("Failed to get volume {}. Error {}", id, e)
-            }
-        }
+    type Topology = HashMap<String, openapi::models::ReplicaTopology>;
+    async fn fetch_topology(id: &Self::ID) -> Option<Self::Topology> {
+        Self::fetch(id)
+            .await
+            .map(|volume| volume.state.replica_topology)
     }
 }
 
@@ -172,3 +192,180 @@ impl CreateRows for HashMap<String, openapi::models::ReplicaTopology> {
         rows
     }
 }
+
+/// Description of a volume, along with the pools and nodes of its target and replicas.
+#[derive(Serialize)]
+pub struct VolumeDescription {
+    volume: openapi::models::Volume,
+    pools: Vec<openapi::models::Pool>,
+    nodes: Vec<openapi::models::Node>,
+}
+
+impl VolumeDescription {
+    /// Describe the volume along with the given pools and nodes which hold its target or replicas.
+    pub(crate) fn new(
+        volume: openapi::models::Volume,
+        pools: Vec<openapi::models::Pool>,
+        nodes: Vec<openapi::models::Node>,
+    ) -> Self {
+        let topology = &volume.state.replica_topology;
+        let target_node = volume.state.target.as_ref().map(|target| &target.node);
+        let pools = pools
+            .into_iter()
+            .filter(|pool| topology.values().any(|r| r.pool.as_ref() == Some(&pool.id)))
+            .collect();
+        let nodes = nodes
+            .into_iter()
+            .filter(|node| {
+                target_node == Some(&node.id)
+                    || topology.values().any(|r| r.node.as_ref() == Some(&node.id))
+            })
+            .collect();
+        Self {
+            volume,
+            pools,
+            nodes,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Describe for Volume {
+    type ID = VolumeId;
+    async fn describe(id: &Self::ID, output: &OutputFormat) {
+        let volume = match Self::fetch(id).await {
+            Some(volume) => volume,
+            None => return,
+        };
+        let (pools, nodes) = match (pool::Pools::fetch().await, node::Nodes::fetch().await) {
+            (Some(pools), Some(nodes)) => (pools, nodes),
+            _ => return,
+        };
+        utils::print_description(output, &VolumeDescription::new(volume, pools, nodes));
+    }
+}
+
+/// Get the fields whose spec differs from their state, as the field name, spec and state.
+pub(crate) fn spec_state_diff(volume: &openapi::models::Volume) -> Vec<(&str, String, String)> {
+    let spec = &volume.spec;
+    let state = &volume.state;
+    let spec_target = spec.target.as_ref();
+    let state_target = state.target.as_ref();
+    let fields = vec![
+        (
+            "replicas",
+            spec.num_replicas.to_string(),
+            state.replica_topology.len().to_string(),
+        ),
+        ("size", spec.size.to_string(), state.size.to_string()),
+        (
+            "target node",
+            optional_cell(spec_target.map(|t| &t.node)),
+            optional_cell(state_target.map(|t| &t.node)),
+        ),
+        (
+            "protocol",
+            optional_cell(spec_target.and_then(|t| t.protocol.as_ref())),
+            optional_cell(state_target.and_then(target_protocol)),
+        ),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, spec, state)| spec != state)
+        .collect()
+}
+
+impl Description for VolumeDescription {
+    fn print_text(&self) {
+        let spec = &self.volume.spec;
+        let state = &self.volume.state;
+        let target = state.target.as_ref();
+        utils::print_fields(vec![
+            ("Name", state.uuid.to_string()),
+            ("Status", state.status.to_string()),
+            ("Size", state.size.to_string()),
+            ("Replicas", spec.num_replicas.to_string()),
+            ("Target Node", optional_cell(target.map(|t| &t.node))),
+            (
+                "Accessibility",
+                optional_cell(target.and_then(target_protocol)),
+            ),
+            ("Spec Status", spec.status.to_string()),
+            (
+                "Pending Operation",
+                optional_cell(spec_operation(&spec.operation)),
+            ),
+        ]);
+
+        let diff = spec_state_diff(&self.volume)
+            .into_iter()
+            .map(|(field, spec, state)| row![field, spec, state])
+            .collect();
+        utils::print_section("Spec vs State", row!["FIELD", "SPEC", "STATE"], diff);
+
+        let children = target
+            .map(|target| {
+                target
+                    .children
+                    .iter()
+                    .map(|child| {
+                        row![
+                            child.uri,
+                            child.state,
+                            optional_cell(child.rebuild_progress.map(|p| format!("{}%", p)))
+                        ]
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        utils::print_section(
+            "Target Children",
+            row!["URI", "STATE", "REBUILD-PROGRESS"],
+            children,
+        );
+
+        let mut replicas = state.replica_topology.iter().collect::<Vec<_>>();
+        replicas.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let replicas = replicas
+            .into_iter()
+            .map(|(id, replica)| {
+                let pool = self
+                    .pools
+                    .iter()
+                    .find(|pool| replica.pool.as_ref() == Some(&pool.id));
+                row![
+                    id,
+                    optional_cell(replica.node.as_ref()),
+                    optional_cell(replica.pool.as_ref()),
+                    replica.state,
+                    optional_cell(pool.and_then(pool::pool_free_space))
+                ]
+            })
+            .collect();
+        utils::print_section(
+            "Replicas",
+            row!["ID", "NODE", "POOL", "STATUS", "POOL-FREE-SPACE"],
+            replicas,
+        );
+
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let mut roles = vec![];
+                if target.map(|t| &t.node) == Some(&node.id) {
+                    roles.push("target");
+                }
+                if state
+                    .replica_topology
+                    .values()
+                    .any(|r| r.node.as_ref() == Some(&node.id))
+                {
+                    roles.push("replica");
+                }
+                row![node.id, roles.join(", "), node::node_status(node)]
+            })
+            .collect();
+        utils::print_section("Nodes", row!["ID", "ROLE", "STATUS"], nodes);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{
+    operations::{Get, List, MoveReplica, RebuildProgress, RemoveReplica},
+    resources::{
+        drain::{progress, wait_volume},
+        replica,
+        utils::{self, optional_cell, CreateRows, GetHeaderRow, OutputFormat},
+        volume, PoolId, ReplicaId, VolumeId,
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use openapi::models;
+use prettytable::Row;
+use serde_derive::Serialize;
+use tokio::time::Instant;
+
+/// Arguments used when moving a replica of a volume.
+#[derive(clap::Args, Debug)]
+pub struct MoveVolumeReplicaArgs {
+    /// ID of the volume.
+    id: VolumeId,
+    /// ID of the replica to move.
+    replica: ReplicaId,
+    /// ID of the pool to move the replica to.
+    pool: PoolId,
+    /// Give up if the new replica is not rebuilt within this period.
+    #[clap(long, default_value = "10m")]
+    timeout: humantime::Duration,
+}
+
+/// The exit code used when the replica could not be moved, eg: on timeout.
+const MOVE_FAILED_EXIT_CODE: i32 = 1;
+
+/// A child of the volume target, along with the replica it corresponds to.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct VolumeChild {
+    /// Replica of the volume which is the child, if known.
+    replica: Option<ReplicaId>,
+    node: Option<String>,
+    pool: Option<String>,
+    child: models::Child,
+}
+
+impl CreateRows for VolumeChild {
+    fn create_rows(&self) -> Vec<Row> {
+        vec![row![
+            optional_cell(self.replica),
+            optional_cell(self.node.as_ref()),
+            optional_cell(self.pool.as_ref()),
+            self.child.state,
+            optional_cell(self.child.rebuild_progress.map(|p| format!("{}%", p)))
+        ]]
+    }
+    fn create_wide_rows(&self) -> Vec<Row> {
+        vec![row![
+            optional_cell(self.replica),
+            optional_cell(self.node.as_ref()),
+            optional_cell(self.pool.as_ref()),
+            self.child.state,
+            optional_cell(self.child.rebuild_progress.map(|p| format!("{}%", p))),
+            self.child.uri
+        ]]
+    }
+}
+
+impl GetHeaderRow for VolumeChild {
+    fn get_header_row(&self) -> Row {
+        (&*utils::REBUILD_PROGRESS_HEADERS).clone()
+    }
+    fn get_wide_header_row(&self) -> Row {
+        (&*utils::REBUILD_PROGRESS_WIDE_HEADERS).clone()
+    }
+}
+
+/// Get the children of the volume target, matched with the replicas through their uri.
+pub(crate) fn volume_children(
+    volume: &models::Volume,
+    replicas: &[models::Replica],
+) -> Vec<VolumeChild> {
+    let children = volume
+        .state
+        .target
+        .as_ref()
+        .map(|target| target.children.as_slice())
+        .unwrap_or_default();
+    children
+        .iter()
+        .map(|child| {
+            let replica = replicas.iter().find(|replica| replica.uri == child.uri);
+            VolumeChild {
+                replica: replica.map(|replica| replica.uuid),
+                node: replica.map(|replica| replica.node.clone()),
+                pool: replica.map(|replica| replica.pool.clone()),
+                child: child.clone(),
+            }
+        })
+        .collect()
+}
+
+#[async_trait(?Send)]
+impl RebuildProgress for volume::Volume {
+    type ID = VolumeId;
+    type Progress = Vec<VolumeChild>;
+    async fn fetch_rebuild_progress(id: &Self::ID) -> Option<Self::Progress> {
+        let volume = Self::fetch(id).await?;
+        if volume.state.target.is_none() {
+            println!("Volume {} is not published", id);
+            return None;
+        }
+        let replicas = replica::Replicas::fetch().await?;
+        Some(volume_children(&volume, &replicas))
+    }
+}
+
+#[async_trait(?Send)]
+impl RemoveReplica for volume::Volume {
+    type ID = VolumeId;
+    type ReplicaID = ReplicaId;
+    async fn remove_replica(id: &Self::ID, replica: &Self::ReplicaID, output: &OutputFormat) {
+        match RestClient::client()
+            .volumes_api()
+            .del_volume_replica(id, replica)
+            .await
+        {
+            Ok(volume) => match output {
+                OutputFormat::None | OutputFormat::Wide => {
+                    // In case the output format is tabular, show a success message.
+                    println!("Volume {} Replica {} Removed Successfully 🚀", id, replica)
+                }
+                _ => {
+                    // Print json, yaml or the selected fields based on output format.
+                    utils::print_table(output, volume.into_body());
+                }
+            },
+            Err(e) => {
+                println!(
+                    "Failed to remove replica {} of volume {}. Error {}",
+                    replica, id, e
+                )
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl MoveReplica for volume::Volume {
+    type Args = MoveVolumeReplicaArgs;
+    async fn move_replica(args: &Self::Args, output: &OutputFormat) -> i32 {
+        match move_replica(args, output).await {
+            Ok(volume) => {
+                match output {
+                    OutputFormat::None | OutputFormat::Wide => println!(
+                        "Volume {} Replica {} Moved Successfully 🚀",
+                        args.id, args.replica
+                    ),
+                    _ => utils::print_table(output, volume),
+                }
+                0
+            }
+            Err(error) => {
+                println!(
+                    "Failed to move replica {} of volume {}. {}",
+                    args.replica, args.id, error
+                );
+                MOVE_FAILED_EXIT_CODE
+            }
+        }
+    }
+}
+
+/// Move the replica of the volume to the pool, by adding a replica on the pool and removing the
+/// given replica once the new replica is rebuilt.
+/// If the new replica is not rebuilt in time, it's removed again.
+async fn move_replica(
+    args: &MoveVolumeReplicaArgs,
+    output: &OutputFormat,
+) -> Result<models::Volume, String> {
+    let volume = volume::Volume::fetch(&args.id)
+        .await
+        .ok_or_else(|| "Error failed to get the volume".to_string())?;
+    if !volume
+        .state
+        .replica_topology
+        .contains_key(&args.replica.to_string())
+    {
+        return Err("Error the replica does not belong to the volume".to_string());
+    }
+    if volume.state.target.is_none() {
+        // without a target the new replica can't be rebuilt from the existing ones
+        return Err("Error the volume is not published".to_string());
+    }
+
+    let volumes_api = RestClient::client().volumes_api();
+    let volume = volumes_api
+        .put_volume_pool_replica(&args.id, &args.pool)
+        .await
+        .map_err(|e| format!("Error {}", e))?
+        .into_body();
+    progress(
+        output,
+        format!("Volume {}: added a replica on pool {}", args.id, args.pool),
+    );
+
+    let deadline = Instant::now() + *args.timeout;
+    let replicas = volume.spec.num_replicas as usize;
+    let rebuilt = wait_volume(&args.id, deadline, output, "the new replica", |volume| {
+        let children = volume
+            .state
+            .target
+            .as_ref()
+            .map(|target| target.children.as_slice())
+            .unwrap_or_default();
+        children.len() >= replicas
+            && children
+                .iter()
+                .all(|child| child.state == models::ChildState::Online)
+    })
+    .await;
+
+    if let Err(error) = rebuilt {
+        // remove the new replica, so the volume is back to its previous replica count
+        let new_replica = volume
+            .state
+            .replica_topology
+            .iter()
+            .find(|(_, topology)| topology.pool.as_ref() == Some(&args.pool))
+            .and_then(|(id, _)| id.parse::<ReplicaId>().ok());
+        if let Some(new_replica) = new_replica {
+            progress(
+                output,
+                format!(
+                    "Volume {}: removing the new replica {}",
+                    args.id, new_replica
+                ),
+            );
+            if let Err(e) = volumes_api.del_volume_replica(&args.id, &new_replica).await {
+                println!(
+                    "Failed to remove the new replica {} of volume {}. Error {}",
+                    new_replica, args.id, e
+                );
+            }
+        }
+        return Err(error);
+    }
+
+    volumes_api
+        .del_volume_replica(&args.id, &args.replica)
+        .await
+        .map(|volume| volume.into_body())
+        .map_err(|e| format!("Error {}", e))
+}
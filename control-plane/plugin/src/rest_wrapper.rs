@@ -4,27 +4,64 @@ use openapi::tower::client::{ApiClient, Configuration, Url};
 
 static REST_SERVER: OnceCell<RestClient> = OnceCell::new();
 
+/// Authentication used by the REST client, for REST servers with authentication enabled.
+#[derive(Debug, Default, Clone)]
+pub struct RestAuth {
+    /// Bearer token sent with every request.
+    pub bearer_token: Option<String>,
+    /// PEM encoded certificate of the CA which signed the certificate of the REST server.
+    pub ca_certificate: Option<Vec<u8>>,
+}
+
 /// REST client
 pub struct RestClient {
     url: Url,
+    auth: RestAuth,
     client: ApiClient,
 }
 
 impl RestClient {
     /// Initialise the URL of the REST server.
     pub fn init(url: Url, timeout: std::time::Duration) -> Result<()> {
-        REST_SERVER.get_or_try_init(|| Self::new(url, timeout))?;
+        Self::init_with_auth(url, timeout, RestAuth::default())
+    }
+
+    /// Initialise the URL of the REST server, along with the authentication to use.
+    pub fn init_with_auth(url: Url, timeout: std::time::Duration, auth: RestAuth) -> Result<()> {
+        REST_SERVER.get_or_try_init(|| Self::new_with_auth(url, timeout, auth))?;
         Ok(())
     }
 
     /// Create new Rest Client.
-    pub fn new(mut url: Url, timeout: std::time::Duration) -> Result<RestClient> {
-        // TODO: Support HTTPS Certificates
+    pub fn new(url: Url, timeout: std::time::Duration) -> Result<RestClient> {
+        Self::new_with_auth(url, timeout, RestAuth::default())
+    }
+
+    /// Create new Rest Client which authenticates with the bearer token and verifies the REST
+    /// server against the CA certificate, if given.
+    pub fn new_with_auth(
+        mut url: Url,
+        timeout: std::time::Duration,
+        auth: RestAuth,
+    ) -> Result<RestClient> {
         if url.port().is_none() {
             url.set_port(Some(30011))
                 .map_err(|_| anyhow::anyhow!("Failed to set REST client port"))?;
         }
-        let cfg = Configuration::new(url.clone(), timeout, None, None, true).map_err(|error| {
+        if auth.ca_certificate.is_some() && url.scheme() != "https" {
+            return Err(anyhow::anyhow!(
+                "A CA certificate was given but the REST URL '{}' is not https",
+                url
+            ));
+        }
+        let cfg = Configuration::new(
+            url.clone(),
+            timeout,
+            auth.bearer_token.clone(),
+            auth.ca_certificate.as_deref(),
+            true,
+        )
+        .map_err(|error| {
             anyhow::anyhow!(
                 "Failed to create openapi configuration, Error: '{:?}'",
                 error
@@ -32,6 +69,7 @@ impl RestClient {
         })?;
         Ok(Self {
             url,
+            auth,
             client: ApiClient::new(cfg),
         })
     }
@@ -50,4 +88,9 @@ impl RestClient {
     pub fn url(&self) -> &Url {
         &self.url
     }
+
+    /// Get the authentication used with the REST server.
+    pub fn auth(&self) -> &RestAuth {
+        &self.auth
+    }
 }
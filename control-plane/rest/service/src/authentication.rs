@@ -121,11 +121,43 @@ pub fn authenticate(req: &HttpRequest) -> Result<(), AuthError> {
     }
 
     match req.headers().get(http::header::AUTHORIZATION) {
-        Some(token) => jwk.validate(&format_token(token)?, &req.uri().to_string()),
+        Some(token) => {
+            let token = format_token(token)?;
+            jwk.validate(&token, &req.uri().to_string())?;
+            req.extensions_mut().insert(Principal::from_token(&token));
+            Ok(())
+        }
         None => Err(AuthError::NoBearerToken {}),
     }
 }
 
+/// Principal of an authenticated request, ie: the subject of its json web token.
+/// It's added to the request extensions once the request is authenticated.
+#[derive(Clone, Debug)]
+pub(crate) struct Principal(String);
+impl Principal {
+    /// Get the principal from an already validated `token`
+    fn from_token(token: &str) -> Self {
+        #[derive(serde::Deserialize)]
+        struct Claims {
+            sub: Option<String>,
+        }
+        match jsonwebtoken::dangerous_insecure_decode::<Claims>(token) {
+            Ok(data) => Self(data.claims.sub.unwrap_or_else(|| "unknown".to_string())),
+            Err(_) => Self("unknown".to_string()),
+        }
+    }
+    /// Get the principal of the authenticated `request`, if any
+    pub(crate) fn from_request(request: &HttpRequest) -> Option<Self> {
+        request.extensions().get::<Self>().cloned()
+    }
+}
+impl std::fmt::Display for Principal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 // Ensure the token is formatted correctly by removing the "Bearer " prefix if
 // present.
 fn format_token(token: &HeaderValue) -> Result<String, AuthError> {
@@ -1,12 +1,13 @@
 mod authentication;
+mod metrics;
 mod v0;
 
 use crate::v0::{CORE_CLIENT, JSON_GRPC_CLIENT};
 use actix_service::ServiceFactory;
 use actix_web::{
     body::MessageBody,
-    dev::{ServiceRequest, ServiceResponse},
-    middleware, App, HttpServer,
+    dev::{Service, ServiceRequest, ServiceResponse},
+    middleware, web, App, HttpServer,
 };
 use rustls::{Certificate, PrivateKey, ServerConfig};
 use rustls_pemfile::{certs, rsa_private_keys};
@@ -70,6 +71,14 @@ pub(crate) struct CliArgs {
     /// Don't use minimum timeouts for specific requests
     #[structopt(long)]
     no_min_timeouts: bool,
+
+    /// Don't serve the prometheus metrics on the `/metrics` path
+    #[structopt(long)]
+    no_metrics: bool,
+
+    /// Log every request as a json access log line, rather than the default log format
+    #[structopt(long)]
+    json_access_log: bool,
 }
 impl CliArgs {
     fn args() -> Self {
@@ -188,11 +197,41 @@ async fn main() -> anyhow::Result<()> {
         cli_args.jaeger.clone(),
     );
 
+    let json_access_log = cli_args.json_access_log;
+    let no_metrics = cli_args.no_metrics;
+    let observer = metrics::RequestObserver::new(json_access_log);
     let app = move || {
         App::new()
             .wrap(RequestTracing::new())
-            .wrap(middleware::Logger::default())
+            .wrap(middleware::Condition::new(
+                !json_access_log,
+                middleware::Logger::default(),
+            ))
+            .wrap_fn(move |request, service| {
+                let start = std::time::Instant::now();
+                // the request must not be cloned before it's routed, as the router needs sole
+                // ownership to match it, so it's observed through its response instead
+                let method = request.method().clone();
+                let path = request.path().to_string();
+                let response = service.call(request);
+                async move {
+                    let response = response.await;
+                    match &response {
+                        Ok(response) => observer.observe(response, start),
+                        Err(error) => {
+                            let status = error.as_response_error().status_code();
+                            observer.observe_error(&method, &path, status, start)
+                        }
+                    }
+                    response
+                }
+            })
             .app_data(authentication::init(get_jwk_path()))
+            .configure(|cfg| {
+                if !no_metrics {
+                    cfg.route("/metrics", web::get().to(metrics::metrics));
+                }
+            })
             .configure_api(&v0::configure_api)
     };
 
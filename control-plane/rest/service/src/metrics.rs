@@ -0,0 +1,142 @@
+//! Prometheus metrics and json access logging of the REST requests.
+
+use crate::authentication::Principal;
+use actix_web::{
+    dev::ServiceResponse,
+    http::{Method, StatusCode},
+    HttpResponse,
+};
+use once_cell::sync::Lazy;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, Encoder, HistogramVec, IntCounterVec,
+    TextEncoder,
+};
+use serde::Serialize;
+use std::time::{Instant, SystemTime};
+
+static REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "rest_requests_total",
+        "Number of REST requests by route, status code and principal",
+        &["method", "route", "status", "principal"]
+    )
+    .expect("Should register the requests metric")
+});
+static REQUEST_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "rest_request_duration_seconds",
+        "Duration of the REST requests by route",
+        &["method", "route"]
+    )
+    .expect("Should register the request duration metric")
+});
+
+/// Route label used for requests which don't match any of the routes, as using their path would
+/// lead to an unbounded number of labels
+const UNMATCHED_ROUTE: &str = "unmatched";
+/// Principal label used for requests which are not authenticated
+const ANONYMOUS_PRINCIPAL: &str = "anonymous";
+
+/// Observes every completed REST request, recording its metrics and optionally logging it as a
+/// json access log line
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RequestObserver {
+    json_access_log: bool,
+}
+
+/// A json access log entry
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccessLog<'a> {
+    time: String,
+    remote_addr: Option<&'a str>,
+    method: &'a str,
+    path: &'a str,
+    route: &'a str,
+    status: u16,
+    duration_ms: f64,
+    principal: Option<String>,
+    user_agent: Option<&'a str>,
+}
+
+impl RequestObserver {
+    /// Create a new `Self`, which also logs the requests as json if `json_access_log` is set
+    pub(crate) fn new(json_access_log: bool) -> Self {
+        Self { json_access_log }
+    }
+
+    /// Record the `response` of a request which started at `start`
+    pub(crate) fn observe<B>(&self, response: &ServiceResponse<B>, start: Instant) {
+        let request = response.request();
+        let route = request.match_pattern();
+        let connection = request.connection_info();
+        self.record(AccessLog {
+            time: humantime::format_rfc3339_millis(SystemTime::now()).to_string(),
+            remote_addr: connection.realip_remote_addr(),
+            method: request.method().as_str(),
+            path: request.path(),
+            route: route.as_deref().unwrap_or(UNMATCHED_ROUTE),
+            status: response.status().as_u16(),
+            duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+            principal: Principal::from_request(request).map(|p| p.to_string()),
+            user_agent: request
+                .headers()
+                .get(http::header::USER_AGENT)
+                .and_then(|agent| agent.to_str().ok()),
+        });
+    }
+
+    /// Record a request with the `method` and `path` which started at `start` and which failed
+    /// with the `status` code before a response was produced
+    pub(crate) fn observe_error(
+        &self,
+        method: &Method,
+        path: &str,
+        status: StatusCode,
+        start: Instant,
+    ) {
+        self.record(AccessLog {
+            time: humantime::format_rfc3339_millis(SystemTime::now()).to_string(),
+            remote_addr: None,
+            method: method.as_str(),
+            path,
+            route: UNMATCHED_ROUTE,
+            status: status.as_u16(),
+            duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+            principal: None,
+            user_agent: None,
+        });
+    }
+
+    fn record(&self, log: AccessLog) {
+        REQUESTS
+            .with_label_values(&[
+                log.method,
+                log.route,
+                &log.status.to_string(),
+                log.principal.as_deref().unwrap_or(ANONYMOUS_PRINCIPAL),
+            ])
+            .inc();
+        REQUEST_DURATION
+            .with_label_values(&[log.method, log.route])
+            .observe(log.duration_ms / 1000.0);
+
+        if self.json_access_log {
+            if let Ok(log) = serde_json::to_string(&log) {
+                tracing::info!(target: "access_log", "{}", log);
+            }
+        }
+    }
+}
+
+/// Serve all the registered metrics in the prometheus text format
+pub(crate) async fn metrics() -> HttpResponse {
+    let encoder = TextEncoder::new();
+    let mut buffer = vec![];
+    match encoder.encode(&prometheus::gather(), &mut buffer) {
+        Ok(_) => HttpResponse::Ok()
+            .content_type(encoder.format_type())
+            .body(buffer),
+        Err(error) => HttpResponse::InternalServerError().body(error.to_string()),
+    }
+}
@@ -0,0 +1,47 @@
+use super::*;
+use common_lib::types::v0::message_bus::SetReconcilerPaused;
+use grpc::operations::registry::traits::RegistryOperations;
+
+fn client() -> impl RegistryOperations {
+    core_grpc().registry()
+}
+
+#[async_trait::async_trait]
+impl apis::actix_server::Core for RestApi {
+    async fn get_core_health() -> Result<models::CoreHealth, RestError<RestJsonError>> {
+        let health = client().get_core_health(None).await?;
+        Ok(health.into())
+    }
+
+    async fn get_core_reconcilers() -> Result<Vec<models::Reconciler>, RestError<RestJsonError>> {
+        let reconcilers = client().get_reconcilers(None).await?;
+        Ok(reconcilers.into())
+    }
+
+    async fn put_core_reconcilers_poll() -> Result<(), RestError<RestJsonError>> {
+        client().poll_reconcilers(None).await?;
+        Ok(())
+    }
+
+    async fn put_core_reconciler_pause(
+        Path(reconciler_name): Path<String>,
+    ) -> Result<models::Reconciler, RestError<RestJsonError>> {
+        set_reconciler_paused(reconciler_name, true).await
+    }
+
+    async fn put_core_reconciler_resume(
+        Path(reconciler_name): Path<String>,
+    ) -> Result<models::Reconciler, RestError<RestJsonError>> {
+        set_reconciler_paused(reconciler_name, false).await
+    }
+}
+
+async fn set_reconciler_paused(
+    name: String,
+    paused: bool,
+) -> Result<models::Reconciler, RestError<RestJsonError>> {
+    let reconciler = client()
+        .set_reconciler_paused(&SetReconcilerPaused { name, paused }, None)
+        .await?;
+    Ok(reconciler.into())
+}
@@ -4,6 +4,7 @@
 
 pub mod block_devices;
 pub mod children;
+pub mod core_agent;
 pub mod jsongrpc;
 pub mod nexuses;
 pub mod nodes;
@@ -1,4 +1,5 @@
 use super::*;
+use common_lib::types::v0::message_bus::{CordonNode, UncordonNode};
 use grpc::operations::node::traits::NodeOperations;
 
 fn client() -> impl NodeOperations {
@@ -23,6 +24,22 @@ impl apis::actix_server::Nodes for RestApi {
         let nodes = client().get(Filter::None, None).await?;
         Ok(nodes.into_inner().into_vec())
     }
+
+    async fn put_node_cordon(
+        Path(id): Path<String>,
+    ) -> Result<models::Node, RestError<RestJsonError>> {
+        let node = client().cordon(&CordonNode::new(&id.into()), None).await?;
+        Ok(node.into())
+    }
+
+    async fn delete_node_cordon(
+        Path(id): Path<String>,
+    ) -> Result<models::Node, RestError<RestJsonError>> {
+        let node = client()
+            .uncordon(&UncordonNode::new(&id.into()), None)
+            .await?;
+        Ok(node.into())
+    }
 }
 
 /// returns node from node option and returns an error on non existence
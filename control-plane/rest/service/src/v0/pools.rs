@@ -1,5 +1,5 @@
 use super::*;
-use common_lib::types::v0::message_bus::{DestroyPool, Filter};
+use common_lib::types::v0::message_bus::{DestroyPool, Filter, LabelPool};
 use grpc::operations::pool::traits::PoolOperations;
 use mbus_api::{message_bus::v0::BusError, ReplyErrorKind, ResourceKind};
 
@@ -97,6 +97,24 @@ impl apis::actix_server::Pools for RestApi {
         let pool = client().create(&create, None).await?;
         Ok(pool.into())
     }
+
+    async fn put_pool_label(
+        Path(pool_id): Path<String>,
+        Body(label_pool_body): Body<models::LabelPoolBody>,
+    ) -> Result<models::Pool, RestError<RestJsonError>> {
+        let pool = client()
+            .label(
+                &LabelPool::new(
+                    &pool_id.into(),
+                    label_pool_body.labels.unwrap_or_default(),
+                    label_pool_body.remove_labels.unwrap_or_default(),
+                    label_pool_body.overwrite.unwrap_or_default(),
+                ),
+                None,
+            )
+            .await?;
+        Ok(pool.into())
+    }
 }
 
 /// returns pool from pool option and returns an error on non existence
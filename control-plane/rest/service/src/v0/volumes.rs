@@ -1,12 +1,14 @@
 use super::*;
 use common_lib::types::v0::{
     message_bus::{
-        DestroyVolume, Filter, PublishVolume, SetVolumeReplica, ShareVolume, UnpublishVolume,
-        UnshareVolume, Volume,
+        CreateVolumeReplica, DestroyVolume, ExplicitNodeTopology, Filter, GetVolumeCapacity,
+        LabelledTopology, NodeTopology, PoolTopology, PublishVolume, RemoveVolumeReplica,
+        SetVolumeReplica, ShareVolume, Topology, UnpublishVolume, UnshareVolume, Volume,
     },
     openapi::{apis::Uuid, models::VolumeShareProtocol},
 };
 use grpc::operations::{volume::traits::VolumeOperations, MaxEntries, Pagination, StartingToken};
+use std::collections::HashMap;
 
 fn client() -> impl VolumeOperations {
     core_grpc().volume()
@@ -38,6 +40,21 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(())
     }
 
+    async fn del_volume_replica(
+        Path((volume_id, replica_id)): Path<(Uuid, Uuid)>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .remove_replica(
+                &RemoveVolumeReplica {
+                    uuid: volume_id.into(),
+                    replica: replica_id.into(),
+                },
+                None,
+            )
+            .await?;
+        Ok(volume.into())
+    }
+
     async fn del_volume_target(
         Path(volume_id): Path<Uuid>,
         Query(force): Query<Option<bool>>,
@@ -65,6 +82,35 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(volume.into())
     }
 
+    async fn get_volume_capacity(
+        Query((replicas, allowed_nodes, pool_labels)): Query<(u8, Option<String>, Option<String>)>,
+    ) -> Result<models::VolumeCapacity, RestError<RestJsonError>> {
+        let node = allowed_nodes.map(|allowed_nodes| {
+            NodeTopology::Explicit(ExplicitNodeTopology {
+                allowed_nodes: allowed_nodes
+                    .split(',')
+                    .filter(|node| !node.is_empty())
+                    .map(Into::into)
+                    .collect(),
+                preferred_nodes: vec![],
+            })
+        });
+        let pool = labels("pool_labels", pool_labels)?.map(|inclusion| {
+            PoolTopology::Labelled(LabelledTopology {
+                exclusion: Default::default(),
+                inclusion,
+            })
+        });
+        let topology = match (&node, &pool) {
+            (None, None) => None,
+            _ => Some(Topology { node, pool }),
+        };
+        let capacity = client()
+            .get_capacity(&GetVolumeCapacity::new(replicas.into(), topology), None)
+            .await?;
+        Ok(capacity.into())
+    }
+
     async fn get_volumes(
         Query((max_entries, starting_token)): Query<(isize, Option<isize>)>,
     ) -> Result<models::Volumes, RestError<RestJsonError>> {
@@ -96,6 +142,21 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(volume.into())
     }
 
+    async fn put_volume_pool_replica(
+        Path((volume_id, pool_id)): Path<(Uuid, String)>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .create_replica(
+                &CreateVolumeReplica {
+                    uuid: volume_id.into(),
+                    pool: pool_id.into(),
+                },
+                None,
+            )
+            .await?;
+        Ok(volume.into())
+    }
+
     async fn put_volume_replica_count(
         Path((volume_id, replica_count)): Path<(Uuid, u8)>,
     ) -> Result<models::Volume, RestError<RestJsonError>> {
@@ -142,6 +203,47 @@ impl apis::actix_server::Volumes for RestApi {
             .await?;
         Ok(volume.into())
     }
+
+    async fn put_volume_target_auto(
+        Path(volume_id): Path<Uuid>,
+        Query(protocol): Query<VolumeShareProtocol>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .publish(
+                &PublishVolume {
+                    uuid: volume_id.into(),
+                    target_node: None,
+                    share: Some(protocol.into()),
+                },
+                None,
+            )
+            .await?;
+        Ok(volume.into())
+    }
+}
+
+/// parses a comma separated list of key=value labels, if any were specified
+fn labels(
+    arg_name: &str,
+    labels: Option<String>,
+) -> Result<Option<HashMap<String, String>>, ReplyError> {
+    let labels = match labels {
+        Some(labels) => labels,
+        None => return Ok(None),
+    };
+    labels
+        .split(',')
+        .filter(|label| !label.is_empty())
+        .map(|label| match label.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+            _ => Err(ReplyError::invalid_argument(
+                ResourceKind::Volume,
+                arg_name,
+                format!("Label '{}' is not in the key=value format", label),
+            )),
+        })
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map(Some)
 }
 
 /// returns volume from volume option and returns an error on non existence
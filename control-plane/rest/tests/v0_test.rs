@@ -91,6 +91,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
     let mut node = models::Node {
         id: io_engine1.to_string(),
         spec: Some(models::NodeSpec {
+            cordoned: false,
             id: io_engine1.to_string(),
             grpc_endpoint: format!(
                 "{}:10124",
@@ -446,3 +447,40 @@ async fn client_invalid_token() {
     };
     assert!(unauthorized);
 }
+
+#[tokio::test]
+async fn client_metrics() {
+    let _cluster = test_setup(&true).await;
+
+    let client = RestClient::new("https://localhost:8080", true, Some(bearer_token()))
+        .unwrap()
+        .v00();
+    client.nodes_api().get_nodes().await.unwrap();
+    let anonymous = RestClient::new("https://localhost:8080", true, None)
+        .unwrap()
+        .v00();
+    anonymous
+        .nodes_api()
+        .get_nodes()
+        .await
+        .expect_err("Request should fail without a token");
+
+    let metrics = reqwest::get("http://localhost:8081/metrics")
+        .await
+        .expect("Should serve the metrics")
+        .text()
+        .await
+        .unwrap();
+    tracing::info!("Metrics: {}", metrics);
+    let requests = |status: &str, principal: &str| {
+        metrics.lines().any(|line| {
+            line.starts_with("rest_requests_total")
+                && line.contains("route=\"/v0/nodes\"")
+                && line.contains(&format!("status=\"{}\"", status))
+                && line.contains(&format!("principal=\"{}\"", principal))
+        })
+    };
+    // the principal is the subject of the bearer token
+    assert!(requests("200", "random subject"));
+    assert!(requests("401", "anonymous"));
+}
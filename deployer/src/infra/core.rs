@@ -10,7 +10,45 @@ impl ComponentAction for Core {
                 .status()?;
             build_error(&format!("the {} agent", name), status.code())?;
         }
-        let mut binary = Binary::from_dbg(name);
+        let cfg = cfg.add_container_bin(name, Self::binary(options, &cfg));
+        Ok(match options.core_standby {
+            true => {
+                let standby = Self::binary(options, &cfg).with_arg("--standby");
+                cfg.add_container_bin("core-standby", standby)
+            }
+            false => cfg,
+        })
+    }
+    async fn start(&self, options: &StartOptions, cfg: &ComposeTest) -> Result<(), Error> {
+        cfg.start("core").await?;
+        if options.core_standby {
+            cfg.start("core-standby").await?;
+        }
+        Ok(())
+    }
+    async fn wait_on(&self, options: &StartOptions, cfg: &ComposeTest) -> Result<(), Error> {
+        if !options.no_nats {
+            Liveness {}.request_on_bus(ChannelVs::Core, bus()).await?;
+        } else {
+            let ip = cfg.container_ip("core");
+            let uri = tonic::transport::Uri::from_str(&format!("https://{}:50051", ip)).unwrap();
+            let timeout = grpc::context::TimeoutOptions::new()
+                .with_timeout(std::time::Duration::from_millis(100));
+            let core =
+                grpc::client::CoreClient::new(uri, Some(timeout.with_max_retries(Some(10)))).await;
+            core.wait_ready(None).await.map_err(|_| {
+                let error = "Failed to wait for core to get ready";
+                std::io::Error::new(std::io::ErrorKind::TimedOut, error)
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl Core {
+    /// The core agent binary, configured with the start `options`.
+    fn binary(options: &StartOptions, cfg: &Builder) -> Binary {
+        let mut binary = Binary::from_dbg("core");
         if !options.no_nats {
             binary = binary.with_nats("-n");
         }
@@ -59,27 +97,6 @@ impl ComponentAction for Core {
         if let Some(max_rebuilds) = &options.max_rebuilds {
             binary = binary.with_args(vec!["--max-rebuilds", &max_rebuilds.to_string()]);
         }
-        Ok(cfg.add_container_bin(name, binary))
-    }
-    async fn start(&self, _options: &StartOptions, cfg: &ComposeTest) -> Result<(), Error> {
-        cfg.start("core").await?;
-        Ok(())
-    }
-    async fn wait_on(&self, options: &StartOptions, cfg: &ComposeTest) -> Result<(), Error> {
-        if !options.no_nats {
-            Liveness {}.request_on_bus(ChannelVs::Core, bus()).await?;
-        } else {
-            let ip = cfg.container_ip("core");
-            let uri = tonic::transport::Uri::from_str(&format!("https://{}:50051", ip)).unwrap();
-            let timeout = grpc::context::TimeoutOptions::new()
-                .with_timeout(std::time::Duration::from_millis(100));
-            let core =
-                grpc::client::CoreClient::new(uri, Some(timeout.with_max_retries(Some(10)))).await;
-            core.wait_ready(None).await.map_err(|_| {
-                let error = "Failed to wait for core to get ready";
-                std::io::Error::new(std::io::ErrorKind::TimedOut, error)
-            })?;
-        }
-        Ok(())
+        binary
     }
 }
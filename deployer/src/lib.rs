@@ -231,6 +231,11 @@ pub struct StartOptions {
     #[structopt(long)]
     pub store_lease_ttl: Option<humantime::Duration>,
 
+    /// Start a standby core agent alongside the core agent, which takes over once the lease of
+    /// the core agent expires
+    #[structopt(long)]
+    pub core_standby: bool,
+
     /// Override the core agent's reconcile period
     #[structopt(long)]
     pub reconcile_period: Option<humantime::Duration>,
@@ -333,6 +338,11 @@ impl StartOptions {
         self
     }
     #[must_use]
+    pub fn with_core_standby(mut self, standby: bool) -> Self {
+        self.core_standby = standby;
+        self
+    }
+    #[must_use]
     pub fn with_reconcile_period(mut self, busy: Duration, idle: Duration) -> Self {
         self.reconcile_period = Some(busy.into());
         self.reconcile_idle_period = Some(idle.into());
@@ -3,9 +3,17 @@ use clap::Parser;
 use openapi::tower::client::Url;
 use opentelemetry::global;
 use plugin::{
-    operations::{Get, List, ReplicaTopology, Scale},
-    resources::{node, pool, volume, GetResources, ScaleResources},
-    rest_wrapper::RestClient,
+    config::{Context, PluginConfig},
+    operations::{
+        Cordon, Create, Delete, Describe, Drain, Get, Label, List, MoveReplica, RebuildProgress,
+        RemoveReplica, ReplicaTopology, Scale,
+    },
+    resources::{
+        blockdevice, check, nexus, node, pool, replica, specs, usage, volume, CordonResources,
+        CreateResources, DeleteResources, DescribeResources, DrainResources, GetResources,
+        LabelResources, MoveResources, RemoveResources, ScaleResources, UncordonResources,
+    },
+    rest_wrapper::{RestAuth, RestClient},
 };
 use std::{
     env,
@@ -30,18 +38,27 @@ struct CliArgs {
     #[clap(subcommand)]
     operations: Operations,
 
-    /// The Output, viz yaml, json.
-    #[clap(global = true, default_value = plugin::resources::utils::OutputFormat::None.as_ref(), short, long)]
-    output: plugin::resources::utils::OutputFormat,
+    /// The Output, viz wide, yaml, json, custom-columns=<NAME:.path,..>, jsonpath=<template>.
+    /// Defaults to the output of the context, if any, and to tabular otherwise.
+    #[clap(global = true, short, long)]
+    output: Option<plugin::resources::utils::OutputFormat>,
 
     /// Trace rest requests to the Jaeger endpoint agent.
     #[clap(global = true, long, short)]
     jaeger: Option<String>,
 
-    /// Timeout for the REST operations.
-    #[clap(long, short, default_value = "10s")]
-    timeout: humantime::Duration,
+    /// Timeout for the REST operations, defaults to 10s.
+    #[clap(long, short)]
+    timeout: Option<humantime::Duration>,
+
+    /// The context of the plugin config file to use, instead of the current context.
+    #[clap(global = true, long)]
+    context: Option<String>,
 }
+
+/// Timeout for the REST operations, if neither the command line nor the context specify one.
+const DEFAULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
 impl CliArgs {
     fn args() -> Self {
         CliArgs::parse()
@@ -50,19 +67,73 @@ impl CliArgs {
 
 #[tokio::main]
 async fn main() {
-    plugin::init_tracing(&CliArgs::args().jaeger);
+    let cli_args = CliArgs::args();
+
+    // The config operations only need the config file.
+    if let Operations::Config(operation) = &cli_args.operations {
+        if let Err(e) = PluginConfig::default_path().and_then(|path| operation.execute(&path)) {
+            println!("Failed to update the plugin config. Error {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let config = match load_config() {
+        Ok(config) => config,
+        Err(e) => {
+            println!("Failed to load the plugin config. Error {}", e);
+            std::process::exit(1);
+        }
+    };
+    let context = match config.select(cli_args.context.as_deref()) {
+        Ok(context) => context.cloned(),
+        Err(e) => {
+            println!("Failed to select the plugin context. Error {}", e);
+            std::process::exit(1);
+        }
+    };
 
-    execute(CliArgs::args()).await;
+    let jaeger = cli_args
+        .jaeger
+        .clone()
+        .or_else(|| context.as_ref().and_then(|context| context.jaeger.clone()));
+    plugin::init_tracing(&jaeger);
+
+    execute(cli_args, context).await;
 
     global::shutdown_tracer_provider();
 }
 
-async fn execute(cli_args: CliArgs) {
+/// Load the plugin config file from its default location.
+fn load_config() -> Result<PluginConfig> {
+    PluginConfig::load(&PluginConfig::default_path()?)
+}
+
+async fn execute(cli_args: CliArgs, context: Option<Context>) {
+    // Command line flags take precedence over the defaults of the context.
+    let defaults = match ContextDefaults::new(context.as_ref()) {
+        Ok(defaults) => defaults,
+        Err(e) => {
+            println!("Failed to apply the plugin context. Error {}", e);
+            std::process::exit(1);
+        }
+    };
+    let timeout = cli_args
+        .timeout
+        .map(|timeout| *timeout)
+        .or(defaults.timeout)
+        .unwrap_or(DEFAULT_TIMEOUT);
+    let output = cli_args
+        .output
+        .or(defaults.output)
+        .unwrap_or(plugin::resources::utils::OutputFormat::None);
+
     // Initialise the REST client.
     if let Err(e) = init_rest(
-        cli_args.rest.clone(),
-        *cli_args.timeout,
+        cli_args.rest.clone().or(defaults.rest),
+        timeout,
         cli_args.kube_config_path.clone(),
+        defaults.auth,
     )
     .await
     {
@@ -72,24 +143,91 @@ async fn execute(cli_args: CliArgs) {
 
     // Perform the operations based on the subcommand, with proper output format.
     match cli_args.operations {
-        Operations::Get(resource) => match resource {
-            GetResources::Volumes => volume::Volumes::list(&cli_args.output).await,
-            GetResources::Volume { id } => volume::Volume::get(&id, &cli_args.output).await,
-            GetResources::VolumeReplicaTopology { id } => {
-                volume::Volume::topology(&id, &cli_args.output).await
+        Operations::Get(args) => {
+            let watch = args.watch();
+            match args.resource {
+                GetResources::Volumes => volume::Volumes::list(&output, watch).await,
+                GetResources::Volume { id } => volume::Volume::get(&id, &output, watch).await,
+                GetResources::VolumeReplicaTopology { id } => {
+                    volume::Volume::topology(&id, &output, watch).await
+                }
+                GetResources::VolumeRebuildProgress { id } => {
+                    volume::Volume::rebuild_progress(&id, &output, watch).await
+                }
+                GetResources::Pools => pool::Pools::list(&output, watch).await,
+                GetResources::Pool { id } => pool::Pool::get(&id, &output, watch).await,
+                GetResources::Nodes => node::Nodes::list(&output, watch).await,
+                GetResources::Node { id } => node::Node::get(&id, &output, watch).await,
+                GetResources::Replicas => replica::Replicas::list(&output, watch).await,
+                GetResources::Replica { id } => replica::Replica::get(&id, &output, watch).await,
+                GetResources::Nexuses => nexus::Nexuses::list(&output, watch).await,
+                GetResources::Nexus { id } => nexus::Nexus::get(&id, &output, watch).await,
+                GetResources::BlockDevices(args) => {
+                    blockdevice::BlockDevice::get(&args, &output, watch).await
+                }
+                GetResources::Specs => specs::Specs::list(&output, watch).await,
             }
-            GetResources::Pools => pool::Pools::list(&cli_args.output).await,
-            GetResources::Pool { id } => pool::Pool::get(&id, &cli_args.output).await,
-            GetResources::Nodes => node::Nodes::list(&cli_args.output).await,
-            GetResources::Node { id } => node::Node::get(&id, &cli_args.output).await,
-        },
+        }
         Operations::Scale(resource) => match resource {
             ScaleResources::Volume { id, replica_count } => {
-                volume::Volume::scale(&id, replica_count, &cli_args.output).await
+                volume::Volume::scale(&id, replica_count, &output).await
             }
         },
+        Operations::Create(resource) => match resource {
+            CreateResources::Pool(args) => pool::Pool::create(&args, &output).await,
+        },
+        Operations::Delete(resource) => match resource {
+            DeleteResources::Pool { id, force } => pool::Pool::delete(&id, force).await,
+        },
+        Operations::Label(resource) => match resource {
+            LabelResources::Pool {
+                id,
+                labels,
+                overwrite,
+            } => pool::Pool::label(&id, &labels, overwrite, &output).await,
+        },
+        Operations::Describe(resource) => match resource {
+            DescribeResources::Volume { id } => volume::Volume::describe(&id, &output).await,
+            DescribeResources::Pool { id } => pool::Pool::describe(&id, &output).await,
+            DescribeResources::Node { id } => node::Node::describe(&id, &output).await,
+        },
+        Operations::Cordon(resource) => match resource {
+            CordonResources::Node { id } => node::Node::cordon(&id, &output).await,
+        },
+        Operations::Uncordon(resource) => match resource {
+            UncordonResources::Node { id } => node::Node::uncordon(&id, &output).await,
+        },
+        Operations::Drain(resource) => match resource {
+            DrainResources::Node(args) => {
+                let exit_code = node::Node::drain(&args, &output).await;
+                if exit_code != 0 {
+                    std::process::exit(exit_code);
+                }
+            }
+        },
+        Operations::Remove(resource) => match resource {
+            RemoveResources::VolumeReplica { id, replica } => {
+                volume::Volume::remove_replica(&id, &replica, &output).await
+            }
+        },
+        Operations::Move(resource) => match resource {
+            MoveResources::VolumeReplica(args) => {
+                let exit_code = volume::Volume::move_replica(&args, &output).await;
+                if exit_code != 0 {
+                    std::process::exit(exit_code);
+                }
+            }
+        },
+        Operations::Usage(args) => usage::Usage::report(&args, &output).await,
+        Operations::Check(args) => {
+            let exit_code = check::Check::run(&args, &output).await;
+            if exit_code != 0 {
+                std::process::exit(exit_code);
+            }
+        }
         Operations::Dump(resources) => {
             let _ignore = resources
+                .with_default_namespace(defaults.namespace)
                 .dump(cli_args.kube_config_path)
                 .await
                 .map_err(|_e| {
@@ -98,21 +236,50 @@ async fn execute(cli_args: CliArgs) {
                 });
             println!("Completed collection of dump !!");
         }
+        // Handled before the REST client is initialised.
+        Operations::Config(_) => {}
     };
 }
 
+/// The defaults of the plugin context, used for the flags which are not given.
+#[derive(Default)]
+struct ContextDefaults {
+    rest: Option<Url>,
+    auth: RestAuth,
+    namespace: Option<String>,
+    output: Option<plugin::resources::utils::OutputFormat>,
+    timeout: Option<std::time::Duration>,
+}
+
+impl ContextDefaults {
+    fn new(context: Option<&Context>) -> Result<Self> {
+        let context = match context {
+            Some(context) => context,
+            None => return Ok(Self::default()),
+        };
+        Ok(Self {
+            rest: context.rest_url()?,
+            auth: context.rest_auth()?,
+            namespace: context.namespace.clone(),
+            output: context.output_format()?,
+            timeout: context.timeout()?.map(|timeout| *timeout),
+        })
+    }
+}
+
 /// Initialise the REST client.
 async fn init_rest(
     url: Option<Url>,
     timeout: std::time::Duration,
     kube_config_path: Option<PathBuf>,
+    auth: RestAuth,
 ) -> Result<()> {
     // Use the supplied URL if there is one otherwise obtain one from the kubeconfig file.
     let url = match url {
         Some(url) => url,
         None => url_from_kubeconfig(kube_config_path).await?,
     };
-    RestClient::init(url, timeout)
+    RestClient::init_with_auth(url, timeout, auth)
 }
 
 /// Get the URL of the master node from the kubeconfig file.
@@ -1,16 +1,59 @@
 use clap::Parser;
-use plugin::resources::{GetResources, ScaleResources};
+use plugin::{
+    config::ConfigOperations,
+    resources::{
+        check::CheckArgs, usage::UsageArgs, CordonResources, CreateResources, DeleteResources,
+        DescribeResources, DrainResources, GetArgs, LabelResources, MoveResources, RemoveResources,
+        ScaleResources, UncordonResources,
+    },
+};
 use supportability::DumpArgs;
 
 /// The types of operations that are supported.
 #[derive(Parser, Debug)]
 pub enum Operations {
     /// 'Get' resources.
-    #[clap(subcommand)]
-    Get(GetResources),
+    Get(GetArgs),
     /// 'Scale' resources.
     #[clap(subcommand)]
     Scale(ScaleResources),
+    /// 'Create' resources.
+    #[clap(subcommand)]
+    Create(CreateResources),
+    /// 'Delete' resources.
+    #[clap(subcommand)]
+    Delete(DeleteResources),
+    /// 'Label' resources.
+    #[clap(subcommand)]
+    Label(LabelResources),
+    /// 'Describe' resources.
+    #[clap(subcommand)]
+    Describe(DescribeResources),
+    /// 'Cordon' resources, so they are not used for new replicas and volume targets.
+    #[clap(subcommand)]
+    Cordon(CordonResources),
+    /// 'Uncordon' resources.
+    #[clap(subcommand)]
+    Uncordon(UncordonResources),
+    /// 'Drain' resources, moving their replicas elsewhere.
+    #[clap(subcommand)]
+    Drain(DrainResources),
+    /// 'Remove' resources, eg: a replica from a volume.
+    #[clap(subcommand)]
+    Remove(RemoveResources),
+    /// 'Move' resources, eg: a replica of a volume to another pool.
+    #[clap(subcommand)]
+    Move(MoveResources),
+    /// Report the storage capacity and usage of the cluster.
+    #[clap(alias = "top")]
+    Usage(UsageArgs),
+    /// Check the consistency of the specs and the states of the resources.
+    /// Exits with 1 if warnings are found, 2 if errors are found and 3 if the check fails.
+    #[clap(alias = "doctor")]
+    Check(CheckArgs),
     /// `Dump` resources.
     Dump(DumpArgs),
+    /// Manage the contexts of the plugin config file.
+    #[clap(subcommand)]
+    Config(ConfigOperations),
 }
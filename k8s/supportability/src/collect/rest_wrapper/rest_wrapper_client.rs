@@ -33,8 +33,15 @@ static REST_CLIENT: OnceCell<RestClient> = OnceCell::new();
 
 impl RestClient {
     pub fn new(time_duration: std::time::Duration) -> Result<&'static Self, RestClientError> {
-        let url = plugin::rest_wrapper::RestClient::get_or_panic().url();
-        let cfg = clients::tower::Configuration::new(url.clone(), time_duration, None, None, true)?;
+        let plugin_client = plugin::rest_wrapper::RestClient::get_or_panic();
+        let auth = plugin_client.auth();
+        let cfg = clients::tower::Configuration::new(
+            plugin_client.url().clone(),
+            time_duration,
+            auth.bearer_token.clone(),
+            auth.ca_certificate.as_deref(),
+            true,
+        )?;
         REST_CLIENT.get_or_init(|| RestClient {
             client: clients::tower::ApiClient::new(cfg),
         });
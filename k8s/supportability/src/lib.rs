@@ -45,10 +45,13 @@ pub struct SupportArgs {
     output_directory_path: String,
 
     /// Kubernetes namespace of mayastor service, defaults to mayastor
-    #[clap(global = true, long, short = 'n', default_value = "mayastor")]
-    namespace: String,
+    #[clap(global = true, long, short = 'n')]
+    namespace: Option<String>,
 }
 
+/// The namespace of the mayastor services, if none is specified.
+const DEFAULT_NAMESPACE: &str = "mayastor";
+
 /// Supportability - collects state & log information of services and dumps it to a tar file.
 #[derive(Debug, Clone, clap::Args)]
 #[clap(
@@ -62,6 +65,14 @@ pub struct DumpArgs {
 }
 
 impl DumpArgs {
+    /// Use the given namespace if no namespace was specified on the command line.
+    pub fn with_default_namespace(mut self, namespace: Option<String>) -> Self {
+        if self.args.namespace.is_none() {
+            self.args.namespace = namespace;
+        }
+        self
+    }
+
     /// Execute the dump of the specified resources.
     pub async fn dump(self, kube_config: Option<PathBuf>) -> anyhow::Result<()> {
         self.args
@@ -104,7 +115,9 @@ impl SupportArgs {
         let mut config = DumpConfig {
             rest_client,
             output_directory: cli_args.output_directory_path,
-            namespace: cli_args.namespace,
+            namespace: cli_args
+                .namespace
+                .unwrap_or_else(|| DEFAULT_NAMESPACE.to_string()),
             loki_uri: cli_args.loki_endpoint,
             etcd_uri: cli_args.etcd_endpoint,
             since: cli_args.since,
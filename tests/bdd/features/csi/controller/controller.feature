@@ -10,12 +10,18 @@ Scenario: get controller capabilities
 Scenario: get overall storage capacity
     Given 2 Io-Engine nodes with one pool on each node
     When a GetCapacity request is sent to the controller
-    Then CSI controller should report overall capacity equal to aggregated sizes of the pools
+    Then CSI controller should report overall capacity equal to aggregated free space of the pools
+    And the maximum volume size should be the free space of the largest pool
 
 Scenario: get node storage capacity
     Given 2 Io-Engine nodes with one pool on each node
     When GetCapacity request with node name is sent to the controller
-    Then CSI controller should report capacity for target node
+    Then CSI controller should report the overall capacity for each node
+
+Scenario: get storage capacity for a 2 replica volume
+    Given 2 Io-Engine nodes with one pool on each node
+    When a GetCapacity request for a 2 replica volume is sent to the controller
+    Then CSI controller should report capacity equal to free space of the smallest pool
 
 Scenario: create 1 replica nvmf volume
     Given 2 Io-Engine nodes with one pool on each node
@@ -105,10 +111,57 @@ Scenario: create 1 replica local nvmf volume
 Scenario: unpinned volume creation
     Given 2 Io-Engine nodes with one pool on each node
     When a CreateVolume request is sent to create a 1 replica nvmf volume (local=false)
-    Then volume creation should fail with invalid argument
+    Then no topology restrictions should be imposed to the unpinned volume
+    And the target of the unpinned volume should be placed on the node of its replica
 
     Given 2 Io-Engine nodes with one pool on each node
     When a CreateVolume request is sent to create a 1 replica nvmf volume (local unset)
+    Then the volume should be created as a local volume
+
+Scenario: volume creation with filesystem options
+    Given 2 Io-Engine nodes with one pool on each node
+    When a CreateVolume request is sent with filesystem options
+    Then the normalised filesystem options should be recorded in the volume context
+
+Scenario: volume creation with zone topology
+    Given 2 Io-Engine nodes with one pool on each node
+    When a CreateVolume request is sent with zone accessibility requirements
+    Then the volume should only be allowed on the nodes in the zone of the chosen node
+    And the accessible topology of the volume should include the zone
+
+Scenario: volume creation with invalid filesystem options
+    Given 2 Io-Engine nodes with one pool on each node
+    When a CreateVolume request is sent with mkfs options but without a filesystem type
+    Then volume creation should fail with invalid argument
+
+    Given 2 Io-Engine nodes with one pool on each node
+    When a CreateVolume request is sent with a reserved default mount option
+    Then volume creation should fail with invalid argument
+
+    Given 2 Io-Engine nodes with one pool on each node
+    When a CreateVolume request is sent with an unknown filesystem check policy
+    Then volume creation should fail with invalid argument
+
+Scenario: volume creation with invalid nvme parameters
+    Given 2 Io-Engine nodes with one pool on each node
+    When a CreateVolume request is sent with no nvme IO queues
+    Then volume creation should fail with invalid argument
+
+    Given 2 Io-Engine nodes with one pool on each node
+    When a CreateVolume request is sent with a reconnect delay exceeding the controller loss timeout
+    Then volume creation should fail with invalid argument
+
+    Given 2 Io-Engine nodes with one pool on each node
+    When a CreateVolume request is sent with an nvme keep alive timeout
+    Then volume creation should fail with invalid argument
+
+Scenario: volume creation with invalid iscsi parameters
+    Given 2 Io-Engine nodes with one pool on each node
+    When a CreateVolume request is sent with iscsi parameters for an nvmf volume
+    Then volume creation should fail with invalid argument
+
+    Given 2 Io-Engine nodes with one pool on each node
+    When a CreateVolume request is sent with no iscsi replacement timeout
     Then volume creation should fail with invalid argument
 
 Scenario: list local volume
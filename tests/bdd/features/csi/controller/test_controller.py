@@ -41,6 +41,7 @@ VOLUME2_SIZE = 1024 * 1024 * 22
 VOLUME3_SIZE = 1024 * 1024 * 28
 VOLUME4_SIZE = 1024 * 1024 * 32
 K8S_HOSTNAME = "kubernetes.io/hostname"
+ZONE_KEY = "topology.kubernetes.io/zone"
 IO_ENGINE_SELECTOR_KEY = "openebs.io/engine"
 IO_ENGINE_SELECTOR_VALUE = "io-engine"
 
@@ -91,17 +92,46 @@ def test_node_capacity(setup):
     """get node capacity"""
 
 
+@scenario("controller.feature", "get storage capacity for a 2 replica volume")
+def test_2_replica_capacity(setup):
+    """get 2 replica volume capacity"""
+
+
 @scenario("controller.feature", "create 1 replica nvmf volume")
 def test_create_1_replica_nvmf_volume(setup):
     """create 1 replica nvmf volume"""
 
 
-@pytest.mark.skip(reason="local parameter not supported")
 @scenario("controller.feature", "unpinned volume creation")
 def test_unpinned_volume_creation(setup):
     """unpinned volume creation."""
 
 
+@scenario("controller.feature", "volume creation with filesystem options")
+def test_volume_creation_with_filesystem_options(setup):
+    """volume creation with filesystem options."""
+
+
+@scenario("controller.feature", "volume creation with zone topology")
+def test_volume_creation_with_zone_topology(setup):
+    """volume creation with zone topology."""
+
+
+@scenario("controller.feature", "volume creation with invalid filesystem options")
+def test_volume_creation_with_invalid_filesystem_options(setup):
+    """volume creation with invalid filesystem options."""
+
+
+@scenario("controller.feature", "volume creation with invalid nvme parameters")
+def test_volume_creation_with_invalid_nvme_parameters(setup):
+    """volume creation with invalid nvme parameters."""
+
+
+@scenario("controller.feature", "volume creation with invalid iscsi parameters")
+def test_volume_creation_with_invalid_iscsi_parameters(setup):
+    """volume creation with invalid iscsi parameters."""
+
+
 @scenario("controller.feature", "volume creation idempotency")
 def test_volume_creation_idempotency(setup):
     """volume creation idempotency"""
@@ -319,6 +349,145 @@ def a_createvolume_request_is_sent_to_create_a_1_replica_nvmf_volume_local_unset
     """a CreateVolume request is sent to create a 1 replica nvmf volume (local unset)."""
 
 
+@when("a CreateVolume request is sent with filesystem options")
+def a_createvolume_request_is_sent_with_filesystem_options(
+    _create_1_replica_nvmf_volume_fs_options,
+):
+    """a CreateVolume request is sent with filesystem options."""
+
+
+@when("a CreateVolume request is sent with zone accessibility requirements")
+def a_createvolume_request_is_sent_with_zone_accessibility_requirements(
+    _create_1_replica_nvmf_volume_zone_topology,
+):
+    """a CreateVolume request is sent with zone accessibility requirements."""
+
+
+@when("a CreateVolume request is sent with mkfs options but without a filesystem type")
+def a_createvolume_request_is_sent_with_mkfs_options_but_without_a_filesystem_type(
+    context,
+):
+    """a CreateVolume request is sent with mkfs options but without a filesystem type."""
+    context["create_result"] = csi_create_1_replica_nvmf_volume_parameters(
+        {"mkfsOptions": "-m 1"}
+    )
+
+
+@when("a CreateVolume request is sent with a reserved default mount option")
+def a_createvolume_request_is_sent_with_a_reserved_default_mount_option(context):
+    """a CreateVolume request is sent with a reserved default mount option."""
+    context["create_result"] = csi_create_1_replica_nvmf_volume_parameters(
+        {"fsType": "ext4", "mountOptions": "noatime,ro"}
+    )
+
+
+@when("a CreateVolume request is sent with an unknown filesystem check policy")
+def a_createvolume_request_is_sent_with_an_unknown_filesystem_check_policy(context):
+    """a CreateVolume request is sent with an unknown filesystem check policy."""
+    context["create_result"] = csi_create_1_replica_nvmf_volume_parameters(
+        {"fsType": "ext4", "fsCheck": "repair"}
+    )
+
+
+@when("a CreateVolume request is sent with no nvme IO queues")
+def a_createvolume_request_is_sent_with_no_nvme_io_queues(context):
+    """a CreateVolume request is sent with no nvme IO queues."""
+    context["create_result"] = csi_create_1_replica_nvmf_volume_parameters(
+        {"nvmeNrIoQueues": "0"}
+    )
+
+
+@when(
+    "a CreateVolume request is sent with a reconnect delay exceeding the controller loss timeout"
+)
+def a_createvolume_request_is_sent_with_a_reconnect_delay_exceeding_the_controller_loss_timeout(
+    context,
+):
+    """a CreateVolume request is sent with a reconnect delay exceeding the controller loss timeout."""
+    context["create_result"] = csi_create_1_replica_nvmf_volume_parameters(
+        {"nvmeCtrlLossTmo": "5", "nvmeReconnectDelay": "10"}
+    )
+
+
+@when("a CreateVolume request is sent with an nvme keep alive timeout")
+def a_createvolume_request_is_sent_with_an_nvme_keep_alive_timeout(context):
+    """a CreateVolume request is sent with an nvme keep alive timeout."""
+    context["create_result"] = csi_create_1_replica_nvmf_volume_parameters(
+        {"nvmeKeepAliveTmo": "10"}
+    )
+
+
+@when("a CreateVolume request is sent with iscsi parameters for an nvmf volume")
+def a_createvolume_request_is_sent_with_iscsi_parameters_for_an_nvmf_volume(context):
+    """a CreateVolume request is sent with iscsi parameters for an nvmf volume."""
+    context["create_result"] = csi_create_1_replica_nvmf_volume_parameters(
+        {"iscsiNoopOutInterval": "5"}
+    )
+
+
+@when("a CreateVolume request is sent with no iscsi replacement timeout")
+def a_createvolume_request_is_sent_with_no_iscsi_replacement_timeout(context):
+    """a CreateVolume request is sent with no iscsi replacement timeout."""
+    context["create_result"] = csi_create_1_replica_nvmf_volume_parameters(
+        {"protocol": "iscsi", "iscsiReplacementTimeout": "0"}
+    )
+
+
+@pytest.fixture
+def _create_1_replica_nvmf_volume_zone_topology(context):
+    csi_delete_1_replica_nvmf_volume_local()
+
+    def topology(node, zone):
+        return pb.Topology(segments={K8S_HOSTNAME: node, ZONE_KEY: zone})
+
+    result = csi_create_1_replica_nvmf_volume_parameters(
+        {},
+        pb.TopologyRequirement(
+            requisite=[topology(NODE1, "zone-a"), topology(NODE2, "zone-b")],
+            preferred=[topology(NODE2, "zone-b"), topology(NODE1, "zone-a")],
+        ),
+    )
+    context["create_result"] = result
+    yield result
+    csi_delete_1_replica_nvmf_volume_local()
+
+
+@then("the normalised filesystem options should be recorded in the volume context")
+def the_normalised_filesystem_options_should_be_recorded_in_the_volume_context(
+    context,
+):
+    """the normalised filesystem options should be recorded in the volume context."""
+    volume_context = context["create_result"].volume.volume_context
+    assert volume_context["fsType"] == "xfs"
+    assert volume_context["mkfsOptions"] == "-m reflink=1"
+    assert volume_context["mountOptions"] == "noatime,nodiscard"
+
+
+@then("the volume should only be allowed on the nodes in the zone of the chosen node")
+def the_volume_should_only_be_allowed_on_the_nodes_in_the_zone_of_the_chosen_node(
+    context,
+):
+    """the volume should only be allowed on the nodes in the zone of the chosen node."""
+    assert not isinstance(
+        context["create_result"], grpc.RpcError
+    ), "Expect the volume to be created"
+    volume = ApiClient.volumes_api().get_volume(VOLUME2_UUID)
+    explicit = volume.spec.topology.node_topology.explicit
+    assert explicit.allowed_nodes == [NODE2], "Volume allowed nodes mismatch"
+    assert explicit.preferred_nodes == [NODE2], "Volume preferred nodes mismatch"
+    assert volume.spec.labels == {
+        f"topology-segment/{ZONE_KEY}": "zone-b"
+    }, "Volume topology labels mismatch"
+
+
+@then("the accessible topology of the volume should include the zone")
+def the_accessible_topology_of_the_volume_should_include_the_zone(context):
+    """the accessible topology of the volume should include the zone."""
+    topology = context["create_result"].volume.accessible_topology
+    assert len(topology) == 1, "Volume must have exactly one accessible topology"
+    assert topology[0].segments.get(ZONE_KEY) == "zone-b", "Volume zone mismatch"
+
+
 @then("volume creation should fail with invalid argument")
 def volume_creation_should_fail_with_invalid_argument(context):
     """volume creation should fail with invalid argument."""
@@ -339,6 +508,47 @@ def republish_volume_on_a_different_node(populate_published_volume):
     return e.value
 
 
+@then("no topology restrictions should be imposed to the unpinned volume")
+def check_unpinned_volume_topology(context):
+    """no topology restrictions should be imposed to the unpinned volume."""
+    volume = context["create_result"].volume
+    assert (
+        len(volume.accessible_topology) == 0
+    ), "Unpinned volume has topology restrictions"
+
+
+@then("the target of the unpinned volume should be placed on the node of its replica")
+def check_unpinned_volume_publish():
+    """the target of the unpinned volume should be placed on the node of its replica."""
+    volume = ApiClient.volumes_api().get_volume(VOLUME2_UUID)
+    replica_node = list(volume.state.replica_topology.values())[0].node
+    app_node = NODE1 if replica_node == NODE2 else NODE2
+
+    # the target is placed by the control plane rather than on the node of the workload
+    do_publish_volume(VOLUME2_UUID, app_node)
+    volume = ApiClient.volumes_api().get_volume(VOLUME2_UUID)
+    assert volume.state["target"]["node"] == replica_node, "Volume target node mismatch"
+
+    # only the node of the target may unpublish the volume
+    with pytest.raises(grpc.RpcError) as error:
+        do_unpublish_volume(VOLUME2_UUID, app_node)
+    assert error.value.code() == grpc.StatusCode.NOT_FOUND, "Unexpected gRPC error code"
+    volume = ApiClient.volumes_api().get_volume(VOLUME2_UUID)
+    assert (
+        volume.state["target"]["node"] == replica_node
+    ), "Volume should still be published"
+
+    do_unpublish_volume(VOLUME2_UUID, replica_node)
+    volume = ApiClient.volumes_api().get_volume(VOLUME2_UUID)
+    assert not hasattr(volume.spec, "target"), "Volume should not be published"
+
+
+@then("the volume should be created as a local volume")
+def check_volume_created_as_local(context):
+    """the volume should be created as a local volume."""
+    check_local_volume_topology(context["create_result"])
+
+
 @then("a new local volume of requested size should be successfully created")
 def check_1_replica_local_nvmf_volume(create_1_replica_local_nvmf_volume):
     assert (
@@ -655,25 +865,34 @@ def check_get_controller_capabilities(get_caps_request):
         assert c in reported_capabilities, "Capability is missing: %s" % str(c)
 
 
+def pools_free_space():
+    pool_api = ApiClient.pools_api()
+    pools = [pool_api.get_pool(p) for p in [POOL1_UUID, POOL2_UUID]]
+    return [p.state.capacity - p.state.used for p in pools]
+
+
 @when(
     "a GetCapacity request is sent to the controller",
     target_fixture="get_overall_capacity",
 )
 def get_overall_capacity(two_pools):
-    capacity = csi_rpc_handle().controller.GetCapacity(pb.GetCapacityRequest())
-    return [
-        two_pools[0].state.capacity + two_pools[1].state.capacity,
-        capacity.available_capacity,
-    ]
+    return csi_rpc_handle().controller.GetCapacity(pb.GetCapacityRequest())
 
 
 @then(
-    "CSI controller should report overall capacity equal to aggregated sizes of the pools"
+    "CSI controller should report overall capacity equal to aggregated free space of the pools"
 )
 def check_get_overall_capacity(get_overall_capacity):
-    assert (
-        get_overall_capacity[0] == get_overall_capacity[1]
-    ), "Overall capacity does not match pool sizes"
+    assert get_overall_capacity.available_capacity == sum(
+        pools_free_space()
+    ), "Overall capacity does not match pool free space"
+
+
+@then("the maximum volume size should be the free space of the largest pool")
+def check_get_maximum_volume_size(get_overall_capacity):
+    assert get_overall_capacity.maximum_volume_size.value == max(
+        pools_free_space()
+    ), "Maximum volume size does not match the free space of the largest pool"
 
 
 @when(
@@ -692,15 +911,37 @@ def get_node_capacity(two_pools):
     return capacity
 
 
-@then("CSI controller should report capacity for target node")
+@then("CSI controller should report the overall capacity for each node")
 def check_get_node_capacity(get_nodes_capacity):
-    pool_api = ApiClient.pools_api()
+    # The replicas of a volume are not necessarily placed on the node of its workload.
+    free_space = sum(pools_free_space())
 
-    for i, p in enumerate([POOL1_UUID, POOL2_UUID]):
-        pool = pool_api.get_pool(p)
+    for capacity in get_nodes_capacity:
         assert (
-            pool.state.capacity == get_nodes_capacity[i]
-        ), "Node pool size does not match reported node capacity"
+            capacity == free_space
+        ), "Reported node capacity does not match pool free space"
+
+
+@when(
+    "a GetCapacity request for a 2 replica volume is sent to the controller",
+    target_fixture="get_2_replica_capacity",
+)
+def get_2_replica_capacity(two_pools):
+    return csi_rpc_handle().controller.GetCapacity(
+        pb.GetCapacityRequest(parameters={"repl": "2"})
+    )
+
+
+@then("CSI controller should report capacity equal to free space of the smallest pool")
+def check_get_2_replica_capacity(get_2_replica_capacity):
+    # Each replica is placed on a different node, so the smallest pool is the limit.
+    free_space = min(pools_free_space())
+    assert (
+        get_2_replica_capacity.available_capacity == free_space
+    ), "2 replica capacity does not match the free space of the smallest pool"
+    assert (
+        get_2_replica_capacity.maximum_volume_size.value == free_space
+    ), "2 replica maximum volume size does not match the free space of the smallest pool"
 
 
 def csi_create_1_replica_nvmf_volume1():
@@ -787,6 +1028,30 @@ def csi_create_1_replica_nvmf_volume_local(local_set=False, local=False):
         return e
 
 
+def csi_create_1_replica_nvmf_volume_parameters(
+    extra_parameters, accessibility_requirements=None
+):
+    capacity = pb.CapacityRange(required_bytes=VOLUME2_SIZE, limit_bytes=0)
+    parameters = {
+        "protocol": "nvmf",
+        "ioTimeout": "30",
+        "repl": "1",
+    }
+    parameters.update(extra_parameters)
+
+    req = pb.CreateVolumeRequest(
+        name=PVC_VOLUME2_NAME,
+        capacity_range=capacity,
+        parameters=parameters,
+        accessibility_requirements=accessibility_requirements,
+    )
+
+    try:
+        return csi_rpc_handle().controller.CreateVolume(req)
+    except grpc.RpcError as e:
+        return e
+
+
 def check_nvmf_target(uri):
     """Check whether NVMF target is discoverable via target URI"""
     # Make sure URI represents nvmf target.
@@ -877,6 +1142,21 @@ def _create_1_replica_nvmf_volume_local_unset(context):
     csi_delete_1_replica_nvmf_volume_local()
 
 
+@pytest.fixture
+def _create_1_replica_nvmf_volume_fs_options(context):
+    csi_delete_1_replica_nvmf_volume_local()
+    result = csi_create_1_replica_nvmf_volume_parameters(
+        {
+            "fsType": "xfs",
+            "mkfsOptions": " -m  reflink=1 ",
+            "mountOptions": "noatime, nodiscard",
+        }
+    )
+    context["create_result"] = result
+    yield result
+    csi_delete_1_replica_nvmf_volume_local()
+
+
 @pytest.fixture
 def _create_2_volumes_1_replica():
     vol1 = csi_create_1_replica_nvmf_volume1()
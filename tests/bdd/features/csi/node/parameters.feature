@@ -11,4 +11,13 @@ Feature: CSI Node plugin parameters
       | io | total |
       | 1  |   2   |
       | 4  |   5   |
-      | 20 |  21   |
\ No newline at end of file
+      | 20 |  21   |
+
+  Scenario Outline: stage volume request with nvme nr io queues from the publish context
+    Given a csi node plugin with 1 IO queues configured
+    When staging a volume with <volume_io> IO queues
+    Then the nvme device should report <total> TOTAL queues
+    Examples:
+      | volume_io | total |
+      | 2         |   3   |
+      | 4         |   5   |
@@ -126,9 +126,10 @@ def test_node_info(csi_instance):
 
 def test_node_capabilities(csi_instance):
     response = csi_instance.node.NodeGetCapabilities(pb.NodeGetCapabilitiesRequest())
-    assert pb.NodeServiceCapability.RPC.Type.STAGE_UNSTAGE_VOLUME in [
-        cap.rpc.type for cap in response.capabilities
-    ]
+    types = [cap.rpc.type for cap in response.capabilities]
+    assert pb.NodeServiceCapability.RPC.Type.STAGE_UNSTAGE_VOLUME in types
+    assert pb.NodeServiceCapability.RPC.Type.GET_VOLUME_STATS in types
+    assert pb.NodeServiceCapability.RPC.Type.VOLUME_CONDITION in types
 
 
 @pytest.fixture(scope="module")
@@ -198,7 +199,7 @@ def test_get_volume_stats(csi_instance, published_nexus, volume_id, target_path)
         csi_instance.node.NodeGetVolumeStats(
             pb.NodeGetVolumeStatsRequest(volume_id=volume_id, volume_path=target_path)
         )
-    assert error.value.code() == grpc.StatusCode.UNIMPLEMENTED
+    assert error.value.code() == grpc.StatusCode.NOT_FOUND
 
 
 @pytest.fixture(params=["multi-node-reader-only", "multi-node-single-writer"])
@@ -406,6 +407,75 @@ def test_stage_mount_volume(
     )
 
 
+def test_get_staged_mount_volume_stats(
+    csi_instance, volume_id, staging_target_path, staged_mount_volume
+):
+    response = csi_instance.node.NodeGetVolumeStats(
+        pb.NodeGetVolumeStatsRequest(
+            volume_id=volume_id, volume_path=staging_target_path
+        )
+    )
+    usage = {u.unit: u for u in response.usage}
+    size = usage[pb.VolumeUsage.Unit.BYTES]
+    assert 0 < size.total <= VOLUME_SIZE
+    assert size.used + size.available <= size.total
+    assert usage[pb.VolumeUsage.Unit.INODES].total > 0
+    assert not response.volume_condition.abnormal
+
+
+def test_stage_checked_mount_volume(
+    csi_instance,
+    volume_id,
+    stage_context,
+    staging_target_path,
+    stage_mount_volume_capability,
+):
+    # the first stage creates the filesystem, which is then checked when restaged
+    for fs_check in ["none", "refuse"]:
+        csi_instance.node.NodeStageVolume(
+            pb.NodeStageVolumeRequest(
+                volume_id=volume_id,
+                publish_context=stage_context,
+                staging_target_path=staging_target_path,
+                volume_capability=stage_mount_volume_capability,
+                secrets={},
+                volume_context={"fsCheck": fs_check},
+            )
+        )
+        response = csi_instance.node.NodeGetVolumeStats(
+            pb.NodeGetVolumeStatsRequest(
+                volume_id=volume_id, volume_path=staging_target_path
+            )
+        )
+        assert not response.volume_condition.abnormal
+        csi_instance.node.NodeUnstageVolume(
+            pb.NodeUnstageVolumeRequest(
+                volume_id=volume_id, staging_target_path=staging_target_path
+            )
+        )
+
+
+def test_stage_mount_volume_invalid_fs_check(
+    csi_instance,
+    volume_id,
+    stage_context,
+    staging_target_path,
+    stage_mount_volume_capability,
+):
+    with pytest.raises(grpc.RpcError) as error:
+        csi_instance.node.NodeStageVolume(
+            pb.NodeStageVolumeRequest(
+                volume_id=volume_id,
+                publish_context=stage_context,
+                staging_target_path=staging_target_path,
+                volume_capability=stage_mount_volume_capability,
+                secrets={},
+                volume_context={"fsCheck": "repair"},
+            )
+        )
+    assert error.value.code() == grpc.StatusCode.INVALID_ARGUMENT
+
+
 def test_publish_mount_volume(
     csi_instance,
     volume_id,
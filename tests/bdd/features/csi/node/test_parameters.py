@@ -29,6 +29,14 @@ def test_stage_volume_request_with_a_specified_nvme_nr_io_queues():
     """stage volume request with a specified nvme nr io queues."""
 
 
+@scenario(
+    "parameters.feature",
+    "stage volume request with nvme nr io queues from the publish context",
+)
+def test_stage_volume_request_with_nvme_nr_io_queues_from_the_publish_context():
+    """stage volume request with nvme nr io queues from the publish context."""
+
+
 @given(parsers.parse("a csi node plugin with {io:d} IO queues configured"))
 def a_csi_node_plugin_with_io_queues_configured(io):
     """a csi node plugin with <IO> queues configured."""
@@ -44,6 +52,11 @@ def staging_a_volume(staging_a_volume):
     """staging a volume."""
 
 
+@when(parsers.parse("staging a volume with {volume_io:d} IO queues"))
+def staging_a_volume_with_io_queues(staging_a_volume_with_io_queues):
+    """staging a volume with <volume_io> IO queues."""
+
+
 @then(parsers.parse("the nvme device should report {total:d} TOTAL queues"))
 def the_nvme_device_should_report_total_queues(
     total, the_nvme_device_should_report_total_queues
@@ -62,6 +75,26 @@ def block_volume_capability():
 
 @pytest.fixture
 def staging_a_volume(staging_target_path, csi_instance, block_volume_capability):
+    yield from stage_volume(
+        staging_target_path, csi_instance, block_volume_capability, {}
+    )
+
+
+@pytest.fixture
+def staging_a_volume_with_io_queues(
+    volume_io, staging_target_path, csi_instance, block_volume_capability
+):
+    yield from stage_volume(
+        staging_target_path,
+        csi_instance,
+        block_volume_capability,
+        {"nvmeNrIoQueues": str(volume_io)},
+    )
+
+
+def stage_volume(
+    staging_target_path, csi_instance, block_volume_capability, publish_parameters
+):
     volume = ApiClient.volumes_api().put_volume(
         VOLUME_UUID, CreateVolumeBody(VolumePolicy(False), 1, 10241024)
     )
@@ -73,7 +106,7 @@ def staging_a_volume(staging_target_path, csi_instance, block_volume_capability)
     csi_instance.node.NodeStageVolume(
         pb.NodeStageVolumeRequest(
             volume_id=volume.spec.uuid,
-            publish_context={"uri": volume.state["target"]["deviceUri"]},
+            publish_context={"uri": device_uri, **publish_parameters},
             staging_target_path=staging_target_path,
             volume_capability=block_volume_capability,
             secrets={},
@@ -648,6 +648,12 @@ impl ClusterBuilder {
         self.opts = self.opts.with_store_lease_ttl(ttl);
         self
     }
+    /// With a standby core agent
+    #[must_use]
+    pub fn with_core_standby(mut self, standby: bool) -> Self {
+        self.opts = self.opts.with_core_standby(standby);
+        self
+    }
     /// Specify the node connect and request timeouts
     #[must_use]
     pub fn with_req_timeouts(mut self, connect: Duration, request: Duration) -> Self {